@@ -0,0 +1,87 @@
+use std::{sync::mpsc::{self, Receiver, RecvTimeoutError}, time::{Duration, Instant}};
+use morse_core::timing::TimingEvent;
+use rodio::cpal::{
+    self,
+    traits::{DeviceTrait, HostTrait, StreamTrait},
+    SampleFormat, SizedSample, Stream, StreamConfig,
+};
+use crate::detect::ToneDetector;
+
+/// How often the recording is checked for the end of an answer.
+const POLL: Duration = Duration::from_millis(50);
+
+/// The default audio input (a microphone near the code practice oscillator, or a line-in
+/// from the rig's sidetone), recording as mono samples for as long as it is open.
+pub struct Capture {
+    _stream: Stream,
+    samples: Receiver<Vec<f32>>,
+    pub sample_rate: u32,
+}
+
+/// Builds a stream that mixes each frame of `T` samples down to one `f32`.
+fn build<T: SizedSample + Into<f32>>(device: &cpal::Device, config: &StreamConfig, scale: f32, offset: f32)
+    -> Result<(Stream, Receiver<Vec<f32>>), cpal::BuildStreamError>
+{
+    let (sender, samples) = mpsc::channel();
+    let channels = config.channels.max(1) as usize;
+    let stream = device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            let mono = data.chunks(channels)
+                .map(|frame| frame.iter().map(|s| ((*s).into() - offset) * scale).sum::<f32>() / channels as f32)
+                .collect();
+            let _ = sender.send(mono);
+        },
+        |e| eprintln!("Error recording audio: {}", e),
+        None,
+    )?;
+    Ok((stream, samples))
+}
+
+impl Capture {
+    pub fn open() -> Result<Self, Box<dyn std::error::Error>> {
+        let device = cpal::default_host().default_input_device()
+            .ok_or("no audio input device found")?;
+        let supported = device.default_input_config()?;
+        let config: StreamConfig = supported.config();
+        let (stream, samples) = match supported.sample_format() {
+            SampleFormat::F32 => build::<f32>(&device, &config, 1.0, 0.0)?,
+            SampleFormat::I16 => build::<i16>(&device, &config, 1.0 / 32768.0, 0.0)?,
+            SampleFormat::U16 => build::<u16>(&device, &config, 1.0 / 32768.0, 32768.0)?,
+            format => return Err(format!("unsupported input sample format {}", format).into()),
+        };
+        stream.play()?;
+        Ok(Capture { _stream: stream, samples, sample_rate: config.sample_rate.0 })
+    }
+
+    /// Throws away what was recorded so far, e.g. the prompt just played.
+    pub fn clear(&self) {
+        while self.samples.try_recv().is_ok() {}
+    }
+
+    /// Records keying until `end_after` of silence follows it, or until `give_up_after`
+    /// passes without a tone. Returns the marks and spaces heard (without the final
+    /// silence) and when the last tone ended.
+    pub fn listen(&self, end_after: Duration, give_up_after: Duration) -> (Vec<TimingEvent>, Instant) {
+        self.clear();
+        let start = Instant::now();
+        let mut detector = ToneDetector::new(self.sample_rate);
+        let mut events = Vec::new();
+        loop {
+            match self.samples.recv_timeout(POLL) {
+                Ok(samples) => events.extend(detector.push(&samples)),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+            let silence = Duration::from_secs_f32(detector.silence_ms() / 1000.0);
+            if detector.heard() && silence >= end_after {
+                return (events, Instant::now() - silence);
+            }
+            if !detector.heard() && start.elapsed() >= give_up_after {
+                break;
+            }
+        }
+        events.extend(detector.finish());
+        (events, Instant::now())
+    }
+}
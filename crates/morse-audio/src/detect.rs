@@ -0,0 +1,228 @@
+use std::f32::consts::TAU;
+use morse_core::timing::TimingEvent;
+
+/// Length of the blocks the envelope is measured over.
+const BLOCK_MS: f32 = 4.0;
+/// A change of state has to last this long to count, which rides out clicks and dropouts.
+const DEBOUNCE_MS: f32 = 12.0;
+/// The envelope has to rise this far above the noise floor (about 12 dB) to be a tone.
+const MIN_CONTRAST: f32 = 4.0;
+/// Where between the noise floor and the peak the tone switches on and off again.
+const ON_THRESHOLD: f32 = 0.5;
+const OFF_THRESHOLD: f32 = 0.3;
+/// How quickly the noise floor creeps up and the peak falls back, per block.
+const FLOOR_RISE: f32 = 0.002;
+const PEAK_DECAY: f32 = 0.001;
+/// Width of the filter a tuned detector listens through, about that of a CW filter.
+const FILTER_WIDTH_HZ: f32 = 200.0;
+/// Lowest and highest pitch searched for a tone, and the step between them.
+const PITCH_SEARCH_HZ: (f32, f32, f32) = (300.0, 1500.0, 10.0);
+/// Length of the windows the search measures each pitch over; sets how wide each pitch is.
+const PITCH_WINDOW_MS: f32 = 50.0;
+/// How much of a recording the pitch is searched in and the levels are calibrated on.
+const SEARCH_SECS: f32 = 30.0;
+
+/// Two-pole band-pass filter with unity gain at its centre.
+#[derive(Debug, Clone)]
+struct BandPass {
+    b0: f32,
+    a1: f32,
+    a2: f32,
+    x: [f32; 2],
+    y: [f32; 2],
+}
+
+impl BandPass {
+    fn new(pitch_hz: f32, sample_rate: u32) -> Self {
+        let w0 = TAU * pitch_hz / sample_rate as f32;
+        let alpha = w0.sin() * FILTER_WIDTH_HZ / (2.0 * pitch_hz);
+        let a0 = 1.0 + alpha;
+        BandPass {
+            b0: alpha / a0,
+            a1: -2.0 * w0.cos() / a0,
+            a2: (1.0 - alpha) / a0,
+            x: [0.0; 2],
+            y: [0.0; 2],
+        }
+    }
+
+    fn process(&mut self, x: f32) -> f32 {
+        let y = self.b0 * (x - self.x[1]) - self.a1 * self.y[0] - self.a2 * self.y[1];
+        self.x = [x, self.x[0]];
+        self.y = [y, self.y[0]];
+        y
+    }
+}
+
+/// Power at `hz` summed over short windows of `samples` (Goertzel).
+fn power_at(samples: &[f32], hz: f32, sample_rate: u32) -> f32 {
+    let coeff = 2.0 * (TAU * hz / sample_rate as f32).cos();
+    let window = ((sample_rate as f32 * PITCH_WINDOW_MS / 1000.0) as usize).max(1);
+    samples.chunks(window)
+        .map(|chunk| {
+            let (mut s1, mut s2) = (0.0f32, 0.0f32);
+            for x in chunk {
+                (s1, s2) = (x + coeff * s1 - s2, s1);
+            }
+            s1 * s1 + s2 * s2 - coeff * s1 * s2
+        })
+        .sum()
+}
+
+/// The pitch of the strongest tone in a recording, from 300 to 1500 Hz.
+pub fn strongest_pitch(samples: &[f32], sample_rate: u32) -> Option<f32> {
+    let samples = &samples[..samples.len().min((SEARCH_SECS * sample_rate as f32) as usize)];
+    if samples.is_empty() {
+        return None;
+    }
+    let (low, high, step) = PITCH_SEARCH_HZ;
+    (0..=((high - low) / step) as usize)
+        .map(|i| low + i as f32 * step)
+        .map(|hz| (hz, power_at(samples, hz, sample_rate)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(hz, _)| hz)
+}
+
+/// Turns audio of a keyed tone into key-down and key-up durations by following its
+/// envelope. The thresholds adapt to the noise floor and the tone's level, so neither the
+/// volume nor the pitch of the oscillator needs setting up.
+#[derive(Debug, Clone)]
+pub struct ToneDetector {
+    sample_rate: u32,
+    filter: Option<BandPass>,
+    block_len: usize,
+    block_ms: f32,
+    energy: f32,
+    filled: usize,
+    floor: f32,
+    peak: f32,
+    on: bool,
+    /// Whether a tone has been heard yet; the silence before it is not reported.
+    started: bool,
+    run_ms: f32,
+    pending_ms: f32,
+}
+
+impl ToneDetector {
+    pub fn new(sample_rate: u32) -> Self {
+        let block_len = ((sample_rate as f32 * BLOCK_MS / 1000.0).round() as usize).max(1);
+        ToneDetector {
+            sample_rate,
+            filter: None,
+            block_len,
+            block_ms: block_len as f32 * 1000.0 / sample_rate as f32,
+            energy: 0.0,
+            filled: 0,
+            floor: f32::MAX,
+            peak: 0.0,
+            on: false,
+            started: false,
+            run_ms: 0.0,
+            pending_ms: 0.0,
+        }
+    }
+
+    /// Listens only around `pitch_hz`, which keeps noise and other signals in a
+    /// recording off the air out of the envelope.
+    pub fn tuned(mut self, pitch_hz: f32) -> Self {
+        self.filter = Some(BandPass::new(pitch_hz, self.sample_rate));
+        self
+    }
+
+    /// Sets the noise floor and the tone level from a recording of keying, rather than
+    /// learning them as it plays, so that a tone right at its start is heard too.
+    pub fn calibrate(&mut self, samples: &[f32]) {
+        let samples = &samples[..samples.len().min((SEARCH_SECS * self.sample_rate as f32) as usize)];
+        let mut filter = self.filter.clone();
+        let mut levels: Vec<f32> = samples.chunks(self.block_len)
+            .map(|block| {
+                let energy: f32 = block.iter()
+                    .map(|s| filter.as_mut().map_or(*s, |f| f.process(*s)).powi(2))
+                    .sum();
+                (energy / block.len() as f32).sqrt()
+            })
+            .collect();
+        if levels.is_empty() {
+            return;
+        }
+        levels.sort_by(f32::total_cmp);
+        self.floor = levels[levels.len() / 20];
+        self.peak = levels[levels.len() * 19 / 20];
+    }
+
+    /// Feeds mono samples, returning the marks and spaces that ended within them.
+    pub fn push(&mut self, samples: &[f32]) -> Vec<TimingEvent> {
+        let mut events = Vec::new();
+        for sample in samples {
+            let sample = match &mut self.filter {
+                Some(filter) => filter.process(*sample),
+                None => *sample,
+            };
+            self.energy += sample * sample;
+            self.filled += 1;
+            if self.filled == self.block_len {
+                let level = (self.energy / self.block_len as f32).sqrt();
+                self.energy = 0.0;
+                self.filled = 0;
+                events.extend(self.block(level));
+            }
+        }
+        events
+    }
+
+    fn block(&mut self, level: f32) -> Option<TimingEvent> {
+        if level < self.floor {
+            self.floor = level;
+        } else if !self.on {
+            self.floor += FLOOR_RISE * (level - self.floor);
+        }
+        if level > self.peak {
+            self.peak = level;
+        } else {
+            self.peak -= PEAK_DECAY * (self.peak - self.floor);
+        }
+
+        let tone = self.peak > self.floor.max(1e-4) * MIN_CONTRAST && {
+            let threshold = if self.on { OFF_THRESHOLD } else { ON_THRESHOLD };
+            level > self.floor + threshold * (self.peak - self.floor)
+        };
+        if tone == self.on {
+            self.run_ms += self.pending_ms + self.block_ms;
+            self.pending_ms = 0.0;
+            return None;
+        }
+        self.pending_ms += self.block_ms;
+        if self.pending_ms < DEBOUNCE_MS {
+            return None;
+        }
+        let ended = match self.on {
+            true => Some(TimingEvent::Mark(self.run_ms)),
+            false if self.started => Some(TimingEvent::Space(self.run_ms)),
+            false => None,
+        };
+        self.on = tone;
+        self.started = true;
+        self.run_ms = self.pending_ms;
+        self.pending_ms = 0.0;
+        ended
+    }
+
+    /// Whether any tone has been heard.
+    pub fn heard(&self) -> bool {
+        self.started
+    }
+
+    /// How long it has been quiet since the last tone; zero while the tone is on.
+    pub fn silence_ms(&self) -> f32 {
+        if self.on { 0.0 } else { self.run_ms + self.pending_ms }
+    }
+
+    /// Ends the recording, returning the tone still sounding, if any.
+    pub fn finish(&mut self) -> Option<TimingEvent> {
+        let mark = self.on.then_some(TimingEvent::Mark(self.run_ms + self.pending_ms));
+        self.on = false;
+        self.run_ms = 0.0;
+        self.pending_ms = 0.0;
+        mark
+    }
+}
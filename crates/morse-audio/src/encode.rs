@@ -0,0 +1,82 @@
+use std::{
+    fs::File,
+    io::{self, Cursor, Write},
+    path::Path,
+    process::{Command, Stdio},
+};
+use crate::render;
+
+/// File formats rendered audio can be saved in. OGG and MP3 are encoded by ffmpeg, which
+/// has to be installed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    Wav,
+    Ogg,
+    Mp3,
+}
+
+impl AudioFormat {
+    /// The format a file name asks for with its extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "wav" => Some(AudioFormat::Wav),
+            "ogg" | "oga" => Some(AudioFormat::Ogg),
+            "mp3" => Some(AudioFormat::Mp3),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "wav",
+            AudioFormat::Ogg => "ogg",
+            AudioFormat::Mp3 => "mp3",
+        }
+    }
+}
+
+/// Tags music players show and sort tracks by.
+#[derive(Debug, Clone, Default)]
+pub struct TrackInfo {
+    pub title: String,
+    pub album: Option<String>,
+    /// Track number and the number of tracks in the album.
+    pub track: Option<(usize, usize)>,
+}
+
+/// Saves rendered samples to `path`. WAV files are written without tags.
+pub fn save(path: &Path, samples: &[f32], format: AudioFormat, info: &TrackInfo) -> Result<(), Box<dyn std::error::Error>> {
+    let codec: &[&str] = match format {
+        AudioFormat::Wav => return Ok(render::write_wav(File::create(path)?, samples)?),
+        AudioFormat::Ogg => &["-c:a", "libvorbis", "-q:a", "4"],
+        AudioFormat::Mp3 => &["-c:a", "libmp3lame", "-q:a", "4"],
+    };
+    let mut wav = Cursor::new(Vec::new());
+    render::write_wav(&mut wav, samples)?;
+
+    let mut command = Command::new("ffmpeg");
+    command.args(["-hide_banner", "-loglevel", "error", "-y", "-f", "wav", "-i", "-"]);
+    command.arg("-metadata").arg(format!("title={}", info.title));
+    if let Some(album) = &info.album {
+        command.arg("-metadata").arg(format!("album={}", album));
+    }
+    if let Some((track, total)) = info.track {
+        command.arg("-metadata").arg(format!("track={}/{}", track, total));
+    }
+    command.args(codec).arg(path);
+    let mut ffmpeg = command.stdin(Stdio::piped()).stdout(Stdio::null()).stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => "saving OGG or MP3 needs ffmpeg, which wasn't found on the PATH".into(),
+            _ => Box::<dyn std::error::Error>::from(e),
+        })?;
+    if let Some(mut stdin) = ffmpeg.stdin.take() {
+        stdin.write_all(wav.get_ref())?;
+    }
+    let output = ffmpeg.wait_with_output()?;
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr).trim()).into());
+    }
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use std::f32::consts::TAU;
+use rand::Rng;
+use crate::render::SAMPLE_RATE;
+
+/// Slow fading of the received signal (QSB).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fading {
+    /// How far the signal drops at the bottom of a fade, in dB.
+    pub depth_db: f32,
+    /// Average time from one peak to the next, in seconds.
+    pub period_secs: f32,
+}
+
+/// Fades the signal in `samples` in and out. Two slow waves of unrelated periods are
+/// combined, so the fades don't repeat exactly; `rng` picks where in the cycle to start.
+pub fn apply(samples: &mut [f32], fading: Fading, rng: &mut impl Rng) {
+    let (phase1, phase2): (f32, f32) = (rng.random_range(0.0..TAU), rng.random_range(0.0..TAU));
+    let slow_period = fading.period_secs * 2.3;
+    for (i, sample) in samples.iter_mut().enumerate() {
+        let t = i as f32 / SAMPLE_RATE as f32;
+        let wave = 0.7 * (TAU * t / fading.period_secs + phase1).sin()
+            + 0.3 * (TAU * t / slow_period + phase2).sin();
+        // 0 at the peaks, 1 at the bottom of the deepest fades.
+        let fade = 0.5 - 0.5 * wave;
+        *sample *= 10f32.powf(-fading.depth_db * fade / 20.0);
+    }
+}
@@ -0,0 +1,89 @@
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+use morse_core::timing::{self, TimingEvent};
+use morse_platform::Os;
+
+/// Visual copy is slower than aural copy, so the LED keys at about 8 WPM.
+pub const LED_UNIT_MS: f32 = 150.0;
+
+const LEDS_DIR: &str = "/sys/class/leds";
+const LED_NAMES: [&str; 2] = ["capslock", "kbd_backlight"];
+
+/// A keyboard LED or backlight exposed by the kernel under /sys/class/leds.
+/// Writing its brightness usually needs root or a udev rule granting access.
+pub struct Led {
+    path: PathBuf,
+    max_brightness: String,
+}
+
+impl Led {
+    /// Finds the caps-lock LED, falling back to the keyboard backlight.
+    pub fn find() -> io::Result<Self> {
+        if Os::current() != Os::Linux {
+            return Err(io::Error::new(io::ErrorKind::Unsupported, "LED output is only supported on Linux"));
+        }
+        let mut entries: Vec<PathBuf> = fs::read_dir(LEDS_DIR)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .collect();
+        entries.sort();
+        for name in LED_NAMES {
+            if let Some(path) = entries.iter().find(|p| p.to_string_lossy().ends_with(name)) {
+                return Self::open(path);
+            }
+        }
+        Err(io::Error::new(io::ErrorKind::NotFound, "no caps-lock LED or keyboard backlight found"))
+    }
+
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let max_brightness = fs::read_to_string(path.join("max_brightness"))?.trim().to_string();
+        Ok(Led {
+            path: path.to_path_buf(),
+            max_brightness,
+        })
+    }
+
+    pub fn name(&self) -> String {
+        self.path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default()
+    }
+
+    fn set(&self, on: bool) -> io::Result<()> {
+        let value = if on { self.max_brightness.as_str() } else { "0" };
+        fs::write(self.path.join("brightness"), value)
+    }
+
+    /// Blinks `morse_code` and restores the previous brightness afterwards.
+    pub fn blink_code(&self, morse_code: &str, unit_ms: f32) -> io::Result<()> {
+        let original = fs::read_to_string(self.path.join("brightness"))?;
+        self.set(false)?;
+        for event in timing::timings_for_code(morse_code, unit_ms) {
+            match event {
+                TimingEvent::Mark(ms) => {
+                    self.set(true)?;
+                    thread::sleep(Duration::from_secs_f32(ms / 1000.0));
+                    self.set(false)?;
+                }
+                TimingEvent::Space(ms) => thread::sleep(Duration::from_secs_f32(ms / 1000.0)),
+            }
+        }
+        thread::sleep(Duration::from_secs_f32(unit_ms / 1000.0));
+        fs::write(self.path.join("brightness"), original.trim())
+    }
+}
+
+pub fn blink_morse_code(morse_code: &str) {
+    let led = match Led::find() {
+        Ok(led) => led,
+        Err(e) => {
+            eprintln!("Error opening keyboard LED: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = led.blink_code(morse_code, LED_UNIT_MS) {
+        eprintln!("Error blinking {}: {}", led.name(), e);
+    }
+}
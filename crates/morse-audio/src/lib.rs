@@ -0,0 +1,317 @@
+pub mod capture;
+pub mod detect;
+pub mod encode;
+pub mod fading;
+pub mod led;
+pub mod midi;
+pub mod noise;
+pub mod qrm;
+pub mod render;
+pub mod stereo;
+pub mod terminal;
+pub mod vibration;
+
+use std::{f32::consts::{PI, TAU}, sync::{atomic::{AtomicBool, AtomicU32, Ordering}, Arc, Mutex}, thread, time::{Duration, Instant}};
+use morse_core::timing::{self, Spacing, TimingEvent};
+use render::ToneSettings;
+use rodio::{buffer::SamplesBuffer, cpal::traits::HostTrait, source::Zero, DeviceTrait, OutputStream, OutputStreamHandle, Sink, Source};
+
+/// Default sidetone pitch.
+pub const PITCH_HZ: f32 = 600.0;
+
+/// Master volume of all output from 0.0 (muted) to 1.0, stored as its bits.
+static VOLUME: AtomicU32 = AtomicU32::new(0x3f80_0000);
+
+/// Sets the master volume for everything played from now on, including a sidetone
+/// already open.
+pub fn set_volume(volume: f32) {
+    VOLUME.store(volume.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+}
+
+pub fn volume() -> f32 {
+    f32::from_bits(VOLUME.load(Ordering::Relaxed))
+}
+
+/// Rise and fall time of every element in ms, 5 ms to start with, stored as its bits.
+static RAMP_MS: AtomicU32 = AtomicU32::new(0x40a0_0000);
+
+/// Sets how long elements take to rise and fall in everything played or rendered from now
+/// on; 0 keys the tone on and off hard, with clicks.
+pub fn set_ramp_ms(ms: f32) {
+    RAMP_MS.store(ms.max(0.0).to_bits(), Ordering::Relaxed);
+}
+
+pub fn ramp_ms() -> f32 {
+    f32::from_bits(RAMP_MS.load(Ordering::Relaxed))
+}
+
+/// Name of the output device everything is played on; the system default when unset.
+static OUTPUT_DEVICE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Plays everything from now on through the output device named `name`, or through the
+/// system default with `None`.
+pub fn set_output_device(name: Option<String>) {
+    *OUTPUT_DEVICE.lock().unwrap() = name;
+}
+
+pub fn output_device() -> Option<String> {
+    OUTPUT_DEVICE.lock().unwrap().clone()
+}
+
+/// Opens the chosen output device. A chosen device that is missing is an error rather
+/// than a reason to play on the default one, which may be a radio.
+fn open_stream() -> Result<(OutputStream, OutputStreamHandle), Box<dyn std::error::Error>> {
+    let Some(name) = output_device() else {
+        return Ok(OutputStream::try_default()?);
+    };
+    let device = rodio::cpal::default_host().output_devices()?
+        .find(|device| device.name().is_ok_and(|n| n == name))
+        .ok_or_else(|| format!("audio output device '{}' not found", name))?;
+    Ok(OutputStream::try_from_device(&device)?)
+}
+
+/// Whether the chosen audio output device can be opened.
+pub fn audio_available() -> bool {
+    open_stream().is_ok()
+}
+
+/// Names of the audio output devices of the platform's default host (ALSA, CoreAudio, WASAPI).
+pub fn output_devices() -> Vec<String> {
+    match rodio::cpal::default_host().output_devices() {
+        Ok(devices) => devices.filter_map(|device| device.name().ok()).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn open_output() -> Option<(OutputStream, Sink)> {
+    let (stream, stream_handle) = match open_stream() {
+        Ok(stream) => stream,
+        Err(e) => {
+            eprintln!("Error creating audio output: {}", e);
+            return None;
+        }
+    };
+    
+    match Sink::try_new(&stream_handle) {
+        Ok(sink) => {
+            sink.set_volume(volume());
+            Some((stream, sink))
+        }
+        Err(e) => {
+            eprintln!("Error creating audio sink: {}", e);
+            None
+        }
+    }
+}
+
+/// Plays `morse_code` with a dash of 3 dots and the given dot length and gaps.
+pub fn play_morse_code_at(morse_code: &str, spacing: Spacing, pitch_hz: f32) {
+    let Some((_stream, sink)) = open_output() else {
+        return;
+    };
+
+    for event in timing::timings_with_spacing(morse_code, spacing) {
+        match event {
+            TimingEvent::Mark(ms) => play_beep(&sink, ms.round() as u64, pitch_hz),
+            TimingEvent::Space(ms) => thread::sleep(Duration::from_secs_f32(ms / 1000.0)),
+        }
+    }
+}
+
+/// Lead-in and tail of band noise around a rendered item, so the signal comes out of the noise.
+const NOISE_PADDING_MS: f32 = 400.0;
+
+/// Renders `morse_code` with the given gaps, pitch and band conditions and plays it,
+/// returning once it has finished.
+pub fn play_rendered(morse_code: &str, spacing: Spacing, settings: &ToneSettings) {
+    let Some((_stream, sink)) = open_output() else {
+        return;
+    };
+    let padding = render::render_events(&[TimingEvent::Space(NOISE_PADDING_MS)], settings);
+    let mut samples = padding.clone();
+    samples.extend(render::render_events(&settings.key(timing::timings_with_spacing(morse_code, spacing)), settings));
+    samples.extend(padding);
+    let (channels, mut samples) = render::with_conditions(samples, settings);
+    for sample in samples.iter_mut() {
+        *sample = sample.clamp(-1.0, 1.0);
+    }
+    sink.append(SamplesBuffer::new(channels, render::SAMPLE_RATE, samples));
+    sink.sleep_until_end();
+}
+
+/// A short sound after an answer, so a drill can be followed without looking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cue {
+    /// Two quick rising notes.
+    Correct,
+    /// Two longer low notes, falling.
+    Incorrect,
+}
+
+/// Plays `cue`, returning once it has finished.
+pub fn play_cue(cue: Cue) {
+    let Some((_stream, sink)) = open_output() else {
+        return;
+    };
+    let notes: [(f32, f32); 2] = match cue {
+        Cue::Correct => [(60.0, 1000.0), (90.0, 1500.0)],
+        Cue::Incorrect => [(150.0, 330.0), (250.0, 220.0)],
+    };
+    for (ms, pitch_hz) in notes {
+        sink.append(SamplesBuffer::new(1, render::SAMPLE_RATE, render::tone(ms, pitch_hz, 0.2)));
+    }
+    sink.sleep_until_end();
+}
+
+/// Plays a tone gliding from `from_hz` to `to_hz` over `secs`, returning once it has finished.
+pub fn play_sweep(from_hz: f32, to_hz: f32, secs: f32) {
+    let Some((_stream, sink)) = open_output() else {
+        return;
+    };
+    sink.append(SamplesBuffer::new(1, render::SAMPLE_RATE, render::sweep(secs * 1000.0, from_hz, to_hz, 0.2)));
+    sink.sleep_until_end();
+}
+
+/// Length of the tone the output delay is measured with.
+const LATENCY_PROBE_MS: u64 = 200;
+
+/// Roughly how long the output holds sound back: how much longer than its length a short
+/// tone takes to play. Delays in the device itself, after the audio system, aren't included.
+pub fn output_latency(pitch_hz: f32) -> Option<Duration> {
+    let (_stream, sink) = open_output()?;
+    let start = Instant::now();
+    sink.append(SamplesBuffer::new(1, render::SAMPLE_RATE, render::tone(LATENCY_PROBE_MS as f32, pitch_hz, 0.2)));
+    sink.sleep_until_end();
+    Some(start.elapsed().saturating_sub(Duration::from_millis(LATENCY_PROBE_MS)))
+}
+
+fn play_beep(sink: &Sink, duration_ms: u64, pitch_hz: f32) {
+    let samples = render::tone(duration_ms as f32, pitch_hz, 0.2);
+    sink.append(SamplesBuffer::new(1, render::SAMPLE_RATE, samples));
+    thread::sleep(Duration::from_millis(duration_ms));
+}
+
+const CLICK_MS: f32 = 10.0;
+const CLICK_HZ: f32 = 2000.0;
+/// Quieter than the sidetone, so the click stays in the background.
+const CLICK_LEVEL: f32 = 0.08;
+
+/// A soft click every `interval` for as long as it lives, to pace keying by.
+pub struct Metronome {
+    _stream: OutputStream,
+    _sink: Sink,
+}
+
+impl Metronome {
+    pub fn start(interval: Duration) -> Option<Self> {
+        let (stream, sink) = open_output()?;
+        let mut beat = render::tone(CLICK_MS, CLICK_HZ, CLICK_LEVEL);
+        beat.resize(((interval.as_secs_f32() * render::SAMPLE_RATE as f32) as usize).max(beat.len()), 0.0);
+        sink.append(SamplesBuffer::new(1, render::SAMPLE_RATE, beat).repeat_infinite());
+        Some(Metronome { _stream: stream, _sink: sink })
+    }
+}
+
+/// A tone for a key held by hand: it rises over the ramp time like [`render::tone`], and
+/// once `held` is cleared falls the same way and ends.
+struct HeldTone {
+    held: Arc<AtomicBool>,
+    pitch_hz: f32,
+    phase: f32,
+    ramp: usize,
+    /// How far up the ramp the tone is, from 0 to `ramp`.
+    level: usize,
+}
+
+impl Iterator for HeldTone {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.held.load(Ordering::Relaxed) {
+            self.level = (self.level + 1).min(self.ramp);
+        } else if self.level == 0 {
+            return None;
+        } else {
+            self.level -= 1;
+        }
+        let gain = match self.ramp {
+            0 => 1.0,
+            ramp => 0.5 - 0.5 * (PI * self.level as f32 / ramp as f32).cos(),
+        };
+        self.phase = (self.phase + TAU * self.pitch_hz / render::SAMPLE_RATE as f32) % TAU;
+        Some(self.phase.sin() * 0.2 * gain)
+    }
+}
+
+impl Source for HeldTone {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        render::SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// An audio output kept open to sound elements as they are keyed. Each element is queued
+/// with its following gap, so quick typing still comes out as evenly timed Morse.
+pub struct Sidetone {
+    _stream: OutputStream,
+    sink: Sink,
+    pitch_hz: f32,
+    unit: Duration,
+    /// Whether the key is still down, for the tone started by [`Sidetone::down`].
+    held: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+impl Sidetone {
+    pub fn open(pitch_hz: f32, unit_ms: f32) -> Option<Self> {
+        let (stream, sink) = open_output()?;
+        Some(Sidetone {
+            _stream: stream,
+            sink,
+            pitch_hz,
+            unit: Duration::from_secs_f32(unit_ms / 1000.0),
+            held: Mutex::new(None),
+        })
+    }
+
+    /// Sounds a `.` or `-` without waiting for it to finish; other symbols are silent.
+    pub fn key(&self, symbol: char) {
+        let length = match symbol {
+            '.' => self.unit,
+            '-' => self.unit * 3,
+            _ => return,
+        };
+        self.sink.set_volume(volume());
+        let samples = render::tone(length.as_secs_f32() * 1000.0, self.pitch_hz, 0.2);
+        self.sink.append(SamplesBuffer::new(1, render::SAMPLE_RATE, samples));
+        self.sink.append(Zero::<f32>::new(1, render::SAMPLE_RATE).take_duration(self.unit));
+    }
+
+    /// Starts a tone that lasts until [`Sidetone::up`], for a key held down by hand.
+    pub fn down(&self) {
+        let held = Arc::new(AtomicBool::new(true));
+        if let Some(previous) = self.held.lock().unwrap().replace(Arc::clone(&held)) {
+            previous.store(false, Ordering::Relaxed);
+        }
+        self.sink.set_volume(volume());
+        let ramp = render::ms_to_samples(ramp_ms());
+        self.sink.append(HeldTone { held, pitch_hz: self.pitch_hz, phase: 0.0, ramp, level: 0 });
+    }
+
+    /// Lets the tone fall away over the ramp time.
+    pub fn up(&self) {
+        if let Some(held) = self.held.lock().unwrap().take() {
+            held.store(false, Ordering::Relaxed);
+        }
+    }
+}
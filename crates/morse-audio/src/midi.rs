@@ -0,0 +1,78 @@
+use std::{sync::mpsc::{self, Receiver}, time::{Duration, Instant}};
+use midir::{MidiInput, MidiInputConnection};
+
+const CLIENT_NAME: &str = "morse_code_learner";
+const NOTE_OFF: u8 = 0x80;
+const NOTE_ON: u8 = 0x90;
+const CONTROL_CHANGE: u8 = 0xB0;
+/// Controllers at this value or above count as pressed, as with the sustain pedal.
+const CONTROL_DOWN: u8 = 64;
+
+/// A key on a MIDI device: a note, or a controller such as a sustain pedal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MidiKey {
+    Note(u8),
+    Controller(u8),
+}
+
+/// The key a MIDI message presses or lets go, and whether it's now down. A note on with
+/// no velocity is a note off.
+pub fn parse_message(message: &[u8]) -> Option<(MidiKey, bool)> {
+    let [status, number, value, ..] = *message else {
+        return None;
+    };
+    match status & 0xF0 {
+        NOTE_ON => Some((MidiKey::Note(number), value > 0)),
+        NOTE_OFF => Some((MidiKey::Note(number), false)),
+        CONTROL_CHANGE => Some((MidiKey::Controller(number), value >= CONTROL_DOWN)),
+        _ => None,
+    }
+}
+
+/// Names of the MIDI inputs on the system.
+pub fn input_ports() -> Vec<String> {
+    let Ok(input) = MidiInput::new(CLIENT_NAME) else {
+        return Vec::new();
+    };
+    input.ports().iter().filter_map(|port| input.port_name(port).ok()).collect()
+}
+
+/// A MIDI input, passing on keys as they are pressed and let go, with when it happened,
+/// for as long as it is open.
+pub struct MidiKeys {
+    _connection: MidiInputConnection<()>,
+    keys: Receiver<(MidiKey, bool, Instant)>,
+    pub name: String,
+}
+
+impl MidiKeys {
+    /// Connects to the first MIDI input whose name contains `name`, or to the first one.
+    pub fn open(name: Option<&str>) -> Result<Self, Box<dyn std::error::Error>> {
+        let input = MidiInput::new(CLIENT_NAME)?;
+        let ports = input.ports();
+        let port = ports.iter()
+            .find(|port| name.is_none_or(|name| input.port_name(port).is_ok_and(|n| n.to_lowercase().contains(&name.to_lowercase()))))
+            .ok_or(match name {
+                Some(name) => format!("no MIDI input matching '{}'", name),
+                None => "no MIDI input found".to_string(),
+            })?;
+        let port_name = input.port_name(port)?;
+        let (sender, keys) = mpsc::channel();
+        let connection = input.connect(port, CLIENT_NAME, move |_, message, _| {
+            if let Some((key, down)) = parse_message(message) {
+                let _ = sender.send((key, down, Instant::now()));
+            }
+        }, ())?;
+        Ok(MidiKeys { _connection: connection, keys, name: port_name })
+    }
+
+    /// Throws away keys pressed so far, e.g. while an item played.
+    pub fn clear(&self) {
+        while self.keys.try_recv().is_ok() {}
+    }
+
+    /// The next key pressed or let go, waiting up to `timeout`.
+    pub fn next(&self, timeout: Duration) -> Option<(MidiKey, bool, Instant)> {
+        self.keys.recv_timeout(timeout).ok()
+    }
+}
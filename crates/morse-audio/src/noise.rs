@@ -0,0 +1,55 @@
+use std::f32::consts::TAU;
+use rand::Rng;
+use crate::render::SAMPLE_RATE;
+
+/// Upper edge of the receiver passband the hiss is limited to, about an SSB filter.
+const PASSBAND_HZ: f32 = 2400.0;
+/// Average number of static crashes per second.
+const CRASHES_PER_SEC: f32 = 0.4;
+/// How quickly a crash dies away.
+const CRASH_DECAY_MS: f32 = 60.0;
+/// Lowest and highest crash peak relative to the hiss.
+const CRASH_PEAK: (f32, f32) = (2.0, 6.0);
+
+/// Standard normal sample (Box-Muller).
+fn gaussian(rng: &mut impl Rng) -> f32 {
+    let u1: f32 = rng.random_range(f32::EPSILON..1.0);
+    let u2: f32 = rng.random();
+    (-2.0 * u1.ln()).sqrt() * (TAU * u2).cos()
+}
+
+/// Mixes HF band noise, a steady hiss with the odd static crash, under `samples`. The hiss
+/// is set `snr_db` below the power of a tone of amplitude `tone_level`; crashes come on top.
+pub fn add_noise(samples: &mut [f32], tone_level: f32, snr_db: f32, rng: &mut impl Rng) {
+    if samples.is_empty() {
+        return;
+    }
+    let smoothing = 1.0 - (-TAU * PASSBAND_HZ / SAMPLE_RATE as f32).exp();
+    let mut filtered = 0.0;
+    let mut hiss: Vec<f32> = (0..samples.len())
+        .map(|_| {
+            filtered += smoothing * (gaussian(rng) - filtered);
+            filtered
+        })
+        .collect();
+
+    let rms = (hiss.iter().map(|n| n * n).sum::<f32>() / hiss.len() as f32).sqrt();
+    let tone_power = tone_level * tone_level / 2.0;
+    let target_rms = (tone_power / 10f32.powf(snr_db / 10.0)).sqrt();
+    let gain = if rms > 0.0 { target_rms / rms } else { 0.0 };
+
+    let decay = (-1000.0 / (CRASH_DECAY_MS * SAMPLE_RATE as f32)).exp();
+    let crash_chance = CRASHES_PER_SEC / SAMPLE_RATE as f32;
+    let mut crash = 0.0;
+    for noise in hiss.iter_mut() {
+        if rng.random::<f32>() < crash_chance {
+            crash = rng.random_range(CRASH_PEAK.0..CRASH_PEAK.1);
+        }
+        *noise *= gain * (1.0 + crash);
+        crash *= decay;
+    }
+
+    for (sample, noise) in samples.iter_mut().zip(hiss) {
+        *sample += noise;
+    }
+}
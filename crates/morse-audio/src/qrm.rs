@@ -0,0 +1,47 @@
+use rand::{seq::IndexedRandom, Rng};
+use morse_core::{material, morse};
+use crate::render::{self, ToneSettings, SAMPLE_RATE};
+
+/// A second station sending underneath the one being copied.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Qrm {
+    /// Pitch of the interfering signal relative to the wanted one, in Hz.
+    pub offset_hz: f32,
+    /// Its strength relative to the wanted signal, in dB.
+    pub level_db: f32,
+}
+
+/// What the other station keeps sending: calling CQ and working a pileup.
+fn chatter<R: Rng + ?Sized>(rng: &mut R) -> String {
+    let calls = material::callsigns(2, rng);
+    let (station, caller) = (&calls[0], &calls[1]);
+    let phrases = [
+        format!("CQ CQ DE {} {} K", station, station),
+        format!("CQ TEST {} {}", station, station),
+        format!("{} 5NN {:03}", caller, rng.random_range(1..1000)),
+        format!("TU {} QRZ?", station),
+        format!("{} DE {} UR RST 579 579", caller, station),
+    ];
+    phrases.choose(rng).cloned().unwrap_or_default()
+}
+
+/// Mixes an interfering signal sending random traffic under `samples`. It runs at its own
+/// speed, a little off the wanted signal's, and starts at a random point.
+pub fn add_qrm<R: Rng + ?Sized>(samples: &mut [f32], qrm: Qrm, settings: &ToneSettings, rng: &mut R) {
+    let interferer = ToneSettings {
+        wpm: settings.wpm * rng.random_range(0.8..1.25),
+        effective_wpm: None,
+        pitch_hz: (settings.pitch_hz + qrm.offset_hz).max(100.0),
+        volume: settings.volume * 10f32.powf(qrm.level_db / 20.0),
+        ..ToneSettings::default()
+    };
+    let start = rng.random_range(0..SAMPLE_RATE as usize);
+    let mut signal = vec![0.0; start];
+    while signal.len() < samples.len() {
+        signal.extend(render::render_code(&morse::encode(&chatter(rng)), &interferer));
+        signal.extend(std::iter::repeat_n(0.0, rng.random_range(SAMPLE_RATE as usize / 4..SAMPLE_RATE as usize)));
+    }
+    for (sample, other) in samples.iter_mut().zip(signal) {
+        *sample += other;
+    }
+}
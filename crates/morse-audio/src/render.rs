@@ -0,0 +1,196 @@
+use std::{f32::consts::{PI, TAU}, io::{Read, Seek, Write}};
+use morse_core::{morse, timing::{self, Fist, Spacing, TimingEvent}};
+use crate::{fading::{self, Fading}, noise, qrm::{self, Qrm}, stereo::{self, Stereo}};
+
+pub const SAMPLE_RATE: u32 = 22_050;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ToneSettings {
+    pub wpm: f32,
+    /// Overall speed with Farnsworth gaps; `wpm` when unset.
+    pub effective_wpm: Option<f32>,
+    pub pitch_hz: f32,
+    pub volume: f32,
+    /// Band noise at this signal-to-noise ratio in dB; a clean tone when unset.
+    pub noise_snr_db: Option<f32>,
+    /// Slow fading of the tone; noise keeps its level.
+    pub fading: Option<Fading>,
+    /// Another station sending underneath.
+    pub qrm: Option<Qrm>,
+    /// The wanted signal in one ear and the other station in the other; only live
+    /// playback is in stereo.
+    pub stereo: Option<Stereo>,
+    /// Irregular element and gap lengths, like a human sender; machine-perfect when unset.
+    pub fist: Option<Fist>,
+}
+
+impl Default for ToneSettings {
+    fn default() -> Self {
+        ToneSettings {
+            wpm: 20.0,
+            effective_wpm: None,
+            pitch_hz: 600.0,
+            volume: 0.5,
+            noise_snr_db: None,
+            fading: None,
+            qrm: None,
+            stereo: None,
+            fist: None,
+        }
+    }
+}
+
+/// Renders key timings to mono samples in -1.0..=1.0.
+pub fn render_events(events: &[TimingEvent], settings: &ToneSettings) -> Vec<f32> {
+    let mut samples = Vec::new();
+    for event in events {
+        match *event {
+            TimingEvent::Mark(ms) => samples.extend(tone(ms, settings.pitch_hz, settings.volume)),
+            TimingEvent::Space(ms) => silence(&mut samples, ms),
+        }
+    }
+    samples
+}
+
+/// A tone lasting `ms`, faded in and out over `ramp_ms` (at most half the tone) with
+/// raised-cosine ramps so that it doesn't click.
+pub fn tone(ms: f32, pitch_hz: f32, volume: f32) -> Vec<f32> {
+    let count = ms_to_samples(ms);
+    let ramp = ms_to_samples(crate::ramp_ms()).min(count / 2);
+    (0..count)
+        .map(|i| {
+            let edge = i.min(count - 1 - i);
+            let gain = match edge < ramp {
+                true => 0.5 - 0.5 * (PI * (edge as f32 + 0.5) / ramp as f32).cos(),
+                false => 1.0,
+            };
+            let t = i as f32 / SAMPLE_RATE as f32;
+            (TAU * pitch_hz * t).sin() * volume * gain
+        })
+        .collect()
+}
+
+pub fn render_code(code: &str, settings: &ToneSettings) -> Vec<f32> {
+    let spacing = Spacing::farnsworth(settings.wpm, settings.effective_wpm.unwrap_or(settings.wpm));
+    render_events(&settings.key(timing::timings_with_spacing(code, spacing)), settings)
+}
+
+/// A tone gliding from `from_hz` to `to_hz` over `ms`, evenly on a musical scale, with the
+/// same ramps as [`tone`] at its ends.
+pub fn sweep(ms: f32, from_hz: f32, to_hz: f32, volume: f32) -> Vec<f32> {
+    let count = ms_to_samples(ms);
+    let ramp = ms_to_samples(crate::ramp_ms()).min(count / 2);
+    let mut phase = 0.0f32;
+    (0..count)
+        .map(|i| {
+            let edge = i.min(count - 1 - i);
+            let gain = match edge < ramp {
+                true => 0.5 - 0.5 * (PI * (edge as f32 + 0.5) / ramp as f32).cos(),
+                false => 1.0,
+            };
+            let pitch_hz = from_hz * (to_hz / from_hz).powf(i as f32 / count as f32);
+            phase = (phase + TAU * pitch_hz / SAMPLE_RATE as f32) % TAU;
+            phase.sin() * volume * gain
+        })
+        .collect()
+}
+
+/// Renders practice items one after another with `pause_ms` of silence after each,
+/// leaving time to write down what was heard.
+pub fn render_items(items: &[String], settings: &ToneSettings, pause_ms: f32) -> Vec<f32> {
+    let mut samples = Vec::new();
+    for item in items {
+        samples.extend(render_code(&morse::encode(item), settings));
+        silence(&mut samples, pause_ms);
+    }
+    add_conditions(&mut samples, settings);
+    samples
+}
+
+impl ToneSettings {
+    /// Whether any band conditions or fist irregularities are added to the clean tone.
+    pub fn has_conditions(&self) -> bool {
+        self.noise_snr_db.is_some() || self.fading.is_some() || self.qrm.is_some() || self.stereo.is_some()
+            || self.fist.is_some()
+    }
+
+    /// Key timings as this sender keys them, jittered when a fist is set.
+    pub fn key(&self, events: Vec<TimingEvent>) -> Vec<TimingEvent> {
+        match self.fist {
+            Some(fist) => timing::humanize(&events, fist, &mut rand::rng()),
+            None => events,
+        }
+    }
+}
+
+/// Mixes the band conditions of `settings` into rendered samples.
+pub fn add_conditions(samples: &mut [f32], settings: &ToneSettings) {
+    if let Some(fading) = settings.fading {
+        fading::apply(samples, fading, &mut rand::rng());
+    }
+    if let Some(qrm) = settings.qrm {
+        qrm::add_qrm(samples, qrm, settings, &mut rand::rng());
+    }
+    if let Some(snr_db) = settings.noise_snr_db {
+        noise::add_noise(samples, settings.volume, snr_db, &mut rand::rng());
+    }
+}
+
+/// Adds the band conditions to mono samples like `add_conditions`, splitting them
+/// between the ears when stereo is set. Returns the channel count and the samples,
+/// interleaved.
+pub fn with_conditions(mut samples: Vec<f32>, settings: &ToneSettings) -> (u16, Vec<f32>) {
+    let Some(split) = settings.stereo else {
+        add_conditions(&mut samples, settings);
+        return (1, samples);
+    };
+    if let Some(fading) = settings.fading {
+        fading::apply(&mut samples, fading, &mut rand::rng());
+    }
+    (2, stereo::split(&samples, split, settings, &mut rand::rng()))
+}
+
+pub fn duration_secs(samples: &[f32]) -> f32 {
+    samples.len() as f32 / SAMPLE_RATE as f32
+}
+
+pub(crate) fn ms_to_samples(ms: f32) -> usize {
+    (ms / 1000.0 * SAMPLE_RATE as f32).round() as usize
+}
+
+fn silence(samples: &mut Vec<f32>, ms: f32) {
+    samples.resize(samples.len() + ms_to_samples(ms), 0.0);
+}
+
+/// Reads a WAV file of any sample format, mixed down to mono, with its sample rate.
+pub fn read_wav<R: Read>(reader: R) -> Result<(Vec<f32>, u32), hound::Error> {
+    let mut wav = hound::WavReader::new(reader)?;
+    let spec = wav.spec();
+    let interleaved: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => wav.samples::<f32>().collect::<Result<_, _>>()?,
+        hound::SampleFormat::Int => {
+            let scale = 1.0 / (1u32 << (spec.bits_per_sample - 1)) as f32;
+            wav.samples::<i32>().map(|s| s.map(|s| s as f32 * scale)).collect::<Result<_, _>>()?
+        }
+    };
+    let channels = spec.channels.max(1) as usize;
+    let mono = interleaved.chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect();
+    Ok((mono, spec.sample_rate))
+}
+
+/// Writes samples as 16-bit mono PCM WAV.
+pub fn write_wav<W: Write + Seek>(writer: W, samples: &[f32]) -> Result<(), hound::Error> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut wav = hound::WavWriter::new(writer, spec)?;
+    for sample in samples {
+        wav.write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)?;
+    }
+    wav.finalize()
+}
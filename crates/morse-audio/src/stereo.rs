@@ -0,0 +1,45 @@
+use rand::Rng;
+use crate::{noise, qrm::{self, Qrm}, render::ToneSettings};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ear {
+    Left,
+    Right,
+}
+
+/// The wanted signal in one ear and a distractor in the other, for training selective
+/// listening the way contest operators copy one receiver per ear.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stereo {
+    /// The ear the wanted signal is heard in; the distractor is in the other.
+    pub target: Ear,
+    /// Levels of the left and right channels, in dB.
+    pub left_db: f32,
+    pub right_db: f32,
+}
+
+/// The distractor when no interference is set up: another station as strong as the wanted one.
+pub const DEFAULT_DISTRACTOR: Qrm = Qrm { offset_hz: 300.0, level_db: 0.0 };
+
+/// Turns the wanted signal into interleaved left and right samples, with the interference
+/// of `settings` (or `DEFAULT_DISTRACTOR`) alone in the other ear. Each ear gets its own
+/// band noise.
+pub fn split<R: Rng>(target: &[f32], stereo: Stereo, settings: &ToneSettings, rng: &mut R) -> Vec<f32> {
+    let mut target = target.to_vec();
+    let mut distractor = vec![0.0; target.len()];
+    qrm::add_qrm(&mut distractor, settings.qrm.unwrap_or(DEFAULT_DISTRACTOR), settings, rng);
+    if let Some(snr_db) = settings.noise_snr_db {
+        noise::add_noise(&mut target, settings.volume, snr_db, rng);
+        noise::add_noise(&mut distractor, settings.volume, snr_db, rng);
+    }
+    let (left, right) = match stereo.target {
+        Ear::Left => (target, distractor),
+        Ear::Right => (distractor, target),
+    };
+    let left_gain = 10f32.powf(stereo.left_db / 20.0);
+    let right_gain = 10f32.powf(stereo.right_db / 20.0);
+    left.into_iter()
+        .zip(right)
+        .flat_map(|(l, r)| [l * left_gain, r * right_gain])
+        .collect()
+}
@@ -0,0 +1,55 @@
+use std::{
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+use morse_core::timing::{self, Spacing, TimingEvent};
+
+const BELL: &str = "\x07";
+
+/// Terminal stand-ins for audio where there is no sound device, e.g. over SSH or in containers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalStyle {
+    /// Ring the terminal bell at the start of every element.
+    Bell,
+    /// Print "beep" for dots and "boop" for dashes in time with the code.
+    Text,
+}
+
+/// Sends `morse_code` to the terminal in real time with the given dot length and gaps.
+pub fn play_morse_code(morse_code: &str, style: TerminalStyle, spacing: Spacing) {
+    let mut stdout = io::stdout().lock();
+    if let Err(e) = play(&mut stdout, morse_code, style, spacing) {
+        eprintln!("Error writing to terminal: {}", e);
+    }
+}
+
+fn play<W: Write>(out: &mut W, morse_code: &str, style: TerminalStyle, spacing: Spacing) -> io::Result<()> {
+    let unit_ms = spacing.unit_ms;
+    for event in timing::timings_with_spacing(morse_code, spacing) {
+        match event {
+            TimingEvent::Mark(ms) => {
+                match style {
+                    // The bell is the only sound here, so muting silences it.
+                    TerminalStyle::Bell if crate::volume() == 0.0 => {}
+                    TerminalStyle::Bell => write!(out, "{}", BELL)?,
+                    TerminalStyle::Text if ms > unit_ms => write!(out, "boop ")?,
+                    TerminalStyle::Text => write!(out, "beep ")?,
+                }
+                out.flush()?;
+                thread::sleep(Duration::from_secs_f32(ms / 1000.0));
+            }
+            TimingEvent::Space(ms) => {
+                if style == TerminalStyle::Text && ms > unit_ms {
+                    write!(out, "  ")?;
+                    out.flush()?;
+                }
+                thread::sleep(Duration::from_secs_f32(ms / 1000.0));
+            }
+        }
+    }
+    if style == TerminalStyle::Text {
+        writeln!(out)?;
+    }
+    Ok(())
+}
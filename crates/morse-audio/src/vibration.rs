@@ -0,0 +1,30 @@
+use std::{io, thread, time::Duration};
+use morse_core::timing::{self, TimingEvent};
+use morse_platform::serial::SerialSwitch;
+
+/// Touch is read more slowly than sound, so vibration keys at about 8 WPM, like the LED,
+/// until `vibration-check` finds the speed that suits the learner and the device.
+pub const VIBRATION_UNIT_MS: f32 = 150.0;
+
+/// Vibrates `morse_code` on the device on serial port `port`, a mark at a time.
+pub fn vibrate_code(port: &str, morse_code: &str, unit_ms: f32) -> io::Result<()> {
+    let mut motor = SerialSwitch::open(port)?;
+    for event in timing::timings_for_code(morse_code, unit_ms) {
+        match event {
+            TimingEvent::Mark(ms) => {
+                motor.set(true)?;
+                thread::sleep(Duration::from_secs_f32(ms / 1000.0));
+                motor.set(false)?;
+            }
+            TimingEvent::Space(ms) => thread::sleep(Duration::from_secs_f32(ms / 1000.0)),
+        }
+    }
+    thread::sleep(Duration::from_secs_f32(unit_ms / 1000.0));
+    Ok(())
+}
+
+pub fn vibrate_morse_code(port: &str, morse_code: &str, unit_ms: f32) {
+    if let Err(e) = vibrate_code(port, morse_code, unit_ms) {
+        eprintln!("Error vibrating on {}: {}", port, e);
+    }
+}
@@ -0,0 +1,176 @@
+use std::{fs, path::PathBuf};
+use crate::cli::{self, KeyPreset};
+use morse_core::{
+    analysis::{self, CodeAnalysis},
+    difficulty::{self, DifficultyLabel},
+    keying,
+    morse,
+    progression::ProgressionSystem,
+    timing::{self, TimingEvent},
+};
+
+pub enum AnalyzeInput {
+    Code(String),
+    Timings(String),
+    TimingsFile(PathBuf),
+}
+
+/// Overlay columns per dot length.
+const CELLS_PER_UNIT: f32 = 2.0;
+const OVERLAY_WIDTH: usize = 64;
+
+fn is_morse(input: &str) -> bool {
+    input.chars().all(|c| matches!(c, '.' | '-' | '/' | ' '))
+}
+
+/// With `expected` text, a timing trace is also drawn against ideal keying of that text
+/// and scored by the norms for `key`.
+pub fn run(input: AnalyzeInput, expected: Option<&str>, key: KeyPreset) -> Result<(), Box<dyn std::error::Error>> {
+    let trace = match input {
+        AnalyzeInput::Code(code) => {
+            let code = if is_morse(&code) { code } else { morse::encode(&code) };
+            print_code_analysis(&code, &analysis::analyze_code(&code));
+            return Ok(());
+        }
+        AnalyzeInput::Timings(trace) => trace,
+        AnalyzeInput::TimingsFile(path) => fs::read_to_string(path)?,
+    };
+
+    let events = timing::parse_timing_trace(&trace)?;
+    let unit_ms = timing::estimate_unit_ms(&events)
+        .ok_or("the timing trace contains no key-down durations")?;
+
+    let mut decoder = timing::TimingDecoder::new(unit_ms);
+    let mut code = String::new();
+    for event in &events {
+        decoder.feed(*event);
+    }
+    decoder.finish();
+    let text = decoder.take_text();
+    for word in text.split_whitespace() {
+        if !code.is_empty() {
+            code.push_str(morse::WORD_SEPARATOR);
+        }
+        code.push_str(&morse::encode_word(word));
+    }
+
+    let analysis = analysis::analyze_code(&code);
+    print_code_analysis(&code, &analysis);
+
+    let total_ms: f32 = events.iter()
+        .map(|e| match e {
+            TimingEvent::Mark(ms) | TimingEvent::Space(ms) => *ms,
+        })
+        .sum();
+    println!("\nTiming:");
+    println!("  Estimated dot length: {:.0} ms", unit_ms);
+    println!("  Character speed:      {:.1} WPM", 1200.0 / unit_ms);
+    if total_ms > 0.0 {
+        // A trace ends on the last element, without the trailing word gap PARIS counts.
+        let words = analysis.units.saturating_sub(7) as f32 / analysis::UNITS_PER_WORD;
+        println!("  Effective speed:      {:.1} WPM", words / (total_ms / 60_000.0));
+    }
+    if let Some(expected) = expected {
+        print_overlay(expected, &events, unit_ms);
+        print_keying_score(expected, &events, unit_ms, key);
+    }
+    Ok(())
+}
+
+fn print_keying_score(expected: &str, events: &[TimingEvent], unit_ms: f32, key: KeyPreset) {
+    let norms = key.norms();
+    println!("\nKeying score ({} key norms, ±{:.0}% per element and gap):", cli::value_name(&key), norms.tolerance * 100.0);
+    let ideal = timing::timings_for_text(expected, unit_ms);
+    let Some(score) = keying::score(events, &ideal, unit_ms, &norms) else {
+        println!("  Not scored: the trace doesn't have the elements of the expected text.");
+        return;
+    };
+    println!("  Clean elements:       {:.0}%", score.clean_elements * 100.0);
+    println!("  Clean gaps:           {:.0}%", score.clean_gaps * 100.0);
+    if let Some(spread) = score.letter_gap_spread {
+        println!("  Letter gap spread:    ±{:.0}%", spread * 100.0);
+    }
+    println!("  Score:                {:.0} / 100", score.score);
+    let wpm = 1200.0 / unit_ms;
+    if wpm < norms.min_wpm || wpm > norms.max_wpm {
+        println!("  {:.0} WPM is outside the usual {:.0}-{:.0} WPM for this key.", wpm, norms.min_wpm, norms.max_wpm);
+    }
+}
+
+/// Key-down state per overlay column.
+fn key_down_cells(events: &[TimingEvent], unit_ms: f32) -> Vec<bool> {
+    let mut cells = Vec::new();
+    let mut elapsed = 0.0;
+    for event in events {
+        let (ms, down) = match event {
+            TimingEvent::Mark(ms) => (*ms, true),
+            TimingEvent::Space(ms) => (*ms, false),
+        };
+        elapsed += ms;
+        let end = (elapsed / unit_ms * CELLS_PER_UNIT).round() as usize;
+        cells.resize(end.max(cells.len()), down);
+    }
+    cells
+}
+
+/// Draws each word of the trace under ideal keying of the expected text at the same speed,
+/// marking the columns where the two disagree.
+fn print_overlay(expected: &str, events: &[TimingEvent], unit_ms: f32) {
+    let ideal = timing::split_words(&timing::timings_for_text(expected, unit_ms), unit_ms);
+    let keyed = timing::split_words(events, unit_ms);
+    let expected_words: Vec<String> = expected.split_whitespace().map(|w| w.to_uppercase()).collect();
+
+    println!("\nKeying vs expected (█ key down, {} columns per dot, ^ where they differ):", CELLS_PER_UNIT);
+    for i in 0..ideal.len().max(keyed.len()) {
+        let ideal_cells = ideal.get(i).map(|w| key_down_cells(w, unit_ms)).unwrap_or_default();
+        let keyed_cells = keyed.get(i).map(|w| key_down_cells(w, unit_ms)).unwrap_or_default();
+        let width = ideal_cells.len().max(keyed_cells.len());
+        let row = |cells: &[bool], range: std::ops::Range<usize>| -> String {
+            range.map(|j| if cells.get(j).copied().unwrap_or(false) { '█' } else { ' ' }).collect()
+        };
+        let differences = |range: std::ops::Range<usize>| -> String {
+            range.map(|j| {
+                let same = ideal_cells.get(j).copied().unwrap_or(false) == keyed_cells.get(j).copied().unwrap_or(false);
+                if same { ' ' } else { '^' }
+            }).collect()
+        };
+
+        println!("\n{} (keyed: {})",
+            expected_words.get(i).map(String::as_str).unwrap_or("-"),
+            keyed.get(i).map(|w| timing::decode_timings(w, unit_ms)).unwrap_or_else(|| "-".to_string()),
+        );
+        for start in (0..width).step_by(OVERLAY_WIDTH) {
+            let range = start..(start + OVERLAY_WIDTH).min(width);
+            println!("  expected │{}", row(&ideal_cells, range.clone()));
+            println!("  keyed    │{}", row(&keyed_cells, range.clone()));
+            println!("           │{}", differences(range));
+        }
+    }
+}
+
+fn print_code_analysis(code: &str, analysis: &CodeAnalysis) {
+    println!("Code:    {}", code);
+    if analysis.decoded() {
+        println!("Text:    {}", morse::decode(code));
+    } else {
+        println!("Unknown codes: {}", analysis.unknown_codes.join(" "));
+    }
+
+    println!("\nElements:");
+    println!("  Dots:    {}", analysis.dots);
+    println!("  Dashes:  {}", analysis.dashes);
+    println!("  Letters: {}", analysis.letters);
+    println!("  Words:   {}", analysis.words);
+    println!("  Length:  {} units ({:.1} standard words)", analysis.units, analysis.standard_words());
+
+    println!("\nCharacters used: {}", analysis.chars_used.iter().collect::<String>());
+    if analysis.decoded() && analysis.letters > 0 {
+        let score = difficulty::text_difficulty(&morse::decode(code));
+        println!("Difficulty:      {:.1} ({})", score, DifficultyLabel::for_score(score));
+    }
+    match analysis.required_level(&ProgressionSystem::with_words(Vec::new())) {
+        Some(level) => println!("Required level:  {}", level),
+        None if analysis.chars_used.is_empty() => {}
+        None => println!("Required level:  uses characters outside the curriculum"),
+    }
+}
@@ -0,0 +1,84 @@
+use serde_derive::{Serialize, Deserialize};
+use morse_core::progression::ProgressionSystem;
+use crate::config::AppConfig;
+
+/// Characters and words left out of everything generated for practice.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct BanList {
+    pub chars: Vec<char>,
+    pub words: Vec<String>,
+}
+
+impl BanList {
+    pub fn allows_char(&self, c: char) -> bool {
+        !self.chars.iter().any(|b| b.to_uppercase().eq(c.to_uppercase()))
+    }
+
+    /// Whether a word is neither banned itself nor contains a banned character.
+    pub fn allows_word(&self, word: &str) -> bool {
+        word.chars().all(|c| self.allows_char(c)) && !self.words.iter().any(|w| w == word)
+    }
+
+    pub fn filter_chars(&self, chars: &[char]) -> Vec<char> {
+        chars.iter().copied().filter(|c| self.allows_char(*c)).collect()
+    }
+
+    pub fn apply(&self, progression: &mut ProgressionSystem) {
+        progression.exclude(&self.chars, &self.words);
+    }
+
+    /// Bans a single character, or a word when `item` is longer. Returns false if it was
+    /// already banned.
+    pub fn ban(&mut self, item: &str) -> bool {
+        let item = item.trim().to_uppercase();
+        let mut chars = item.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if !self.chars.contains(&c) => {
+                self.chars.push(c);
+                true
+            }
+            (Some(_), Some(_)) if !self.words.contains(&item) => {
+                self.words.push(item);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Lifts a ban, returning false if `item` wasn't banned.
+    pub fn unban(&mut self, item: &str) -> bool {
+        let item = item.trim().to_uppercase();
+        let (chars, words) = (self.chars.len(), self.words.len());
+        self.chars.retain(|c| item != c.to_string());
+        self.words.retain(|w| *w != item);
+        chars != self.chars.len() || words != self.words.len()
+    }
+}
+
+fn update(items: &[String], change: impl Fn(&mut BanList, &str) -> bool, verb: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    for item in items {
+        if !change(&mut config.banned, item) {
+            println!("{} was not {}", item.trim().to_uppercase(), verb);
+        }
+    }
+    config.save()?;
+
+    let banned = &config.banned;
+    if banned.chars.is_empty() && banned.words.is_empty() {
+        println!("Nothing is banned.");
+    } else {
+        println!("Banned characters: {}", banned.chars.iter().collect::<String>());
+        println!("Banned words:      {}", banned.words.join(" "));
+    }
+    Ok(())
+}
+
+pub fn ban(items: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    update(items, BanList::ban, "banned again")
+}
+
+pub fn unban(items: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    update(items, BanList::unban, "banned")
+}
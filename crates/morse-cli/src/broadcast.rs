@@ -0,0 +1,152 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+use morse_audio::render::{self, SAMPLE_RATE};
+use crate::{config::AppConfig, export_audio};
+
+/// How far listeners are sent ahead of the live position, so players don't run dry.
+const LEAD: Duration = Duration::from_secs(1);
+const CHUNK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// One rendered drill played on a shared clock, so every listener hears the same moment.
+struct Drill {
+    pcm: Vec<u8>,
+    key: String,
+    start: Instant,
+    repeat: bool,
+}
+
+impl Drill {
+    fn len_secs(&self) -> f32 {
+        self.pcm.len() as f32 / 2.0 / SAMPLE_RATE as f32
+    }
+
+    /// Byte offset of the live position, or `None` once a non-repeating drill is over.
+    fn offset_at(&self, at: Instant) -> Option<usize> {
+        let sample = (at.duration_since(self.start).as_secs_f64() * SAMPLE_RATE as f64) as usize;
+        let offset = sample * 2;
+        if self.repeat {
+            Some(offset % self.pcm.len())
+        } else {
+            (offset < self.pcm.len()).then_some(offset)
+        }
+    }
+
+    fn finished(&self) -> bool {
+        !self.repeat && self.offset_at(Instant::now()).is_none()
+    }
+}
+
+/// A WAV header for a stream of unknown length; players read until the connection closes.
+fn stream_header() -> Vec<u8> {
+    let mut header = Vec::with_capacity(44);
+    header.extend_from_slice(b"RIFF");
+    header.extend_from_slice(&u32::MAX.to_le_bytes());
+    header.extend_from_slice(b"WAVEfmt ");
+    header.extend_from_slice(&16u32.to_le_bytes());
+    header.extend_from_slice(&1u16.to_le_bytes());
+    header.extend_from_slice(&1u16.to_le_bytes());
+    header.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    header.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    header.extend_from_slice(&2u16.to_le_bytes());
+    header.extend_from_slice(&16u16.to_le_bytes());
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&(u32::MAX - 36).to_le_bytes());
+    header
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, content_type, body.len(), body
+    )
+}
+
+/// Sends the live audio from the current position until the drill ends or the listener leaves.
+fn stream_audio(stream: &mut TcpStream, drill: &Drill) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: audio/wav\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n"
+    )?;
+    stream.write_all(&stream_header())?;
+
+    let Some(mut position) = drill.offset_at(Instant::now()) else {
+        return Ok(());
+    };
+    loop {
+        // Stay LEAD ahead of the live position, wrapping around for a repeating drill.
+        let Some(target) = drill.offset_at(Instant::now() + LEAD) else {
+            stream.write_all(&drill.pcm[position..])?;
+            return Ok(());
+        };
+        if target < position {
+            stream.write_all(&drill.pcm[position..])?;
+            position = 0;
+        }
+        stream.write_all(&drill.pcm[position..target])?;
+        position = target;
+        thread::sleep(CHUNK_INTERVAL);
+    }
+}
+
+fn serve(mut stream: TcpStream, drill: &Drill) {
+    let mut line = String::new();
+    let mut reader = BufReader::new(&stream);
+    if reader.read_line(&mut line).is_err() {
+        return;
+    }
+    // The rest of the request doesn't matter; drain the headers.
+    let mut header = String::new();
+    while reader.read_line(&mut header).is_ok_and(|n| n > 2) {
+        header.clear();
+    }
+    let path = line.split_whitespace().nth(1).unwrap_or("/");
+
+    let result = match path {
+        "/" | "/drill.wav" => stream_audio(&mut stream, drill),
+        "/key.txt" if drill.finished() => respond(&mut stream, "200 OK", "text/plain; charset=utf-8", &drill.key),
+        "/key.txt" => respond(&mut stream, "403 Forbidden", "text/plain", "The answer key is available once the drill is over.\n"),
+        _ => respond(&mut stream, "404 Not Found", "text/plain", "not found\n"),
+    };
+    if let Err(e) = result {
+        // Listeners leaving mid-stream is normal.
+        if e.kind() != std::io::ErrorKind::BrokenPipe && e.kind() != std::io::ErrorKind::ConnectionReset {
+            eprintln!("Error streaming to listener: {}", e);
+        }
+    }
+}
+
+/// Renders `items` at the configured speed and pitch and streams them live over HTTP as WAV.
+pub fn run(items: &[String], bind: &str, pause_ms: f32, repeat: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if items.is_empty() {
+        return Err("nothing to broadcast".into());
+    }
+    let config = AppConfig::load().unwrap_or_default();
+    let samples = render::render_items(items, &config.tone_settings(), pause_ms);
+    let pcm: Vec<u8> = samples.iter()
+        .flat_map(|s| ((s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_le_bytes())
+        .collect();
+    let key = export_audio::answer_key(items);
+
+    let listener = TcpListener::bind(bind)?;
+    let drill = Arc::new(Drill { pcm, key, start: Instant::now(), repeat });
+    println!("Broadcasting {} items ({:.1} minutes{}) on http://{}/drill.wav",
+        items.len(), drill.len_secs() / 60.0, if repeat { ", repeating" } else { "" }, bind);
+    println!("The answer key is at http://{}/key.txt once the drill is over. Press Ctrl+C to stop.", bind);
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let drill = Arc::clone(&drill);
+                thread::spawn(move || serve(stream, &drill));
+            }
+            Err(e) => eprintln!("Error accepting connection: {}", e),
+        }
+    }
+    Ok(())
+}
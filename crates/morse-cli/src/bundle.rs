@@ -0,0 +1,95 @@
+use std::{
+    fs::File,
+    io::{Cursor, Write},
+    path::Path,
+};
+use morse_audio::render::{self, ToneSettings};
+use morse_core::material;
+use serde_json::json;
+use zip::{write::SimpleFileOptions, ZipWriter};
+use crate::config::AppConfig;
+
+pub const MANIFEST_VERSION: u32 = 1;
+
+pub struct BundleOptions {
+    pub level: Option<u8>,
+    pub wpm: f32,
+    /// The configured sidetone pitch when unset.
+    pub pitch_hz: Option<f32>,
+    pub drills: usize,
+    pub items: usize,
+    pub pause_ms: f32,
+}
+
+fn answer_key(title: &str, items: &[String]) -> String {
+    let mut key = format!("{}\n\n", title);
+    for (i, item) in items.iter().enumerate() {
+        key.push_str(&format!("{:>3}. {}\n", i + 1, item));
+    }
+    key
+}
+
+/// Writes a zip of WAV drills with one answer key per drill and a `manifest.json`.
+pub fn export(options: BundleOptions, out: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load().unwrap_or_default();
+    let level = options.level.unwrap_or(config.difficulty_level).clamp(1, 8);
+    let progression = config.progression();
+    let chars = progression.chars_through(level);
+    let settings = ToneSettings {
+        wpm: options.wpm,
+        pitch_hz: options.pitch_hz.unwrap_or(config.pitch_hz()),
+        ..ToneSettings::default()
+    };
+
+    let mut zip = ZipWriter::new(File::create(out)?);
+    let file_options = SimpleFileOptions::default();
+    let mut rng = rand::rng();
+    let mut drills = Vec::new();
+    let mut total_secs = 0.0;
+    for n in 1..=options.drills {
+        let words = options.items / 2;
+        let items = material::groups_and_words(&chars, &progression.common_words, options.items - words, words, &mut rng);
+        let samples = render::render_items(&items, &settings, options.pause_ms);
+        let mut wav = Cursor::new(Vec::new());
+        render::write_wav(&mut wav, &samples)?;
+
+        let audio = format!("drill-{:02}.wav", n);
+        let key = format!("drill-{:02}-key.txt", n);
+        zip.start_file(audio.as_str(), file_options)?;
+        zip.write_all(wav.get_ref())?;
+        zip.start_file(key.as_str(), file_options)?;
+        let title = format!("Drill {} - Level {}, {} WPM", n, level, options.wpm);
+        zip.write_all(answer_key(&title, &items).as_bytes())?;
+
+        let secs = render::duration_secs(&samples);
+        total_secs += secs;
+        drills.push(json!({
+            "audio": audio,
+            "answer_key": key,
+            "items": items.len(),
+            "duration_s": (secs as f64 * 10.0).round() / 10.0,
+        }));
+    }
+
+    let manifest = json!({
+        "version": MANIFEST_VERSION,
+        "level": level,
+        "characters": chars.iter().collect::<String>(),
+        "wpm": options.wpm,
+        "pitch_hz": settings.pitch_hz,
+        "pause_ms": options.pause_ms,
+        "created": chrono::Local::now().to_rfc3339(),
+        "drills": drills,
+    });
+    zip.start_file("manifest.json", file_options)?;
+    zip.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+    zip.finish()?;
+
+    println!(
+        "Bundle with {} drills ({:.1} minutes of audio) written to {}",
+        options.drills,
+        total_secs / 60.0,
+        out.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,783 @@
+use std::path::PathBuf;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde_derive::{Serialize, Deserialize};
+use morse_audio::encode;
+use morse_core::{
+    keying::{self, KeyingNorms},
+    morse::{MORSE_MAPPING, PUNCTUATION_MAPPING},
+};
+use crate::{goals::GoalKindArg, import::ImportSource, keyer};
+
+#[derive(Debug, Parser)]
+#[command(name = "morse_code_learner", version, about = "Learn Morse code in the terminal")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Start a practice session (the default when no command is given)
+    Practice(PracticeArgs),
+    /// Encode text to Morse code
+    Encode {
+        /// Text to encode; read from stdin (or the clipboard) when omitted
+        text: Option<String>,
+        /// Read from and write the result to the system clipboard
+        #[arg(long)]
+        clipboard: bool,
+    },
+    /// Decode Morse code to text
+    Decode {
+        /// Code like ".- -... / -.-."; read from stdin (or the clipboard) when omitted
+        #[arg(allow_hyphen_values = true)]
+        code: Option<String>,
+        /// Read from and write the result to the system clipboard
+        #[arg(long)]
+        clipboard: bool,
+    },
+    /// Decode the Morse in a WAV recording, e.g. one taken off the air
+    DecodeAudio {
+        file: PathBuf,
+        /// Pitch of the signal in Hz; the strongest tone in the recording when omitted
+        #[arg(long)]
+        pitch: Option<f32>,
+        /// Rough speed of the sending, a starting point for following it
+        #[arg(long)]
+        wpm: Option<f32>,
+    },
+    /// Report element counts, characters and speed for Morse code, text or a timing trace
+    Analyze {
+        /// Morse code like ".- -... / -.-." or plain text
+        #[arg(required_unless_present_any = ["timings", "timings_file"])]
+        code: Option<String>,
+        /// Durations in milliseconds, positive for key down and negative for key up
+        #[arg(long, allow_hyphen_values = true, conflicts_with_all = ["code", "timings_file"])]
+        timings: Option<String>,
+        /// Read the timing trace from a file
+        #[arg(long, conflicts_with = "code")]
+        timings_file: Option<PathBuf>,
+        /// Text the trace was meant to send; draws the keying against ideal timing
+        #[arg(long, conflicts_with = "code")]
+        expected: Option<String>,
+        /// Timing norms to score the keying by, overriding `key` in the config
+        #[arg(long, value_enum, requires = "expected")]
+        key: Option<KeyPreset>,
+    },
+    /// Compose a short text that contains each chosen character several times
+    Generate {
+        /// Characters to cover, defaults to the ones introduced at your current level
+        #[arg(long)]
+        chars: Option<String>,
+        /// Minimum number of times each character must appear
+        #[arg(long, default_value_t = 3)]
+        min_count: usize,
+        /// Only use words made of characters you already know
+        #[arg(long)]
+        known_only: bool,
+    },
+    /// Create a printable PDF worksheet with an answer key for offline copy practice
+    Worksheet {
+        /// Level whose characters to use, defaults to your current level
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=8))]
+        level: Option<u8>,
+        #[arg(long, default_value = "worksheet.pdf")]
+        out: PathBuf,
+        /// Number of five-character code groups
+        #[arg(long, default_value_t = 10)]
+        groups: usize,
+        /// Number of words (replaced by groups if too few words fit the level)
+        #[arg(long, default_value_t = 10)]
+        words: usize,
+    },
+    /// Produce bundles of practice material for offline use
+    Bundle {
+        #[command(subcommand)]
+        action: BundleAction,
+    },
+    /// Show a session's items, answers, timings and playback settings for review by a mentor
+    Share {
+        /// Session number (1 is the oldest), `last`, or the start of its timestamp; lists sessions if omitted
+        session: Option<String>,
+        /// Write to a file instead of printing; a .json file gets the full session record
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Look into your practice history
+    Stats {
+        #[command(subcommand)]
+        action: StatsAction,
+    },
+    /// Set what you're working toward; suggested sessions share the time between your goals
+    Goal {
+        #[command(subcommand)]
+        action: GoalAction,
+    },
+    /// Exchange sessions with a morse_sync_server shared by your devices
+    Sync {
+        #[command(subcommand)]
+        action: SyncAction,
+    },
+    /// Change settings
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Add results from other training programs to your history
+    Import {
+        #[command(subcommand)]
+        action: ImportAction,
+    },
+    /// Take part in a simulated slow-speed net: copy the roll call, check in, copy the traffic
+    Net {
+        /// Built-in net when omitted; otherwise a scenario file or the name of one in your nets directory
+        scenario: Option<String>,
+        /// Your callsign, overriding `callsign` in the config
+        #[arg(long)]
+        call: Option<String>,
+        /// Rules your copy is judged by, e.g. `errors-per-minute:2,consecutive:3`
+        #[arg(long)]
+        rules: Option<String>,
+        /// Where to send net control's Morse
+        #[arg(long, value_enum, default_value_t = Output::Audio)]
+        output: Output,
+        /// List the available nets
+        #[arg(long, conflicts_with = "scenario")]
+        list: bool,
+    },
+    /// Take a receiving test modeled on a historical Morse license exam and get a pass/fail verdict
+    Exam {
+        /// Exam preset, e.g. `us-general`
+        #[arg(required_unless_present = "list")]
+        preset: Option<String>,
+        /// Rules to judge by instead of the preset's, e.g. `errors-per-minute:1,consecutive:3`
+        #[arg(long)]
+        rules: Option<String>,
+        /// Where to send the Morse
+        #[arg(long, value_enum, default_value_t = Output::Audio)]
+        output: Output,
+        /// List the exam presets
+        #[arg(long)]
+        list: bool,
+    },
+    /// Take a two-minute copy test at a set speed; results go into their own history
+    Speedtest {
+        /// Speed to test at, with no Farnsworth spacing
+        #[arg(long, required_unless_present = "history")]
+        wpm: Option<f32>,
+        /// Rules the test is passed by, e.g. `accuracy:95,consecutive:2`
+        #[arg(long)]
+        rules: Option<String>,
+        /// Where to send the Morse
+        #[arg(long, value_enum, default_value_t = Output::Audio)]
+        output: Output,
+        /// List your past speed tests
+        #[arg(long, conflicts_with = "wpm")]
+        history: bool,
+    },
+    /// Copy NTS radiograms and get graded field by field
+    Radiogram {
+        /// Number of messages
+        #[arg(long, default_value_t = 3)]
+        count: usize,
+        /// Character speed, overriding `character_wpm`
+        #[arg(long)]
+        wpm: Option<f32>,
+        /// Where to send the Morse
+        #[arg(long, value_enum, default_value_t = Output::Audio)]
+        output: Output,
+    },
+    /// Find your starting level and speed if you already know some Morse
+    Placement {
+        /// Where to send the Morse
+        #[arg(long, value_enum, default_value_t = Output::Audio)]
+        output: Output,
+    },
+    /// Listen to several words or a sentence in one go and only then write all of it down
+    HeadCopy {
+        /// Number of passages
+        #[arg(long, default_value_t = 5)]
+        passages: usize,
+        /// Words per passage, from your current level
+        #[arg(long, default_value_t = 4)]
+        words: usize,
+        /// Send a sentence from a QSO instead of words
+        #[arg(long, conflicts_with = "words")]
+        sentences: bool,
+        /// Character speed, overriding `character_wpm`
+        #[arg(long)]
+        wpm: Option<f32>,
+        /// Where to send the Morse
+        #[arg(long, value_enum, default_value_t = Output::Audio)]
+        output: Output,
+    },
+    /// Check the sound before a session: a pitch sweep, sample characters at your speed and the output delay
+    SoundCheck,
+    /// Find how fast you can read Morse by touch: characters are vibrated faster and faster until they blur, and the fastest you copied is kept
+    VibrationCheck,
+    /// Hands-free practice for driving or walking: items from your level are played and their answers spoken
+    Listen {
+        /// Minutes to practice, `session_duration` by default
+        #[arg(long)]
+        minutes: Option<u32>,
+        /// Seconds to answer in your head before the answer is spoken
+        #[arg(long, default_value_t = 3.0)]
+        delay: f32,
+        /// Character speed, overriding `character_wpm`
+        #[arg(long)]
+        wpm: Option<f32>,
+    },
+    /// Stream a drill live over HTTP so a group can copy it at the same time
+    Broadcast {
+        /// Text to send; without it, a session is generated as `practice` would
+        text: Option<String>,
+        /// Generate the session from a saved template
+        #[arg(long, conflicts_with = "text")]
+        template: Option<String>,
+        /// Address and port to listen on
+        #[arg(long, default_value = "0.0.0.0:8000")]
+        bind: String,
+        /// Silence after each item, in milliseconds, to write it down
+        #[arg(long, default_value_t = 3000.0)]
+        pause_ms: f32,
+        /// Start over when the drill ends instead of going quiet
+        #[arg(long)]
+        repeat: bool,
+    },
+    /// Export practice data
+    Export {
+        #[command(subcommand)]
+        target: ExportTarget,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BundleAction {
+    /// Zip of WAV listening drills with answer keys and a manifest
+    Export {
+        /// Level whose characters to use, defaults to your current level
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=8))]
+        level: Option<u8>,
+        #[arg(long, default_value_t = 20.0)]
+        wpm: f32,
+        /// Tone pitch in Hz, defaults to the configured sidetone pitch
+        #[arg(long)]
+        pitch: Option<f32>,
+        /// Number of drills, e.g. one per day of the week
+        #[arg(long, default_value_t = 7)]
+        drills: usize,
+        /// Groups and words per drill
+        #[arg(long, default_value_t = 25)]
+        items: usize,
+        /// Silence after each item, in milliseconds, to write it down
+        #[arg(long, default_value_t = 3000.0)]
+        pause_ms: f32,
+        #[arg(long, default_value = "morse_drills.zip")]
+        out: PathBuf,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum StatsAction {
+    /// Per-character speed and accuracy in one period against another
+    Compare {
+        /// First period as START..END (YYYY-MM-DD, either end may be left open)
+        #[arg(long)]
+        from: String,
+        /// Second period, e.g. 2025-03-01..
+        #[arg(long)]
+        to: String,
+    },
+    /// How sessions ended and how far into them you stopped early
+    Endings,
+    /// Expected sessions and practice time left per level, based on your past promotions
+    Estimate,
+    /// Days in a row you have practiced, now and at best
+    Streak,
+    /// How strongly each character is remembered, fading while it goes unpracticed
+    Strength,
+    /// Time spent in graded drills and listening passively, and the two together
+    Time,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum GoalAction {
+    /// Add a goal, e.g. `goal add receive 20 --priority 2`
+    Add {
+        #[arg(value_enum)]
+        kind: GoalKindArg,
+        /// A speed in WPM, or the characters to learn: letters, numbers, punctuation or a list
+        target: String,
+        /// How much of the practice time it gets next to the other goals, from 1 to 5
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=5))]
+        priority: u8,
+    },
+    /// Show each goal's progress and its share of the practice time
+    List,
+    /// Remove a goal by its number in `goal list`
+    Remove {
+        number: usize,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SyncAction {
+    /// Upload this device's sessions
+    Push {
+        /// Server URL, e.g. http://192.168.1.10:7373, overriding the configured one
+        #[arg(long)]
+        server: Option<String>,
+    },
+    /// Download sessions practiced on other devices into the local history
+    Pull {
+        /// Server URL, e.g. http://192.168.1.10:7373, overriding the configured one
+        #[arg(long)]
+        server: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigAction {
+    /// Leave characters or words out of all practice material, e.g. `config ban Q QUIZ`
+    Ban {
+        /// Single characters or whole words
+        #[arg(required = true)]
+        items: Vec<String>,
+    },
+    /// Allow banned characters or words again
+    Unban {
+        #[arg(required = true)]
+        items: Vec<String>,
+    },
+    /// Show the rise and fall time of each element, or set it in ms and hear a sample, e.g. `config envelope 5`
+    Envelope {
+        ms: Option<f32>,
+    },
+    /// Show the master volume, or set it in percent and hear a sample, e.g. `config volume 60`
+    Volume {
+        #[arg(value_parser = clap::value_parser!(u8).range(0..=100))]
+        percent: Option<u8>,
+    },
+    /// Show whether sound is muted, or turn muting `on` or `off`
+    Mute {
+        #[arg(value_parser = clap::builder::BoolishValueParser::new())]
+        muted: Option<bool>,
+    },
+    /// List the audio output devices and pick one to play on, or set it by name, e.g. `config device "USB Audio"`; `default` follows the system
+    Device {
+        name: Option<String>,
+    },
+    /// Show the serial port `--output vibration` keys and the ports there are, or set it, e.g. `config vibration /dev/ttyUSB0`; `off` unsets it
+    Vibration {
+        port: Option<String>,
+    },
+    /// Show how practice days are counted for the streak, or change it, e.g. `config streak --timezone Europe/Berlin --day-starts-at 4`
+    Streak {
+        /// IANA time zone the days are counted in, or `system`
+        #[arg(long)]
+        timezone: Option<String>,
+        /// Hour the practice day starts at, so late-night sessions count toward the day before
+        #[arg(long, value_parser = clap::value_parser!(u32).range(0..=12))]
+        day_starts_at: Option<u32>,
+    },
+    /// Show the sidetone pitch, or set it in Hz and hear a sample, e.g. `config pitch 700`
+    Pitch {
+        hz: Option<f32>,
+    },
+    /// Show the pitches `practice --random-pitch` picks from, or set them and hear both ends, e.g. `config pitch-range 450 850`
+    PitchRange {
+        /// Lowest pitch in Hz
+        #[arg(requires = "max_hz")]
+        min_hz: Option<f32>,
+        /// Highest pitch in Hz
+        max_hz: Option<f32>,
+    },
+    /// Show the band noise level, or set its signal-to-noise ratio in dB and hear a sample, e.g. `config noise 6`
+    Noise {
+        #[arg(allow_negative_numbers = true, conflicts_with = "off")]
+        snr_db: Option<f32>,
+        /// Back to a clean tone
+        #[arg(long)]
+        off: bool,
+    },
+    /// Show the fading (QSB) setup, or change it and hear a sample, e.g. `config qsb --depth 20 --period 8`
+    Qsb {
+        /// How far the signal drops at the bottom of a fade, in dB
+        #[arg(long, conflicts_with = "off")]
+        depth: Option<f32>,
+        /// Average seconds from one peak to the next
+        #[arg(long, conflicts_with = "off")]
+        period: Option<f32>,
+        /// Back to a steady tone
+        #[arg(long)]
+        off: bool,
+    },
+    /// Show the interfering signal (QRM) setup, or change it and hear a sample, e.g. `config qrm --offset 300 --level -6`
+    Qrm {
+        /// Pitch of the interfering signal relative to yours, in Hz
+        #[arg(long, allow_negative_numbers = true, conflicts_with = "off")]
+        offset: Option<f32>,
+        /// Its strength relative to yours, in dB
+        #[arg(long, allow_negative_numbers = true, conflicts_with = "off")]
+        level: Option<f32>,
+        /// No interference
+        #[arg(long)]
+        off: bool,
+    },
+    /// Show the stereo setup, or change it and hear a sample, e.g. `config stereo --ear left --right -6`
+    Stereo {
+        /// The ear your signal is heard in; the interfering one is in the other
+        #[arg(long, value_enum, conflicts_with = "off")]
+        ear: Option<Ear>,
+        /// Level of the left channel, in dB
+        #[arg(long, allow_negative_numbers = true, conflicts_with = "off")]
+        left: Option<f32>,
+        /// Level of the right channel, in dB
+        #[arg(long, allow_negative_numbers = true, conflicts_with = "off")]
+        right: Option<f32>,
+        /// Both signals in both ears again
+        #[arg(long)]
+        off: bool,
+    },
+    /// Show the fist setup, or make generated code less machine-perfect and hear a sample, e.g. `config fist --elements 10 --spacing 15`
+    Fist {
+        /// How far dots and dashes may run long or short, in percent
+        #[arg(long, conflicts_with = "off")]
+        elements: Option<f32>,
+        /// How far the gaps may run long or short, in percent
+        #[arg(long, conflicts_with = "off")]
+        spacing: Option<f32>,
+        /// Back to machine-perfect timing
+        #[arg(long)]
+        off: bool,
+    },
+    /// Show the paddle keyer setup, or change it to match the keyer on your rig
+    Keyer {
+        #[arg(long, value_enum)]
+        mode: Option<keyer::Mode>,
+        /// Swap the paddles for left-handed operation
+        #[arg(long)]
+        reverse: Option<bool>,
+    },
+    /// Show how much feedback practice gives, or set it, e.g. `config feedback terse`
+    Feedback {
+        #[arg(value_enum)]
+        style: Option<FeedbackStyle>,
+    },
+    /// Show the locale dates and numbers in reports are formatted for, or set it, e.g. `config locale de_DE`; `system` follows the system again
+    Locale {
+        name: Option<String>,
+    },
+    /// Show whether a sound tells right from wrong after each answer, or turn it `on` or `off`
+    AnswerSounds {
+        #[arg(value_parser = clap::builder::BoolishValueParser::new())]
+        enabled: Option<bool>,
+    },
+    /// Show whether a wrong answer is played back before the right code, or turn it `on` or `off`
+    ReplayAttempt {
+        #[arg(value_parser = clap::builder::BoolishValueParser::new())]
+        enabled: Option<bool>,
+    },
+    /// Show whether a metronome clicks once a word at your overall speed while you key answers, or turn it `on` or `off`
+    Metronome {
+        #[arg(value_parser = clap::builder::BoolishValueParser::new())]
+        enabled: Option<bool>,
+    },
+    /// Show whether a desktop notification shows how each session went and when you level up, or turn it `on` or `off`
+    Notifications {
+        #[arg(value_parser = clap::builder::BoolishValueParser::new())]
+        enabled: Option<bool>,
+    },
+    /// Show whether answers keyed on any key or the microphone are recorded to a WAV file, or turn it `on` or `off`
+    RecordKeying {
+        #[arg(value_parser = clap::builder::BoolishValueParser::new())]
+        enabled: Option<bool>,
+    },
+    /// Show whether typed dots and dashes sound a sidetone, or turn it `on` or `off`
+    Sidetone {
+        #[arg(value_parser = clap::builder::BoolishValueParser::new())]
+        enabled: Option<bool>,
+    },
+    /// List saved session templates
+    Templates,
+    /// Delete a saved session template
+    RemoveTemplate {
+        name: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ImportAction {
+    /// MorseRunner and RufzXP score lists or result reports
+    Results {
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// Program that wrote the files, when it can't be told from their contents
+        #[arg(long, value_enum)]
+        source: Option<ImportSource>,
+    },
+    /// Callsigns, names and exchanges from an ADIF log, for `practice --logbook`
+    Adif {
+        file: PathBuf,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ExportTarget {
+    /// WAV files of text or of a practice session, at your configured speed and pitch
+    Audio {
+        /// Text to render; without it, a session is generated as `practice` would
+        text: Option<String>,
+        /// Generate the session from a saved template
+        #[arg(long, conflicts_with = "text")]
+        template: Option<String>,
+        /// Silence after each item, in milliseconds, to write it down
+        #[arg(long, default_value_t = 3000.0)]
+        pause_ms: f32,
+        /// One file per item (per word for text) in the --out directory
+        #[arg(long)]
+        split: bool,
+        /// File format; by default the one --out is named for, or WAV
+        #[arg(long, value_enum)]
+        format: Option<AudioFormat>,
+        #[arg(long, default_value = "morse_practice.wav")]
+        out: PathBuf,
+    },
+    /// A lesson for every level, tagged as the tracks of one album for a music player
+    Course {
+        #[arg(long, value_enum, default_value_t = AudioFormat::Mp3)]
+        format: AudioFormat,
+        /// Groups and words in each lesson, after its new characters
+        #[arg(long, default_value_t = 25)]
+        items: usize,
+        /// Silence after each item, in milliseconds, to write it down
+        #[arg(long, default_value_t = 3000.0)]
+        pause_ms: f32,
+        #[arg(long, default_value = "morse_course")]
+        out: PathBuf,
+    },
+    /// Anonymized dataset for research on Morse acquisition (opt-in)
+    Research {
+        #[arg(long, default_value = "morse_research_export.json")]
+        out: PathBuf,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+/// Practice flags; saved as they are in session templates.
+#[derive(Debug, Clone, Default, Args, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PracticeArgs {
+    /// Practice pronounceable pseudo-words built only from characters you know
+    #[arg(long, conflicts_with = "cover")]
+    pub pseudo_words: bool,
+    /// Practice words that together contain each of these characters several times
+    #[arg(long, value_name = "CHARS")]
+    pub cover: Option<String>,
+    /// How often each --cover character must appear
+    #[arg(long, default_value_t = 2, requires = "cover")]
+    pub min_count: usize,
+    /// Where to send the Morse for each item
+    #[arg(long, value_enum, default_value_t = Output::Audio)]
+    pub output: Output,
+    /// Practice the callsigns, names and exchanges of stations from your imported logs
+    #[arg(long, conflicts_with_all = ["pseudo_words", "cover", "subset", "focus", "pipeline"])]
+    pub logbook: bool,
+    /// Practice generated grid squares or frequencies, common copy tasks on VHF and portable
+    #[arg(long, value_enum, conflicts_with_all = ["pseudo_words", "cover", "logbook", "subset", "focus", "pipeline"])]
+    pub drill: Option<Drill>,
+    /// Practice all characters of one kind, whatever your level
+    #[arg(long, value_enum)]
+    pub subset: Option<Subset>,
+    /// Drill only these characters, whatever your level
+    #[arg(long, value_name = "CHARS", conflicts_with_all = ["pseudo_words", "cover", "subset"])]
+    pub focus: Option<String>,
+    /// Listen to each item and type what you heard, instead of keying what is shown
+    #[arg(long)]
+    pub copy: bool,
+    /// Character speed for this session, overriding `character_wpm`
+    #[arg(long)]
+    pub wpm: Option<f32>,
+    /// Play each item at a different pitch from `pitch_range` in the config
+    #[arg(long)]
+    pub random_pitch: bool,
+    /// Key your answers on a code practice oscillator and have them decoded through the microphone
+    #[arg(long, conflicts_with = "copy")]
+    pub mic: bool,
+    /// Key your answers by holding the spacebar as a straight key; needs a terminal that reports key releases
+    #[arg(long, conflicts_with_all = ["copy", "mic"])]
+    pub spacebar: bool,
+    /// Key your answers through the keyer on two keys used as paddles, Z or ← for the left and X or → for the right
+    #[arg(long, conflicts_with_all = ["copy", "mic", "spacebar"])]
+    pub paddles: bool,
+    /// Key your answers on a straight key wired to a serial port such as /dev/ttyUSB0 or COM3,
+    /// or with --paddles, on paddles wired to it
+    #[arg(long, value_name = "PORT", conflicts_with_all = ["copy", "mic", "spacebar"])]
+    pub serial: Option<String>,
+    /// Play items on a K1EL WinKeyer on this serial port and key your answers on its paddles
+    #[arg(long, value_name = "PORT", conflicts_with_all = ["copy", "mic", "spacebar", "paddles", "serial"])]
+    pub winkeyer: Option<String>,
+    /// Key your answers on a MIDI pedal, pad or keyboard held as a straight key; the first
+    /// MIDI input, or the one whose name contains DEVICE
+    #[arg(long, value_name = "DEVICE", num_args = 0..=1, default_missing_value = "",
+        conflicts_with_all = ["copy", "mic", "spacebar", "paddles", "serial", "winkeyer"])]
+    pub midi: Option<String>,
+    /// Key your answers by holding a mouse button as a straight key, or with --paddles, on
+    /// the left and right buttons as paddles
+    #[arg(long, conflicts_with_all = ["copy", "mic", "spacebar", "serial", "winkeyer", "midi"])]
+    pub mouse: bool,
+    /// Practice the spoken form of each item, e.g. `dah-di-dah-dit`, both ways and without audio
+    #[arg(long, conflicts_with_all = ["copy", "mic", "spacebar", "paddles", "serial", "winkeyer", "midi", "mouse"])]
+    pub verbal: bool,
+    /// Key back each item right after hearing it, scored on the code and its rhythm, with no prompt between items
+    #[arg(long, conflicts_with_all = ["copy", "verbal"])]
+    pub echo: bool,
+    /// Feedback style for this session, overriding `feedback` in the config
+    #[arg(long, value_enum)]
+    pub feedback: Option<FeedbackStyle>,
+    /// Relearn the items that kept needing hints, step by step
+    #[arg(long, conflicts_with_all = ["pseudo_words", "cover", "logbook", "drill", "subset", "focus", "copy", "pipeline"])]
+    pub rehab: bool,
+    /// Number of exercises in the session
+    #[arg(long)]
+    pub items: Option<usize>,
+    /// Run stages back to back: a pipeline name from the config, or stages like
+    /// `chars:2,groups:3,words:3,review:2` (minutes each)
+    #[arg(long, conflicts_with_all = ["pseudo_words", "cover", "items"])]
+    pub pipeline: Option<String>,
+    /// Start a saved session template instead of giving the flags
+    #[arg(long, conflicts_with_all = ["pseudo_words", "cover", "logbook", "drill", "subset", "focus", "copy", "wpm", "random_pitch", "mic", "spacebar", "paddles", "serial", "winkeyer", "midi", "mouse", "verbal", "echo", "feedback", "rehab", "items", "pipeline", "save_template"])]
+    #[serde(skip)]
+    pub template: Option<String>,
+    /// Save the other flags under this name, then start the session
+    #[arg(long, value_name = "NAME")]
+    #[serde(skip)]
+    pub save_template: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Subset {
+    Letters,
+    Numbers,
+    Punctuation,
+}
+
+impl Subset {
+    pub fn chars(self) -> Vec<char> {
+        match self {
+            Subset::Letters => MORSE_MAPPING.iter().map(|(c, _)| *c).filter(|c| c.is_ascii_alphabetic()).collect(),
+            Subset::Numbers => MORSE_MAPPING.iter().map(|(c, _)| *c).filter(|c| c.is_ascii_digit()).collect(),
+            Subset::Punctuation => PUNCTUATION_MAPPING.iter().map(|(c, _)| *c).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyPreset {
+    /// Straight key (SKCC style): wide timing tolerance, low speeds, scored mostly on spacing
+    Straight,
+    /// Paddle and electronic keyer: tight timing
+    #[default]
+    Paddle,
+}
+
+impl KeyPreset {
+    pub fn norms(self) -> KeyingNorms {
+        match self {
+            KeyPreset::Straight => keying::STRAIGHT_KEY,
+            KeyPreset::Paddle => keying::PADDLE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Drill {
+    /// Maidenhead locators like JO62 and JO62QM
+    Grids,
+    /// Frequencies like 14.025 and offsets like +1.5
+    Frequencies,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Output {
+    #[default]
+    Audio,
+    /// Blink the caps-lock LED or keyboard backlight (Linux, needs write access to /sys/class/leds)
+    Led,
+    /// Ring the terminal bell for each element, for sessions without an audio stack
+    Bell,
+    /// Vibrate a motor or wearable on the serial port set with `config vibration`
+    Vibration,
+    /// Print a "beep boop" animation in time with the code
+    Text,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Ear {
+    #[default]
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum AudioFormat {
+    Wav,
+    /// Ogg Vorbis, encoded with ffmpeg
+    Ogg,
+    /// MP3, encoded with ffmpeg
+    Mp3,
+}
+
+impl AudioFormat {
+    pub fn encoding(self) -> encode::AudioFormat {
+        match self {
+            AudioFormat::Wav => encode::AudioFormat::Wav,
+            AudioFormat::Ogg => encode::AudioFormat::Ogg,
+            AudioFormat::Mp3 => encode::AudioFormat::Mp3,
+        }
+    }
+}
+
+/// How much is said about each answer and at the end of a session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedbackStyle {
+    /// Just a tick or a cross and the right code, and a one-line summary
+    Terse,
+    /// Response times, your answer next to the right one, and a full summary
+    #[default]
+    Detailed,
+    /// Detailed, plus how each letter sounds and a word to remember it by
+    Mnemonic,
+    /// Terse text; a miss plays back what you sent, then what was right
+    Echo,
+}
+
+/// How a value is written on the command line, e.g. `bell`.
+pub fn value_name<T: ValueEnum>(value: &T) -> String {
+    value.to_possible_value().map(|v| v.get_name().to_string()).unwrap_or_default()
+}
+
+impl PracticeArgs {
+    /// Sessions on custom material don't count toward level progression.
+    pub fn is_custom_material(&self) -> bool {
+        self.pseudo_words || self.cover.is_some() || self.logbook || self.drill.is_some() || self.subset.is_some() || self.focus.is_some() || self.pipeline.is_some() || self.rehab
+    }
+
+    /// Copy, spoken-form, echo and custom-material sessions don't count toward level progression.
+    pub fn counts_toward_progression(&self) -> bool {
+        !(self.is_custom_material() || self.copy || self.verbal || self.echo)
+    }
+
+    /// Whether the session is made of words rather than character drills.
+    pub fn is_word_material(&self) -> bool {
+        self.pseudo_words || self.cover.is_some() || self.logbook || self.drill.is_some()
+    }
+}
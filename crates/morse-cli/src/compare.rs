@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+use chrono::NaiveDate;
+use morse_stats::{LearningSession, UserStats};
+use crate::{layout::{self, Align, Table}, locale};
+
+const CHART_WIDTH: f32 = 20.0;
+
+/// Dates from `start` to `end` inclusive; either end may be open.
+#[derive(Debug, Clone, Copy)]
+pub struct Period {
+    pub start: Option<NaiveDate>,
+    pub end: Option<NaiveDate>,
+}
+
+impl Period {
+    /// Parses `START..END` with dates as YYYY-MM-DD, e.g. `2025-01-01..2025-01-31`,
+    /// `..2025-03-01` or `2025-03-01..`. A single date is that day alone.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let date = |s: &str| -> Result<Option<NaiveDate>, String> {
+            let s = s.trim();
+            if s.is_empty() {
+                return Ok(None);
+            }
+            NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .map(Some)
+                .map_err(|_| format!("invalid date '{}', expected YYYY-MM-DD", s))
+        };
+        match text.split_once("..") {
+            Some((start, end)) => Ok(Period { start: date(start)?, end: date(end)? }),
+            None => {
+                let day = date(text)?.ok_or("empty period")?;
+                Ok(Period { start: Some(day), end: Some(day) })
+            }
+        }
+    }
+
+    fn contains(&self, session: &LearningSession) -> bool {
+        let day = session.timestamp.date_naive();
+        self.start.is_none_or(|s| day >= s) && self.end.is_none_or(|e| day <= e)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct CharSummary {
+    attempts: u32,
+    correct: u32,
+    time_sum: f32,
+    timed: u32,
+}
+
+impl CharSummary {
+    fn accuracy(&self) -> Option<f32> {
+        (self.attempts > 0).then(|| self.correct as f32 / self.attempts as f32)
+    }
+
+    fn mean_time(&self) -> Option<f32> {
+        (self.timed > 0).then(|| self.time_sum / self.timed as f32)
+    }
+}
+
+/// Per-character results of the character drills in `period`. Only correct, trustworthy
+/// answers count toward the response time.
+fn summarize(stats: &UserStats, period: &Period) -> (usize, BTreeMap<char, CharSummary>) {
+    let sessions: Vec<&LearningSession> = stats.session_history.iter().filter(|s| period.contains(s)).collect();
+    let mut chars: BTreeMap<char, CharSummary> = BTreeMap::new();
+    for answer in sessions.iter().flat_map(|s| s.answers.iter()) {
+        let mut item = answer.item.chars();
+        let (Some(c), None) = (item.next(), item.next()) else {
+            continue;
+        };
+        let summary = chars.entry(c).or_default();
+        summary.attempts += 1;
+        if answer.correct {
+            summary.correct += 1;
+            if !answer.suspect {
+                summary.time_sum += answer.response_time;
+                summary.timed += 1;
+            }
+        }
+    }
+    (sessions.len(), chars)
+}
+
+fn cell<T>(value: Option<T>, format: impl Fn(T) -> String) -> String {
+    value.map(format).unwrap_or_else(|| "-".to_string())
+}
+
+pub fn run(from: &str, to: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let (from_period, to_period) = (Period::parse(from)?, Period::parse(to)?);
+    let stats = UserStats::load()?;
+    let (from_sessions, before) = summarize(&stats, &from_period);
+    let (to_sessions, after) = summarize(&stats, &to_period);
+    if before.is_empty() || after.is_empty() {
+        return Err(format!(
+            "not enough character practice to compare ({} sessions in {}, {} in {})",
+            from_sessions, from, to_sessions, to
+        ).into());
+    }
+
+    println!("Comparing {} ({} sessions) with {} ({} sessions)\n", from, from_sessions, to, to_sessions);
+    let mut table = Table::new(&[
+        ("Char", Align::Left), ("Time A", Align::Right), ("Time B", Align::Right), ("Change", Align::Right),
+        ("Acc A", Align::Right), ("Acc B", Align::Right), ("Change", Align::Right),
+    ]);
+    let mut changes = Vec::new();
+    for c in before.keys().chain(after.keys()).collect::<std::collections::BTreeSet<_>>() {
+        let a = before.get(c).copied().unwrap_or_default();
+        let b = after.get(c).copied().unwrap_or_default();
+        let time_change = a.mean_time().zip(b.mean_time()).map(|(a, b)| b - a);
+        let accuracy_change = a.accuracy().zip(b.accuracy()).map(|(a, b)| b - a);
+        table.row(vec![
+            c.to_string(),
+            cell(a.mean_time(), |t| format!("{}s", locale::decimal(t, 2))),
+            cell(b.mean_time(), |t| format!("{}s", locale::decimal(t, 2))),
+            cell(time_change, |t| format!("{}s", locale::signed(t, 2))),
+            cell(a.accuracy(), |x| format!("{:.0}%", x * 100.0)),
+            cell(b.accuracy(), |x| format!("{:.0}%", x * 100.0)),
+            cell(accuracy_change, |x| format!("{:+.0}", x * 100.0)),
+        ]);
+        if let Some(change) = time_change {
+            changes.push((*c, change));
+        }
+    }
+    table.print();
+
+    if changes.is_empty() {
+        return Ok(());
+    }
+    // Bars grow left for characters that got faster and right for ones that slowed down.
+    let largest = changes.iter().map(|(_, t)| t.abs()).fold(f32::EPSILON, f32::max);
+    // Half of what's left beside the character and the change, for each direction.
+    let chart_width = layout::columns().map_or(CHART_WIDTH, |c| (c.saturating_sub(12) / 2).max(1) as f32).min(CHART_WIDTH);
+    println!("\nResponse time change (faster ◀ │ ▶ slower)");
+    for (c, change) in changes {
+        let length = (change.abs() / largest * chart_width).round() as usize;
+        let bar = "█".repeat(length);
+        if change < 0.0 {
+            println!("{} {:>w$}│ {}s", c, bar, locale::signed(change, 2), w = chart_width as usize);
+        } else {
+            println!("{} {:>w$}│{} {}s", c, "", bar, locale::signed(change, 2), w = chart_width as usize);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,841 @@
+use std::{collections::BTreeMap, fs, io::{self, Write}, path::PathBuf, thread, time::Duration};
+use serde_derive::{Serialize, Deserialize};
+use morse_audio::{fading::Fading, qrm::Qrm, render::ToneSettings, stereo::{self, Stereo}, Cue, PITCH_HZ};
+use morse_core::{progression::ProgressionSystem, timing::{self, Fist, Spacing, MAX_FIST_JITTER}};
+use crate::{ban::BanList, cli::{self, Ear, FeedbackStyle, KeyPreset, PracticeArgs}, hooks::HookConfig, goals::Goal, keyer::KeyerConfig, locale, probation::Probation, recording, scoring::ScoringConfig, speed::{SpeedConfig, DEFAULT_WPM}, streak::StreakConfig, sync::SyncConfig};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AppConfig {
+    pub difficulty_level: u8,
+    pub session_duration: u32,
+    pub known_chars: Vec<char>,
+    /// Speed of the characters themselves; `DEFAULT_WPM` when neither speed is set.
+    #[serde(default)]
+    pub character_wpm: Option<f32>,
+    /// Overall speed, reached by lengthening the gaps between characters and words
+    /// (Farnsworth spacing); the character speed when unset.
+    #[serde(default)]
+    pub effective_wpm: Option<f32>,
+    /// Sidetone pitch in Hz for all generated audio; 600 Hz when unset.
+    #[serde(default)]
+    pub pitch_hz: Option<f32>,
+    /// Rise and fall time of each element in ms, which keeps the tone from clicking;
+    /// `morse_audio::ramp_ms` when unset.
+    #[serde(default)]
+    pub ramp_ms: Option<f32>,
+    /// Pitches `practice --random-pitch` picks from.
+    #[serde(default)]
+    pub pitch_range: PitchRange,
+    /// Master volume in percent for all sound; full when unset.
+    #[serde(default)]
+    pub volume: Option<u8>,
+    /// Silences all sound, leaving `volume` as it was.
+    #[serde(default)]
+    pub muted: bool,
+    /// Name of the audio output device to play on; the system default when unset.
+    #[serde(default)]
+    pub output_device: Option<String>,
+    /// Serial port of the vibration motor or wearable that `--output vibration` keys.
+    #[serde(default)]
+    pub vibration_port: Option<String>,
+    /// Dot length in ms that vibration keys at, as found by `vibration-check`; about 8 WPM when unset.
+    #[serde(default)]
+    pub vibration_unit_ms: Option<f32>,
+    /// Signal-to-noise ratio in dB of band noise under all generated audio; a clean tone when unset.
+    #[serde(default)]
+    pub noise_snr_db: Option<f32>,
+    /// Fading of the tone in all generated audio; a steady tone when unset.
+    #[serde(default)]
+    pub qsb: Option<QsbConfig>,
+    /// Another station sending under all generated audio.
+    #[serde(default)]
+    pub qrm: Option<QrmConfig>,
+    /// Your signal in one ear and the interference in the other.
+    #[serde(default)]
+    pub stereo: Option<StereoConfig>,
+    /// Irregular timing in all generated audio, like a human sender; machine-perfect when unset.
+    #[serde(default)]
+    pub fist: Option<FistConfig>,
+    #[serde(default)]
+    pub scoring: ScoringConfig,
+    /// Your callsign, for nets.
+    #[serde(default)]
+    pub callsign: Option<String>,
+    /// The key you send with, which sets the timing norms keying is scored by.
+    #[serde(default)]
+    pub key: KeyPreset,
+    #[serde(default)]
+    pub keyer: KeyerConfig,
+    /// Sound each dot and dash as it is typed in answers.
+    #[serde(default)]
+    pub keyed_sidetone: bool,
+    /// Play a short sound after each answer, a different one for right and wrong.
+    #[serde(default)]
+    pub answer_sounds: bool,
+    /// Play back a wrong answer before the right code, as echo feedback does.
+    #[serde(default)]
+    pub replay_attempt: bool,
+    /// Click once a word at the overall speed while answers are keyed.
+    #[serde(default)]
+    pub metronome: bool,
+    /// Show a desktop notification at the end of each session and on levelling up.
+    #[serde(default)]
+    pub notifications: bool,
+    /// Write the answers keyed on any key or the microphone in each session to a WAV file.
+    #[serde(default)]
+    pub record_keying: bool,
+    /// How much practice says about each answer and each session.
+    #[serde(default)]
+    pub feedback: FeedbackStyle,
+    /// Locale dates and numbers in reports are formatted for, e.g. `de_DE`; the system's when unset.
+    #[serde(default)]
+    pub locale: Option<String>,
+    #[serde(default)]
+    pub hooks: HookConfig,
+    #[serde(default)]
+    pub sync: SyncConfig,
+    /// Set while a recent promotion can still be reverted.
+    #[serde(default)]
+    pub probation: Option<Probation>,
+    #[serde(default)]
+    pub speed: SpeedConfig,
+    #[serde(default)]
+    pub streak: StreakConfig,
+    #[serde(default)]
+    pub banned: BanList,
+    /// Saved practice flags by name.
+    #[serde(default)]
+    pub templates: BTreeMap<String, PracticeArgs>,
+    /// What practice is working toward; the suggested session balances them.
+    #[serde(default)]
+    pub goals: Vec<Goal>,
+    /// Named stage lists for `practice --pipeline`, e.g. `daily = "chars:2,words:3"`.
+    #[serde(default)]
+    pub pipelines: BTreeMap<String, String>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig {
+            difficulty_level: 1,
+            session_duration: 5,
+            known_chars: vec![],
+            character_wpm: None,
+            effective_wpm: None,
+            pitch_hz: None,
+            ramp_ms: None,
+            pitch_range: PitchRange::default(),
+            volume: None,
+            muted: false,
+            output_device: None,
+            vibration_port: None,
+            vibration_unit_ms: None,
+            noise_snr_db: None,
+            qsb: None,
+            qrm: None,
+            stereo: None,
+            fist: None,
+            scoring: ScoringConfig::default(),
+            callsign: None,
+            key: KeyPreset::default(),
+            keyer: KeyerConfig::default(),
+            keyed_sidetone: false,
+            answer_sounds: false,
+            replay_attempt: false,
+            metronome: false,
+            notifications: false,
+            record_keying: false,
+            feedback: FeedbackStyle::default(),
+            locale: None,
+            hooks: HookConfig::default(),
+            sync: SyncConfig::default(),
+            probation: None,
+            speed: SpeedConfig::default(),
+            streak: StreakConfig::default(),
+            banned: BanList::default(),
+            templates: BTreeMap::new(),
+            goals: Vec::new(),
+            pipelines: BTreeMap::new(),
+        }
+    }
+}
+
+impl AppConfig {
+    pub fn config_path() -> PathBuf {
+        morse_platform::paths::data_file("morse_config.toml")
+    }
+
+    /// The curriculum and word list with banned items taken out.
+    pub fn progression(&self) -> ProgressionSystem {
+        let mut progression = ProgressionSystem::new();
+        self.banned.apply(&mut progression);
+        progression
+    }
+
+    /// Playback timing from the speed settings, with `wpm` overriding the character speed.
+    /// Standard timing at `DEFAULT_WPM` when no speed is set.
+    pub fn spacing(&self, wpm: Option<f32>) -> Spacing {
+        let character = wpm.or(self.character_wpm);
+        if character.is_none() && self.effective_wpm.is_none() {
+            return Spacing::standard(timing::unit_ms_for_wpm(DEFAULT_WPM));
+        }
+        let character = character.unwrap_or(DEFAULT_WPM);
+        Spacing::farnsworth(character, self.effective_wpm.unwrap_or(character))
+    }
+
+    /// Time between metronome clicks: one PARIS word at the overall speed.
+    pub fn beat(&self, wpm: Option<f32>) -> Duration {
+        Duration::from_secs_f32(self.spacing(wpm).word_ms() / 1000.0)
+    }
+
+    pub fn volume(&self) -> u8 {
+        self.volume.unwrap_or(100)
+    }
+
+    pub fn vibration_unit_ms(&self) -> f32 {
+        self.vibration_unit_ms.unwrap_or(morse_audio::vibration::VIBRATION_UNIT_MS)
+    }
+
+    /// Sets up the sound of everything played or rendered from the volume, keying envelope
+    /// and output device settings.
+    pub fn apply_audio_settings(&self) {
+        morse_audio::set_volume(self.output_volume());
+        morse_audio::set_output_device(self.output_device.clone());
+        if let Some(ms) = self.ramp_ms {
+            morse_audio::set_ramp_ms(ms);
+        }
+    }
+
+    /// The master volume sound is played at, from 0.0 to 1.0.
+    pub fn output_volume(&self) -> f32 {
+        if self.muted { 0.0 } else { self.volume() as f32 / 100.0 }
+    }
+
+    pub fn pitch_hz(&self) -> f32 {
+        self.pitch_hz.unwrap_or(PITCH_HZ)
+    }
+
+    /// Speeds and pitch for rendered audio.
+    pub fn tone_settings(&self) -> ToneSettings {
+        ToneSettings {
+            wpm: self.character_wpm.unwrap_or(DEFAULT_WPM),
+            effective_wpm: self.effective_wpm,
+            pitch_hz: self.pitch_hz(),
+            noise_snr_db: self.noise_snr_db,
+            fading: self.qsb.map(|qsb| Fading { depth_db: qsb.depth_db, period_secs: qsb.period_secs }),
+            qrm: self.qrm.map(|qrm| Qrm { offset_hz: qrm.offset_hz, level_db: qrm.level_db }),
+            stereo: self.stereo.map(|s| Stereo {
+                target: match s.ear {
+                    Ear::Left => stereo::Ear::Left,
+                    Ear::Right => stereo::Ear::Right,
+                },
+                left_db: s.left_db,
+                right_db: s.right_db,
+            }),
+            fist: self.fist.map(|f| Fist { element: f.element_pct / 100.0, spacing: f.spacing_pct / 100.0 }),
+            ..ToneSettings::default()
+        }
+    }
+
+    fn farnsworth(&self) -> bool {
+        self.effective_wpm.is_some_and(|e| e < self.character_wpm.unwrap_or(DEFAULT_WPM))
+    }
+
+    /// The speed the ramp raises: the effective speed while it is below the character
+    /// speed, so the gaps close first, then the character speed.
+    pub fn ramped_wpm(&self) -> f32 {
+        match self.effective_wpm {
+            Some(effective) if self.farnsworth() => effective,
+            _ => self.character_wpm.unwrap_or(DEFAULT_WPM),
+        }
+    }
+
+    pub fn set_ramped_wpm(&mut self, wpm: f32) {
+        if self.farnsworth() {
+            let character = self.character_wpm.unwrap_or(DEFAULT_WPM);
+            self.effective_wpm = Some(wpm.min(character));
+        } else {
+            self.character_wpm = Some(wpm);
+            self.effective_wpm = self.effective_wpm.map(|e| e.max(wpm));
+        }
+    }
+
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Self::config_path();
+        if path.exists() {
+            let data = fs::read_to_string(&path)?;
+            Self::from_toml(&data)
+        } else {
+            let config = AppConfig::default();
+            config.save()?;
+            Ok(config)
+        }
+    }
+
+    pub fn from_toml(data: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut config: AppConfig = toml::from_str(data)?;
+        if let Some(wpm) = config.speed.wpm.take() {
+            config.character_wpm.get_or_insert(wpm);
+        }
+        // Hand edits can leave lowercase letters, repeats or characters without a code.
+        let curriculum = ProgressionSystem::with_words(Vec::new());
+        config.known_chars = curriculum.curriculum_order(&config.known_chars);
+        if let Some(probation) = config.probation.as_mut() {
+            probation.previous_known_chars = curriculum.curriculum_order(&probation.previous_known_chars);
+        }
+        Ok(config)
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::config_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        
+        let data = toml::to_string(self)?;
+        fs::write(&path, data)?;
+        Ok(())
+    }
+}
+
+/// Range accepted for the sidetone pitch.
+pub const MIN_PITCH_HZ: f32 = 300.0;
+pub const MAX_PITCH_HZ: f32 = 1500.0;
+
+/// Shows the sidetone pitch, or sets it and plays a sample at the new pitch.
+pub fn pitch(hz: Option<f32>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    let Some(hz) = hz else {
+        println!("Sidetone pitch: {:.0} Hz", config.pitch_hz());
+        return Ok(());
+    };
+    if !(MIN_PITCH_HZ..=MAX_PITCH_HZ).contains(&hz) {
+        return Err(format!("pitch must be between {:.0} and {:.0} Hz", MIN_PITCH_HZ, MAX_PITCH_HZ).into());
+    }
+    config.pitch_hz = Some(hz);
+    config.save()?;
+    println!("Sidetone pitch set to {:.0} Hz.", hz);
+    if morse_audio::audio_available() {
+        let sample = morse_core::morse::encode("VVV");
+        morse_audio::play_morse_code_at(&sample, config.spacing(None), hz);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct PitchRange {
+    pub min_hz: f32,
+    pub max_hz: f32,
+}
+
+impl Default for PitchRange {
+    fn default() -> Self {
+        PitchRange { min_hz: 450.0, max_hz: 850.0 }
+    }
+}
+
+/// Shows the range random pitches are picked from, or changes it and plays its ends.
+pub fn pitch_range(min_hz: Option<f32>, max_hz: Option<f32>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    let mut range = config.pitch_range;
+    let (Some(min_hz), Some(max_hz)) = (min_hz, max_hz) else {
+        println!("Random pitch range: {:.0} to {:.0} Hz", range.min_hz, range.max_hz);
+        return Ok(());
+    };
+    for hz in [min_hz, max_hz] {
+        if !(MIN_PITCH_HZ..=MAX_PITCH_HZ).contains(&hz) {
+            return Err(format!("pitch must be between {:.0} and {:.0} Hz", MIN_PITCH_HZ, MAX_PITCH_HZ).into());
+        }
+    }
+    if min_hz >= max_hz {
+        return Err("the lowest pitch must be below the highest".into());
+    }
+    range.min_hz = min_hz;
+    range.max_hz = max_hz;
+    config.pitch_range = range;
+    config.save()?;
+    println!("Random pitch range set to {:.0} to {:.0} Hz.", min_hz, max_hz);
+    if morse_audio::audio_available() {
+        let sample = morse_core::morse::encode("VVV");
+        for hz in [min_hz, max_hz] {
+            morse_audio::play_morse_code_at(&sample, config.spacing(None), hz);
+        }
+    }
+    Ok(())
+}
+
+pub const MAX_RAMP_MS: f32 = 20.0;
+
+/// Name that picks the system default in `config device`.
+const DEFAULT_DEVICE: &str = "default";
+
+/// Lists the audio output devices with the one sound plays on, or picks one: by name, from
+/// a numbered menu when run in a terminal, or `default` for the system default.
+pub fn device(name: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    let devices = morse_audio::output_devices();
+    let name = match name {
+        Some(name) => name,
+        None => {
+            println!("Sound plays on: {}", config.output_device.as_deref().unwrap_or("the system default"));
+            if devices.is_empty() {
+                println!("No audio output devices found.");
+                return Ok(());
+            }
+            for (i, device) in devices.iter().enumerate() {
+                let current = config.output_device.as_ref() == Some(device);
+                println!("{:>3}. {}{}", i + 1, device, if current { "  (current)" } else { "" });
+            }
+            if !morse_platform::terminal::is_interactive() {
+                return Ok(());
+            }
+            print!("Pick a device by number, 0 for the system default, or press Enter to keep it: ");
+            io::stdout().flush()?;
+            let choice = morse_platform::terminal::read_line().unwrap_or_default();
+            match choice.trim() {
+                "" => return Ok(()),
+                "0" => DEFAULT_DEVICE.to_string(),
+                number => number.parse::<usize>().ok()
+                    .and_then(|n| devices.get(n.wrapping_sub(1)))
+                    .cloned()
+                    .ok_or_else(|| format!("no device {}", number))?,
+            }
+        }
+    };
+
+    if name == DEFAULT_DEVICE {
+        config.output_device = None;
+        println!("Sound now plays on the system default device.");
+    } else if devices.contains(&name) {
+        println!("Sound now plays on {}.", name);
+        config.output_device = Some(name);
+    } else {
+        return Err(format!("no audio output device '{}' (see `config device`)", name).into());
+    }
+    config.save()?;
+    config.apply_audio_settings();
+    if morse_audio::audio_available() {
+        morse_audio::play_morse_code_at(&morse_core::morse::encode("VVV"), config.spacing(None), config.pitch_hz());
+    }
+    Ok(())
+}
+
+/// Name that unsets the vibration device in `config vibration`.
+const NO_VIBRATION: &str = "off";
+
+/// Shows the serial port vibration output keys and the ports there are, or sets it and
+/// buzzes a sample, or unsets it with `off`.
+pub fn vibration(port: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    let Some(port) = port else {
+        println!("Vibration device: {}", config.vibration_port.as_deref().unwrap_or("none"));
+        let ports = morse_platform::serial::ports();
+        match ports.is_empty() {
+            true => println!("No serial ports found."),
+            false => println!("Serial ports: {}", ports.join(", ")),
+        }
+        return Ok(());
+    };
+    if port == NO_VIBRATION {
+        config.vibration_port = None;
+        config.save()?;
+        println!("Vibration device unset.");
+        return Ok(());
+    }
+    morse_audio::vibration::vibrate_code(&port, &morse_core::morse::encode("V"), config.vibration_unit_ms())
+        .map_err(|e| format!("can't vibrate on {}: {}", port, e))?;
+    println!("Vibration now keys on {}.", port);
+    config.vibration_port = Some(port);
+    config.save()
+}
+
+/// Shows the rise and fall time of the elements, or changes it and plays a sample.
+pub fn envelope(ms: Option<f32>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    let Some(ms) = ms else {
+        println!("Element rise and fall: {:.1} ms", morse_audio::ramp_ms());
+        return Ok(());
+    };
+    if !(0.0..=MAX_RAMP_MS).contains(&ms) {
+        return Err(format!("rise and fall time must be between 0 and {:.0} ms", MAX_RAMP_MS).into());
+    }
+    config.ramp_ms = Some(ms);
+    config.save()?;
+    println!("Element rise and fall set to {:.1} ms.", ms);
+    if ms == 0.0 {
+        println!("Elements now start and stop hard, which clicks.");
+    }
+    if morse_audio::audio_available() {
+        config.apply_audio_settings();
+        let sample = morse_core::morse::encode("VVV");
+        morse_audio::play_morse_code_at(&sample, config.spacing(None), config.pitch_hz());
+    }
+    Ok(())
+}
+
+/// Shows the master volume, or changes it and plays a sample.
+pub fn volume(percent: Option<u8>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    let Some(percent) = percent else {
+        println!("Volume: {}%{}", config.volume(), if config.muted { " (muted)" } else { "" });
+        return Ok(());
+    };
+    config.volume = Some(percent);
+    config.save()?;
+    println!("Volume set to {}%.", percent);
+    if config.muted {
+        println!("Sound is muted; `config mute off` turns it back on.");
+    } else if morse_audio::audio_available() {
+        config.apply_audio_settings();
+        let sample = morse_core::morse::encode("VVV");
+        morse_audio::play_morse_code_at(&sample, config.spacing(None), config.pitch_hz());
+    }
+    Ok(())
+}
+
+/// Shows whether sound is muted, or mutes or unmutes it.
+pub fn mute(muted: Option<bool>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    if let Some(muted) = muted {
+        config.muted = muted;
+        config.save()?;
+    }
+    println!("Sound: {}", if config.muted { "muted".to_string() } else { format!("on ({}%)", config.volume()) });
+    Ok(())
+}
+
+/// Shows the locale reports are formatted for, or sets it; `system` goes back to the system's.
+pub fn locale(name: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    let Some(name) = name else {
+        match &config.locale {
+            Some(name) => println!("Reports are formatted for {}.", name),
+            None => println!("Reports are formatted for the system locale ({:?}).", locale::current()),
+        }
+        return Ok(());
+    };
+    if name == "system" {
+        config.locale = None;
+        config.save()?;
+        println!("Reports are formatted for the system locale again.");
+        return Ok(());
+    }
+    let parsed = locale::parse(&name).ok_or_else(|| format!("unknown locale '{}', expected a name like de_DE", name))?;
+    config.locale = Some(format!("{:?}", parsed));
+    config.save()?;
+    let now = chrono::Local::now().fixed_offset();
+    println!("Reports are now formatted for {:?}, e.g. {} and {}.", parsed, locale::date_time(&now), locale::decimal(87.5, 1));
+    Ok(())
+}
+
+/// Shows the feedback style practice uses, or changes it.
+pub fn feedback(style: Option<FeedbackStyle>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    match style {
+        Some(style) => {
+            config.feedback = style;
+            config.save()?;
+            println!("Feedback style set to {}.", cli::value_name(&style));
+        }
+        None => println!("Feedback style: {}", cli::value_name(&config.feedback)),
+    }
+    Ok(())
+}
+
+/// Shows whether a wrong answer is played back before the right code, or turns it on or off.
+pub fn replay_attempt(enabled: Option<bool>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    if let Some(enabled) = enabled {
+        config.replay_attempt = enabled;
+        config.save()?;
+    }
+    println!("Play back wrong answers: {}", if config.replay_attempt { "on" } else { "off" });
+    Ok(())
+}
+
+/// Shows whether a metronome clicks while answers are keyed, and how often, or turns it on
+/// or off.
+pub fn metronome(enabled: Option<bool>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    if let Some(enabled) = enabled {
+        config.metronome = enabled;
+        config.save()?;
+    }
+    let wpm = 60.0 / config.beat(None).as_secs_f32();
+    println!("Metronome while keying: {} (a click every {} s, one word at {:.0} WPM)",
+        if config.metronome { "on" } else { "off" }, locale::decimal(config.beat(None).as_secs_f32(), 1), wpm);
+    Ok(())
+}
+
+/// Shows whether sessions end with a desktop notification, or turns it on or off.
+pub fn notifications(enabled: Option<bool>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    if let Some(enabled) = enabled {
+        config.notifications = enabled;
+        config.save()?;
+    }
+    println!("Desktop notifications: {}", if config.notifications { "on" } else { "off" });
+    Ok(())
+}
+
+/// Shows whether keyed answers are recorded, or turns it on or off.
+pub fn record_keying(enabled: Option<bool>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    if let Some(enabled) = enabled {
+        config.record_keying = enabled;
+        config.save()?;
+    }
+    println!("Record keyed answers: {}", if config.record_keying { "on" } else { "off" });
+    if config.record_keying {
+        println!("Answers keyed on any key or the microphone are saved as WAV files in {}", recording::dir().display());
+    }
+    Ok(())
+}
+
+/// Pause between the two sounds played when answer sounds are turned on.
+const CUE_SAMPLE_GAP: Duration = Duration::from_millis(400);
+
+/// Shows whether practice plays a sound after each answer, or turns it on or off, and
+/// then plays both.
+pub fn answer_sounds(enabled: Option<bool>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    if let Some(enabled) = enabled {
+        config.answer_sounds = enabled;
+        config.save()?;
+    }
+    println!("Sounds after answers: {}", if config.answer_sounds { "on" } else { "off" });
+    if enabled == Some(true) && morse_audio::audio_available() {
+        morse_audio::play_cue(Cue::Correct);
+        thread::sleep(CUE_SAMPLE_GAP);
+        morse_audio::play_cue(Cue::Incorrect);
+    }
+    Ok(())
+}
+
+pub const MIN_SNR_DB: f32 = -10.0;
+pub const MAX_SNR_DB: f32 = 40.0;
+
+/// Shows the band noise level, or sets or removes it and plays a sample.
+pub fn noise(snr_db: Option<f32>, off: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    if off {
+        config.noise_snr_db = None;
+        config.save()?;
+        println!("Band noise turned off.");
+        return Ok(());
+    }
+    let Some(snr_db) = snr_db else {
+        match config.noise_snr_db {
+            Some(snr_db) => println!("Band noise: {:.0} dB signal-to-noise ratio", snr_db),
+            None => println!("Band noise: off"),
+        }
+        return Ok(());
+    };
+    if !(MIN_SNR_DB..=MAX_SNR_DB).contains(&snr_db) {
+        return Err(format!("signal-to-noise ratio must be between {:.0} and {:.0} dB", MIN_SNR_DB, MAX_SNR_DB).into());
+    }
+    config.noise_snr_db = Some(snr_db);
+    config.save()?;
+    println!("Band noise set to a signal-to-noise ratio of {:.0} dB.", snr_db);
+    if morse_audio::audio_available() {
+        let spacing = config.spacing(None);
+        morse_audio::play_rendered(&morse_core::morse::encode("VVV"), spacing, &config.tone_settings());
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct QsbConfig {
+    pub depth_db: f32,
+    pub period_secs: f32,
+}
+
+impl Default for QsbConfig {
+    fn default() -> Self {
+        QsbConfig { depth_db: 20.0, period_secs: 8.0 }
+    }
+}
+
+pub const MAX_QSB_DEPTH_DB: f32 = 40.0;
+pub const MIN_QSB_PERIOD_SECS: f32 = 1.0;
+pub const MAX_QSB_PERIOD_SECS: f32 = 60.0;
+
+/// Shows the fading setup, or changes or removes it and plays a sample.
+pub fn qsb(depth_db: Option<f32>, period_secs: Option<f32>, off: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    if off {
+        config.qsb = None;
+        config.save()?;
+        println!("Fading turned off.");
+        return Ok(());
+    }
+    if depth_db.is_none() && period_secs.is_none() {
+        match config.qsb {
+            Some(qsb) => println!("Fading: {:.0} dB deep, about every {:.0} s", qsb.depth_db, qsb.period_secs),
+            None => println!("Fading: off"),
+        }
+        return Ok(());
+    }
+    let mut qsb = config.qsb.unwrap_or_default();
+    qsb.depth_db = depth_db.unwrap_or(qsb.depth_db);
+    qsb.period_secs = period_secs.unwrap_or(qsb.period_secs);
+    if !(0.0..=MAX_QSB_DEPTH_DB).contains(&qsb.depth_db) {
+        return Err(format!("fading depth must be between 0 and {:.0} dB", MAX_QSB_DEPTH_DB).into());
+    }
+    if !(MIN_QSB_PERIOD_SECS..=MAX_QSB_PERIOD_SECS).contains(&qsb.period_secs) {
+        return Err(format!("fading period must be between {:.0} and {:.0} seconds", MIN_QSB_PERIOD_SECS, MAX_QSB_PERIOD_SECS).into());
+    }
+    config.qsb = Some(qsb);
+    config.save()?;
+    println!("Fading set to {:.0} dB deep, about every {:.0} s.", qsb.depth_db, qsb.period_secs);
+    if morse_audio::audio_available() {
+        let spacing = config.spacing(None);
+        morse_audio::play_rendered(&morse_core::morse::encode("VVV VVV VVV VVV"), spacing, &config.tone_settings());
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct QrmConfig {
+    pub offset_hz: f32,
+    pub level_db: f32,
+}
+
+impl Default for QrmConfig {
+    fn default() -> Self {
+        QrmConfig { offset_hz: 300.0, level_db: -6.0 }
+    }
+}
+
+pub const MAX_QRM_OFFSET_HZ: f32 = 1000.0;
+pub const MIN_QRM_LEVEL_DB: f32 = -30.0;
+pub const MAX_QRM_LEVEL_DB: f32 = 10.0;
+
+/// Shows the interference setup, or changes or removes it and plays a sample.
+pub fn qrm(offset_hz: Option<f32>, level_db: Option<f32>, off: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    if off {
+        config.qrm = None;
+        config.save()?;
+        println!("Interference turned off.");
+        return Ok(());
+    }
+    if offset_hz.is_none() && level_db.is_none() {
+        match config.qrm {
+            Some(qrm) => println!("Interference: {:+.0} Hz off, {:+.0} dB", qrm.offset_hz, qrm.level_db),
+            None => println!("Interference: off"),
+        }
+        return Ok(());
+    }
+    let mut qrm = config.qrm.unwrap_or_default();
+    qrm.offset_hz = offset_hz.unwrap_or(qrm.offset_hz);
+    qrm.level_db = level_db.unwrap_or(qrm.level_db);
+    if qrm.offset_hz.abs() > MAX_QRM_OFFSET_HZ {
+        return Err(format!("interference offset must be within ±{:.0} Hz", MAX_QRM_OFFSET_HZ).into());
+    }
+    if !(MIN_QRM_LEVEL_DB..=MAX_QRM_LEVEL_DB).contains(&qrm.level_db) {
+        return Err(format!("interference level must be between {:.0} and {:+.0} dB", MIN_QRM_LEVEL_DB, MAX_QRM_LEVEL_DB).into());
+    }
+    config.qrm = Some(qrm);
+    config.save()?;
+    println!("Interference set to {:+.0} Hz off, {:+.0} dB.", qrm.offset_hz, qrm.level_db);
+    if morse_audio::audio_available() {
+        let spacing = config.spacing(None);
+        morse_audio::play_rendered(&morse_core::morse::encode("VVV VVV VVV"), spacing, &config.tone_settings());
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub struct StereoConfig {
+    pub ear: Ear,
+    pub left_db: f32,
+    pub right_db: f32,
+}
+
+pub const MIN_CHANNEL_DB: f32 = -30.0;
+
+/// Shows the stereo setup, or changes or removes it and plays a sample.
+pub fn stereo(ear: Option<Ear>, left_db: Option<f32>, right_db: Option<f32>, off: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    if off {
+        config.stereo = None;
+        config.save()?;
+        println!("Stereo turned off.");
+        return Ok(());
+    }
+    let describe = |s: StereoConfig| format!("your signal in the {} ear, left {:+.0} dB, right {:+.0} dB",
+        cli::value_name(&s.ear), s.left_db, s.right_db);
+    if ear.is_none() && left_db.is_none() && right_db.is_none() {
+        match config.stereo {
+            Some(stereo) => println!("Stereo: {}", describe(stereo)),
+            None => println!("Stereo: off"),
+        }
+        return Ok(());
+    }
+    let mut stereo = config.stereo.unwrap_or_default();
+    stereo.ear = ear.unwrap_or(stereo.ear);
+    stereo.left_db = left_db.unwrap_or(stereo.left_db);
+    stereo.right_db = right_db.unwrap_or(stereo.right_db);
+    for db in [stereo.left_db, stereo.right_db] {
+        if !(MIN_CHANNEL_DB..=0.0).contains(&db) {
+            return Err(format!("channel levels must be between {:.0} and 0 dB", MIN_CHANNEL_DB).into());
+        }
+    }
+    config.stereo = Some(stereo);
+    config.save()?;
+    println!("Stereo set: {}.", describe(stereo));
+    if config.qrm.is_none() {
+        println!("The other ear hears a station {:+.0} Hz off yours; change it with `config qrm`.", stereo::DEFAULT_DISTRACTOR.offset_hz);
+    }
+    if morse_audio::audio_available() {
+        let spacing = config.spacing(None);
+        morse_audio::play_rendered(&morse_core::morse::encode("VVV VVV VVV"), spacing, &config.tone_settings());
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct FistConfig {
+    pub element_pct: f32,
+    pub spacing_pct: f32,
+}
+
+impl Default for FistConfig {
+    fn default() -> Self {
+        FistConfig { element_pct: 10.0, spacing_pct: 15.0 }
+    }
+}
+
+/// Shows the fist setup, or changes or removes it and plays a sample.
+pub fn fist(element_pct: Option<f32>, spacing_pct: Option<f32>, off: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    if off {
+        config.fist = None;
+        config.save()?;
+        println!("Machine-perfect timing again.");
+        return Ok(());
+    }
+    if element_pct.is_none() && spacing_pct.is_none() {
+        match config.fist {
+            Some(fist) => println!("Fist: elements up to ±{:.0}%, spacing up to ±{:.0}%", fist.element_pct, fist.spacing_pct),
+            None => println!("Fist: off (machine-perfect timing)"),
+        }
+        return Ok(());
+    }
+    let mut fist = config.fist.unwrap_or_default();
+    fist.element_pct = element_pct.unwrap_or(fist.element_pct);
+    fist.spacing_pct = spacing_pct.unwrap_or(fist.spacing_pct);
+    let max_pct = MAX_FIST_JITTER * 100.0;
+    if !(0.0..=max_pct).contains(&fist.element_pct) || !(0.0..=max_pct).contains(&fist.spacing_pct) {
+        return Err(format!("fist irregularity must be between 0 and {:.0}%", max_pct).into());
+    }
+    config.fist = Some(fist);
+    config.save()?;
+    println!("Fist set: elements up to ±{:.0}%, spacing up to ±{:.0}%.", fist.element_pct, fist.spacing_pct);
+    if morse_audio::audio_available() {
+        let spacing = config.spacing(None);
+        morse_audio::play_rendered(&morse_core::morse::encode("CQ CQ DE"), spacing, &config.tone_settings());
+    }
+    Ok(())
+}
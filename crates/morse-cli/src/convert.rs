@@ -0,0 +1,50 @@
+use std::io::{self, Read};
+use arboard::Clipboard;
+use morse_core::morse;
+
+/// Where `encode`/`decode` take their input from and send their output to.
+pub struct ConvertArgs {
+    pub input: Option<String>,
+    pub clipboard: bool,
+}
+
+fn read_input(args: &ConvertArgs, clipboard: Option<&mut Clipboard>) -> Result<String, Box<dyn std::error::Error>> {
+    if let Some(input) = &args.input {
+        return Ok(input.clone());
+    }
+    if let Some(clipboard) = clipboard {
+        return Ok(clipboard.get_text()?);
+    }
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    Ok(input)
+}
+
+fn convert(args: ConvertArgs, f: impl Fn(&str) -> String) -> Result<(), Box<dyn std::error::Error>> {
+    let mut clipboard = if args.clipboard {
+        Some(Clipboard::new()?)
+    } else {
+        None
+    };
+    
+    let input = read_input(&args, clipboard.as_mut())?;
+    let output = input.lines()
+        .map(f)
+        .collect::<Vec<String>>()
+        .join("\n");
+    println!("{}", output);
+
+    if let Some(clipboard) = &mut clipboard {
+        clipboard.set_text(output)?;
+        println!("(copied to clipboard)");
+    }
+    Ok(())
+}
+
+pub fn encode(args: ConvertArgs) -> Result<(), Box<dyn std::error::Error>> {
+    convert(args, morse::encode)
+}
+
+pub fn decode(args: ConvertArgs) -> Result<(), Box<dyn std::error::Error>> {
+    convert(args, morse::decode)
+}
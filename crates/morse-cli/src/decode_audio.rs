@@ -0,0 +1,26 @@
+use std::{fs::File, io::BufReader, path::Path};
+use morse_audio::{detect::{self, ToneDetector}, render};
+use morse_core::timing;
+use crate::{mic, speed::DEFAULT_WPM};
+
+/// Decodes the Morse in a WAV recording, listening at `pitch_hz` or else at the pitch of
+/// its strongest tone. `wpm` is a first guess at the speed, which is followed from there.
+pub fn run(path: &Path, pitch_hz: Option<f32>, wpm: Option<f32>) -> Result<(), Box<dyn std::error::Error>> {
+    let (samples, sample_rate) = render::read_wav(BufReader::new(File::open(path)?))?;
+    let pitch_hz = match pitch_hz {
+        Some(hz) => hz,
+        None => detect::strongest_pitch(&samples, sample_rate).ok_or("the recording is empty")?,
+    };
+    let mut detector = ToneDetector::new(sample_rate).tuned(pitch_hz);
+    detector.calibrate(&samples);
+    let mut events = detector.push(&samples);
+    events.extend(detector.finish());
+    if events.is_empty() {
+        return Err(format!("no keyed tone found at {:.0} Hz", pitch_hz).into());
+    }
+
+    let mut decoder = mic::decode(&events, timing::unit_ms_for_wpm(wpm.unwrap_or(DEFAULT_WPM)));
+    println!("{}", decoder.take_text());
+    println!("(tone at {:.0} Hz, about {:.0} WPM)", pitch_hz, 1200.0 / decoder.unit_ms());
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use chrono::Local;
+use morse_stats::{EndingPatterns, UserStats};
+use crate::config::AppConfig;
+
+/// Sessions stopped early needed before a pattern is pointed out.
+const MIN_STOPPED: usize = 3;
+/// Share of the early stops that must fall in one third for it to count as a pattern.
+const CLUSTER_SHARE: f32 = 0.5;
+const THIRDS: [&str; 3] = ["first", "middle", "last"];
+
+fn percent(part: usize, whole: usize) -> f32 {
+    part as f32 / whole.max(1) as f32 * 100.0
+}
+
+/// The third of a session the early stops cluster in, and its share of them.
+fn cluster(patterns: &EndingPatterns) -> Option<(usize, f32)> {
+    if patterns.stopped() < MIN_STOPPED {
+        return None;
+    }
+    let (third, count) = patterns.stopped_by_third.iter().enumerate().max_by_key(|(_, count)| **count)?;
+    let share = *count as f32 / patterns.stopped() as f32;
+    (share >= CLUSTER_SHARE).then_some((third, share))
+}
+
+/// Shows how sessions ended and where the early stops fall, and suggests a shorter
+/// session length when they keep coming at the same point.
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let stats = UserStats::load()?;
+    let patterns = stats.ending_patterns::<Local>(None);
+    let sessions = patterns.sessions();
+    if sessions == 0 {
+        println!("No session endings recorded yet.");
+        return Ok(());
+    }
+
+    println!("{:<22} {:>8} {:>6}", "Ended by", "Sessions", "Share");
+    for (label, count) in [
+        ("Completing the queue", patterns.completed),
+        ("Time running out", patterns.timeout),
+        ("Quitting with q", patterns.quit),
+        ("Closing the input", patterns.closed),
+    ] {
+        println!("{:<22} {:>8} {:>5.0}%", label, count, percent(count, sessions));
+    }
+    if patterns.stopped() == 0 {
+        println!("\nEvery session ran to its end.");
+        return Ok(());
+    }
+
+    println!("\nStopped early in the:");
+    for (third, count) in THIRDS.iter().zip(patterns.stopped_by_third) {
+        println!("  {:<7} third {:>4}", third, count);
+    }
+
+    let Some((third, share)) = cluster(&patterns) else {
+        return Ok(());
+    };
+    println!("\nYou stopped {:.0}% of the sessions you ended early in their {} third.", share * 100.0, THIRDS[third]);
+    let config = AppConfig::load().unwrap_or_default();
+    let minutes = ((patterns.stopped_secs as f32 / patterns.stopped() as f32 / 60.0).round() as u32).max(1);
+    if third > 0 && minutes < config.session_duration {
+        println!("Sessions of about {} minutes may suit you better than {}: set session_duration = {} in morse_config.toml.",
+            minutes, config.session_duration, minutes);
+    } else if third == 0 {
+        println!("Stopping that early is more often about the speed or material than the length; try a lower `--wpm`.");
+    }
+    Ok(())
+}
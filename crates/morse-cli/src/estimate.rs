@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+use morse_core::progression::ProgressionSystem;
+use morse_stats::UserStats;
+use crate::config::AppConfig;
+
+/// Sessions each new character is assumed to take until the learner's own promotions say otherwise.
+pub const DEFAULT_SESSIONS_PER_CHAR: f32 = 1.5;
+/// The word level after the last character level.
+const FINAL_LEVEL: u8 = 9;
+
+#[derive(Debug, Clone, Copy)]
+pub struct LevelEstimate {
+    pub level: u8,
+    pub sessions: f32,
+    pub minutes: f32,
+}
+
+/// Remaining sessions and practice time for the current level and every level after it,
+/// extrapolated from how many sessions per new character past promotions took.
+pub fn remaining(stats: &UserStats, progression: &ProgressionSystem, config: &AppConfig) -> Vec<LevelEstimate> {
+    let current = config.difficulty_level;
+    let mut per_level: BTreeMap<u8, u32> = BTreeMap::new();
+    // Only the sessions that count toward promotion, as those are what a level takes.
+    for session in stats.session_history.iter().filter(|s| s.external.is_none() && !s.passive && !s.outside_progression) {
+        *per_level.entry(session.difficulty).or_default() += 1;
+    }
+
+    let (finished_sessions, finished_chars) = progression.levels.iter()
+        .filter(|l| l.level < current)
+        .filter_map(|l| per_level.get(&l.level).map(|n| (*n, l.chars_to_learn.len())))
+        .fold((0, 0), |(s, c), (n, chars)| (s + n, c + chars));
+    let sessions_per_char = if finished_chars > 0 {
+        finished_sessions as f32 / finished_chars as f32
+    } else {
+        DEFAULT_SESSIONS_PER_CHAR
+    };
+
+    let timed: Vec<u32> = stats.session_history.iter()
+        .filter(|s| !s.passive)
+        .map(|s| s.duration)
+        .filter(|d| *d > 0)
+        .collect();
+    let minutes_per_session = if timed.is_empty() {
+        config.session_duration as f32
+    } else {
+        timed.iter().sum::<u32>() as f32 / timed.len() as f32 / 60.0
+    };
+
+    progression.levels.iter()
+        .filter(|l| l.level >= current && l.level < FINAL_LEVEL)
+        .map(|l| {
+            let expected = sessions_per_char * l.chars_to_learn.len() as f32;
+            let sessions = if l.level == current {
+                let done = per_level.get(&current).copied().unwrap_or(0) as f32;
+                (expected - done).max(1.0)
+            } else {
+                expected
+            };
+            LevelEstimate {
+                level: l.level,
+                sessions,
+                minutes: sessions * minutes_per_session,
+            }
+        })
+        .collect()
+}
+
+pub fn format_minutes(minutes: f32) -> String {
+    if minutes < 60.0 {
+        format!("{:.0} min", minutes)
+    } else {
+        format!("{:.1} h", minutes / 60.0)
+    }
+}
+
+/// One-line outlook shown after each session.
+pub fn summary(estimates: &[LevelEstimate]) -> Option<String> {
+    let current = estimates.first()?;
+    let total_sessions: f32 = estimates.iter().map(|e| e.sessions).sum();
+    let total_minutes: f32 = estimates.iter().map(|e| e.minutes).sum();
+    Some(format!(
+        "About {:.0} more session(s) (~{}) to finish level {}, {:.0} (~{}) to reach word level.",
+        current.sessions.ceil(),
+        format_minutes(current.minutes),
+        current.level,
+        total_sessions.ceil(),
+        format_minutes(total_minutes)
+    ))
+}
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load().unwrap_or_default();
+    let stats = UserStats::load()?;
+    let progression = config.progression();
+    let estimates = remaining(&stats, &progression, &config);
+    if estimates.is_empty() {
+        println!("You've reached word level, there are no character levels left.");
+        return Ok(());
+    }
+
+    println!("{:<6} {:>9} {:>10}", "Level", "Sessions", "Time");
+    let mut total_minutes = 0.0;
+    for e in &estimates {
+        total_minutes += e.minutes;
+        println!("{:<6} {:>9.0} {:>10}", e.level, e.sessions.ceil(), format_minutes(e.minutes));
+    }
+    println!("\nWord level in about {} of practice.", format_minutes(total_minutes));
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use std::thread;
+use std::time::Instant;
+use morse_core::{exam::{self, ExamPreset, PRESETS}, scoring::Scorecard};
+use crate::{cli::Output, config::AppConfig, net::{self, Station}, scoring};
+
+/// Characters per minute in one word per minute (PARIS is five letters).
+pub(crate) const CHARS_PER_WPM: f32 = 5.0;
+
+pub fn list() {
+    for preset in PRESETS {
+        println!("{:<11} {}", preset.name, preset.description);
+    }
+}
+
+/// Sends `text` once Enter is pressed and reads the copy typed in meanwhile, line by line
+/// until an empty one. Returns the copy and how many minutes the sending took, or `None`
+/// when the input ends before the test starts.
+pub(crate) fn copy_while_sending(station: Station, text: &str) -> Option<(String, Option<f32>)> {
+    println!("Type your copy as you hear it, pressing Enter whenever you like.");
+    println!("When the text is over, press Enter on an empty line.");
+    net::answer("Press Enter to start...")?;
+
+    let sending = thread::spawn({
+        let text = text.to_string();
+        move || {
+            let start = Instant::now();
+            station.send(&text);
+            start.elapsed()
+        }
+    });
+    let mut copy = Vec::new();
+    while let Some(line) = net::answer("") {
+        if line.is_empty() {
+            break;
+        }
+        copy.push(line);
+    }
+    if !sending.is_finished() {
+        println!("Waiting for the end of the text...");
+    }
+    let minutes = sending.join().ok().map(|took| took.as_secs_f32() / 60.0);
+    Some((copy.join(" "), minutes))
+}
+
+/// Sends a plain-language text at the preset's speed while the copy is typed in, then
+/// grades it by the preset's rules or the given ones.
+pub fn run(name: &str, rules: Option<&str>, output: Output) -> Result<(), Box<dyn std::error::Error>> {
+    let preset: ExamPreset = exam::preset(name)
+        .ok_or_else(|| format!("no exam preset '{}' (see `exam --list`)", name))?;
+    let config = AppConfig::load().unwrap_or_default();
+    let rules = scoring::resolve(rules, config.scoring.exam.as_deref(), preset.rules)?;
+    let station = Station::at_speed(output, preset.wpm, config);
+    let text = exam::qso_text((preset.wpm * CHARS_PER_WPM * preset.minutes) as usize, &mut rand::rng());
+
+    println!("{}", preset.description);
+    println!("To pass: {}.", scoring::describe(&rules));
+    let Some((copy, minutes)) = copy_while_sending(station, &text) else {
+        return Ok(());
+    };
+    let card = Scorecard::compare(&text, &copy, minutes.unwrap_or(preset.minutes));
+    println!("\nSent:   {}", text);
+    println!("Copied: {}\n", copy);
+    if scoring::print_verdict(&card, &rules) {
+        println!("PASS");
+    } else {
+        println!("FAIL");
+    }
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use std::{fs, path::Path};
+use morse_audio::{encode::{self, AudioFormat, TrackInfo}, render};
+use morse_core::{material, morse, progression::ProgressionSystem};
+use crate::config::AppConfig;
+
+/// Album name of an exported course.
+const COURSE_ALBUM: &str = "Morse code course";
+/// Times each new character of a course lesson is played before the drill.
+const INTRODUCTIONS: usize = 3;
+
+fn file_stem(item: &str) -> String {
+    item.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect()
+}
+
+pub(crate) fn answer_key(items: &[String]) -> String {
+    items.iter()
+        .enumerate()
+        .map(|(i, item)| format!("{:>3}. {}\n", i + 1, item))
+        .collect()
+}
+
+/// The title of a level's lesson, with the characters it introduces, e.g. "Level 3 – D G K O".
+pub fn lesson_title(progression: &ProgressionSystem, level: u8) -> String {
+    let chars = progression.level(level)
+        .map(|l| l.chars_to_learn.iter().map(char::to_string).collect::<Vec<_>>().join(" "))
+        .unwrap_or_default();
+    format!("Level {} – {}", level, chars)
+}
+
+/// Renders `items` at the configured speed and pitch with `pause_ms` of silence after each:
+/// into one file at `out` with the answer key beside it, or with `split` into one numbered
+/// file per item in the directory `out`. The format is `format`, or else the one `out` is
+/// named for, or WAV; compressed files are tagged with `title`.
+pub fn write(items: &[String], pause_ms: f32, split: bool, format: Option<AudioFormat>, title: &str, out: &Path)
+    -> Result<(), Box<dyn std::error::Error>>
+{
+    if items.is_empty() {
+        return Err("nothing to render".into());
+    }
+    let config = AppConfig::load().unwrap_or_default();
+    let settings = config.tone_settings();
+    let format = format
+        .or_else(|| AudioFormat::from_path(out).filter(|_| !split))
+        .unwrap_or(AudioFormat::Wav);
+
+    let mut total_secs = 0.0;
+    if split {
+        fs::create_dir_all(out)?;
+        for (i, item) in items.iter().enumerate() {
+            let samples = render::render_items(std::slice::from_ref(item), &settings, pause_ms);
+            total_secs += render::duration_secs(&samples);
+            let path = out.join(format!("{:03}-{}.{}", i + 1, file_stem(item), format.extension()));
+            let info = TrackInfo { title: item.clone(), album: Some(title.to_string()), track: Some((i + 1, items.len())) };
+            encode::save(&path, &samples, format, &info)?;
+        }
+        fs::write(out.join("key.txt"), answer_key(items))?;
+    } else {
+        let samples = render::render_items(items, &settings, pause_ms);
+        total_secs = render::duration_secs(&samples);
+        let info = TrackInfo { title: title.to_string(), ..TrackInfo::default() };
+        encode::save(out, &samples, format, &info)?;
+        fs::write(out.with_extension("txt"), answer_key(items))?;
+    }
+
+    println!(
+        "Rendered {} item(s), {:.1} minutes at {} WPM and {:.0} Hz, to {}",
+        items.len(),
+        total_secs / 60.0,
+        match settings.effective_wpm {
+            Some(effective) if effective < settings.wpm => format!("{:.0}/{:.0}", settings.wpm, effective),
+            _ => format!("{:.0}", settings.wpm),
+        },
+        settings.pitch_hz,
+        out.display()
+    );
+    Ok(())
+}
+
+/// Renders a lesson for every level into the directory `out`, tagged as the tracks of
+/// one album so the whole course can go onto a music player. Each lesson plays the
+/// characters it introduces a few times each, then `items` groups and words on everything
+/// learned so far.
+pub fn course(format: AudioFormat, items: usize, pause_ms: f32, out: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load().unwrap_or_default();
+    let settings = config.tone_settings();
+    let progression = config.progression();
+    let mut rng = rand::rng();
+    fs::create_dir_all(out)?;
+
+    let mut keys = String::new();
+    let mut total_secs = 0.0;
+    for (i, level) in progression.levels.iter().enumerate() {
+        let mut lesson: Vec<String> = level.chars_to_learn.iter()
+            .flat_map(|c| std::iter::repeat_n(c.to_string(), INTRODUCTIONS))
+            .collect();
+        let words = items / 2;
+        let chars = progression.chars_through(level.level);
+        lesson.extend(material::groups_and_words(&chars, &progression.common_words, items - words, words, &mut rng));
+
+        let samples = render::render_items(&lesson, &settings, pause_ms);
+        total_secs += render::duration_secs(&samples);
+        let title = lesson_title(&progression, level.level);
+        let path = out.join(format!("{:02}-level-{}.{}", i + 1, level.level, format.extension()));
+        let info = TrackInfo {
+            title: title.clone(),
+            album: Some(COURSE_ALBUM.to_string()),
+            track: Some((i + 1, progression.levels.len())),
+        };
+        encode::save(&path, &samples, format, &info)?;
+        keys.push_str(&format!("{}\n{}\n", title, answer_key(&lesson)));
+    }
+    fs::write(out.join("key.txt"), keys)?;
+
+    println!("Rendered {} lessons, {:.1} minutes, to {}", progression.levels.len(), total_secs / 60.0, out.display());
+    Ok(())
+}
+
+/// Items for rendering typed text: the whole text as one item, or one per word with `split`.
+pub fn text_items(text: &str, split: bool) -> Vec<String> {
+    let text = text.to_uppercase();
+    let items: Vec<String> = if split {
+        text.split_whitespace().map(str::to_string).collect()
+    } else {
+        vec![text.split_whitespace().collect::<Vec<_>>().join(" ")]
+    };
+    items.into_iter()
+        .filter(|item| !morse::encode(item).is_empty())
+        .collect()
+}
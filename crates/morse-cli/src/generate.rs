@@ -0,0 +1,45 @@
+use morse_core::{coverage, morse, progression::ProgressionSystem};
+use crate::config::AppConfig;
+
+fn parse_chars(chars: &str) -> Vec<char> {
+    chars.chars()
+        .map(|c| c.to_ascii_uppercase())
+        .filter(|c| morse::char_to_morse(*c).is_some())
+        .collect()
+}
+
+/// Characters to cover for `--cover`/`--chars`, falling back to the current level's new ones.
+pub fn target_chars(chars: Option<&str>, config: &AppConfig, progression: &ProgressionSystem) -> Vec<char> {
+    let chars = match chars {
+        Some(chars) => parse_chars(chars),
+        None => progression.level(config.difficulty_level)
+            .map(|l| l.chars_to_learn.clone())
+            .unwrap_or_else(|| config.known_chars.clone()),
+    };
+    config.banned.filter_chars(&chars)
+}
+
+pub fn run(chars: Option<String>, min_count: usize, known_only: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load().unwrap_or_default();
+    let progression = config.progression();
+    let targets = target_chars(chars.as_deref(), &config, &progression);
+    if targets.is_empty() {
+        return Err("no valid characters to cover".into());
+    }
+
+    let mut allowed = config.banned.filter_chars(&config.known_chars);
+    allowed.extend(&targets);
+    let words = coverage::coverage_text(
+        &targets,
+        min_count,
+        &progression.common_words,
+        known_only.then_some(allowed.as_slice()),
+        &mut rand::rng(),
+    );
+
+    let text = words.join(" ");
+    println!("Covering {} at least {} times each:\n", targets.iter().collect::<String>(), min_count);
+    println!("{}\n", text);
+    println!("{}", morse::encode(&text));
+    Ok(())
+}
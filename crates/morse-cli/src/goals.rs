@@ -0,0 +1,237 @@
+use chrono::{DateTime, Local};
+use clap::ValueEnum;
+use serde_derive::{Serialize, Deserialize};
+use morse_core::morse;
+use morse_stats::{SpeedTestHistory, UserStats};
+use crate::{cli::{PracticeArgs, Subset}, config::AppConfig, layout::{self, Align, Table}, speed::DEFAULT_WPM};
+
+/// How much a practice suggestion raises the speed over what was last reached.
+const SPEED_STEP: f32 = 2.0;
+/// Share of an echo session's answers that must be right for its speed to count as sent.
+const SEND_ACCURACY: f32 = 0.9;
+/// Average rhythm score an echo session needs for its speed to count as sent.
+const SEND_RHYTHM: f32 = 80.0;
+/// Characters a learning goal drills at a time.
+const LEARN_CHARS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GoalKindArg {
+    /// Copy at a speed, e.g. `receive 20`; measured by passed speed tests
+    Receive,
+    /// Key at a speed, e.g. `send 15`; measured by clean echo sessions
+    Send,
+    /// Know a set of characters, e.g. `learn punctuation` or `learn "?/="`
+    Learn,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum GoalKind {
+    Receive { wpm: f32 },
+    Send { wpm: f32 },
+    Learn { chars: String },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Goal {
+    pub kind: GoalKind,
+    /// Higher priorities get more of the practice time.
+    pub priority: u8,
+}
+
+impl Goal {
+    pub fn describe(&self) -> String {
+        match &self.kind {
+            GoalKind::Receive { wpm } => format!("Receive {} WPM", wpm),
+            GoalKind::Send { wpm } => format!("Send {} WPM", wpm),
+            GoalKind::Learn { chars } => format!("Learn {}", chars),
+        }
+    }
+}
+
+/// Where a goal stands: `current` out of `target`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalProgress {
+    pub current: f32,
+    pub target: f32,
+    pub detail: String,
+}
+
+impl GoalProgress {
+    pub fn fraction(&self) -> f32 {
+        (self.current / self.target).clamp(0.0, 1.0)
+    }
+
+    pub fn reached(&self) -> bool {
+        self.current >= self.target
+    }
+}
+
+/// The fastest echo session keyed cleanly enough, in WPM.
+fn fastest_sent(stats: &UserStats) -> Option<f32> {
+    stats.session_history.iter()
+        .filter_map(|session| {
+            let echoed: Vec<_> = session.answers.iter().filter(|a| a.rhythm.is_some()).collect();
+            let dot_ms = session.settings.as_ref()?.dot_ms;
+            if echoed.is_empty() || dot_ms == 0 {
+                return None;
+            }
+            let correct = echoed.iter().filter(|a| a.correct).count() as f32 / echoed.len() as f32;
+            let rhythm = echoed.iter().filter_map(|a| a.rhythm).sum::<f32>() / echoed.len() as f32;
+            (correct >= SEND_ACCURACY && rhythm >= SEND_RHYTHM).then_some(1200.0 / dot_ms as f32)
+        })
+        .max_by(f32::total_cmp)
+}
+
+pub fn progress(goal: &Goal, stats: &UserStats, tests: &SpeedTestHistory, now: &DateTime<Local>) -> GoalProgress {
+    match &goal.kind {
+        GoalKind::Receive { wpm } => {
+            let best = tests.best_pass().map(|t| t.wpm);
+            GoalProgress {
+                current: best.unwrap_or(0.0),
+                target: *wpm,
+                detail: match best {
+                    Some(best) => format!("fastest speed test passed at {} WPM", best),
+                    None => "no speed test passed yet".to_string(),
+                },
+            }
+        }
+        GoalKind::Send { wpm } => {
+            let fastest = fastest_sent(stats);
+            GoalProgress {
+                current: fastest.unwrap_or(0.0),
+                target: *wpm,
+                detail: match fastest {
+                    Some(fastest) => format!("echoed cleanly at {:.0} WPM", fastest),
+                    None => "no clean echo session yet".to_string(),
+                },
+            }
+        }
+        GoalKind::Learn { chars } => {
+            let strengths = stats.strengths(now);
+            let known = chars.chars().filter(|c| strengths.get(c).is_some_and(|s| !s.is_fading())).count();
+            GoalProgress {
+                current: known as f32,
+                target: chars.chars().count() as f32,
+                detail: format!("{} of {} characters remembered", known, chars.chars().count()),
+            }
+        }
+    }
+}
+
+/// How much a goal is owed practice: the part still to go, weighted by its priority.
+fn gap(goal: &Goal, progress: &GoalProgress) -> f32 {
+    (1.0 - progress.fraction()) * goal.priority as f32
+}
+
+/// The unreached goal with the biggest weighted gap, and where it stands.
+pub fn next(config: &AppConfig, stats: &UserStats, tests: &SpeedTestHistory, now: &DateTime<Local>) -> Option<(Goal, GoalProgress)> {
+    config.goals.iter()
+        .map(|goal| (goal.clone(), progress(goal, stats, tests, now)))
+        .filter(|(_, progress)| !progress.reached())
+        .max_by(|a, b| gap(&a.0, &a.1).total_cmp(&gap(&b.0, &b.1)))
+}
+
+/// A session that works toward `goal`: copying or echoing a step above the speed reached,
+/// or drilling the weakest of its characters.
+pub fn session(goal: &Goal, progress: &GoalProgress, stats: &UserStats, now: &DateTime<Local>) -> PracticeArgs {
+    let step = |current: f32| Some((current.max(DEFAULT_WPM - SPEED_STEP) + SPEED_STEP).min(progress.target));
+    match &goal.kind {
+        GoalKind::Receive { .. } => PracticeArgs { copy: true, wpm: step(progress.current), ..PracticeArgs::default() },
+        GoalKind::Send { .. } => PracticeArgs { echo: true, wpm: step(progress.current), ..PracticeArgs::default() },
+        GoalKind::Learn { chars } => {
+            let strengths = stats.strengths(now);
+            let mut weakest: Vec<char> = chars.chars().collect();
+            weakest.sort_by(|a, b| {
+                let strength = |c: &char| strengths.get(c).map_or(0.0, |s| s.strength);
+                strength(a).total_cmp(&strength(b))
+            });
+            PracticeArgs { focus: Some(weakest.into_iter().take(LEARN_CHARS).collect()), ..PracticeArgs::default() }
+        }
+    }
+}
+
+/// Characters named by `target`: a subset like `punctuation`, or the characters themselves.
+fn learn_chars(target: &str) -> Result<String, Box<dyn std::error::Error>> {
+    if let Ok(subset) = Subset::from_str(target, true) {
+        return Ok(subset.chars().into_iter().collect());
+    }
+    let mut chars: Vec<char> = Vec::new();
+    for c in target.to_uppercase().chars().filter(|c| !c.is_whitespace()) {
+        if morse::char_to_morse(c).is_none() {
+            return Err(format!("'{}' has no Morse code", c).into());
+        }
+        if !chars.contains(&c) {
+            chars.push(c);
+        }
+    }
+    if chars.is_empty() {
+        return Err("a learning goal needs characters or one of letters, numbers, punctuation".into());
+    }
+    Ok(chars.into_iter().collect())
+}
+
+pub fn add(kind: GoalKindArg, target: &str, priority: u8) -> Result<(), Box<dyn std::error::Error>> {
+    let kind = match kind {
+        GoalKindArg::Learn => GoalKind::Learn { chars: learn_chars(target)? },
+        speed => {
+            let wpm: f32 = target.parse().map_err(|_| format!("'{}' is not a speed in WPM", target))?;
+            if !(5.0..=60.0).contains(&wpm) {
+                return Err("goal speeds must be between 5 and 60 WPM".into());
+            }
+            match speed {
+                GoalKindArg::Receive => GoalKind::Receive { wpm },
+                _ => GoalKind::Send { wpm },
+            }
+        }
+    };
+    let mut config = AppConfig::load()?;
+    let goal = Goal { kind, priority };
+    println!("Added goal {}: {} (priority {}).", config.goals.len() + 1, goal.describe(), priority);
+    config.goals.push(goal);
+    config.save()
+}
+
+pub fn remove(number: usize) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    if number == 0 || number > config.goals.len() {
+        return Err(format!("there is no goal {} (see `goal list`)", number).into());
+    }
+    let goal = config.goals.remove(number - 1);
+    config.save()?;
+    println!("Removed goal: {}.", goal.describe());
+    Ok(())
+}
+
+/// Lists the goals with their progress and the share of practice each is due.
+pub fn list() -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load()?;
+    if config.goals.is_empty() {
+        println!("No goals yet. Add one with e.g. `goal add receive 20`.");
+        return Ok(());
+    }
+    let stats = UserStats::load()?;
+    let tests = SpeedTestHistory::load()?;
+    let now = Local::now();
+    let progress: Vec<GoalProgress> = config.goals.iter().map(|g| progress(g, &stats, &tests, &now)).collect();
+    let total: f32 = config.goals.iter().zip(&progress).map(|(g, p)| gap(g, p)).sum();
+
+    let mut table = Table::new(&[
+        ("#", Align::Left), ("Goal", Align::Left), ("Priority", Align::Right),
+        ("Done", Align::Right), ("Share", Align::Right), ("Progress", Align::Left),
+    ]);
+    for (n, (goal, p)) in config.goals.iter().zip(&progress).enumerate() {
+        let share = if total > 0.0 { gap(goal, p) / total * 100.0 } else { 0.0 };
+        table.row(vec![
+            (n + 1).to_string(), goal.describe(), goal.priority.to_string(),
+            format!("{:.0}%", p.fraction() * 100.0), format!("{:.0}%", share),
+            if p.reached() { "reached".to_string() } else { p.detail.clone() },
+        ]);
+    }
+    table.print();
+    if let Some((goal, p)) = next(&config, &stats, &tests, &now) {
+        println!("\n{}", layout::wrap(&format!("Next up: {}, with `practice {}`.", goal.describe(),
+            crate::template::describe(&session(&goal, &p, &stats, &now))), 2));
+    }
+    Ok(())
+}
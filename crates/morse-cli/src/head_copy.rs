@@ -0,0 +1,107 @@
+use std::collections::BTreeSet;
+use std::time::Instant;
+use rand::Rng;
+use morse_core::{exam, grading::{self, WordDiff}, material, pseudowords::PseudoWordGenerator};
+use morse_stats::{HeadCopyPassage, LearningSession, UserStats};
+use crate::{cli::Output, config::AppConfig, net::{self, Station}};
+
+/// Words from the current level, made up with pseudo-words when the common words run short.
+fn words<R: Rng + ?Sized>(config: &AppConfig, count: usize, rng: &mut R) -> Vec<String> {
+    let progression = config.progression();
+    let chars: Vec<char> = progression.chars_through(config.difficulty_level).into_iter()
+        .filter(|c| config.banned.allows_char(*c))
+        .collect();
+    let corpus: Vec<String> = progression.common_words.into_iter()
+        .filter(|word| config.banned.allows_word(word))
+        .collect();
+    let mut words = material::words_within(&chars, &corpus, count, rng);
+    let generator = PseudoWordGenerator::new(&chars, &corpus);
+    while words.len() < count && generator.is_usable() {
+        words.push(generator.generate(rng));
+    }
+    words
+}
+
+/// The comparison on one line: `[WORD]` was missed, `WORD→COPY` miscopied, `+WORD` not sent.
+fn show(diff: &[WordDiff]) -> String {
+    diff.iter()
+        .map(|word| match word {
+            WordDiff::Copied(word) => word.clone(),
+            WordDiff::Missed(word) => format!("[{}]", word),
+            WordDiff::Miscopied { sent, copied } => format!("{}→{}", sent, copied),
+            WordDiff::Extra(word) => format!("+{}", word),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Streams `passages` passages of `words` words, or QSO sentences, each in one go; the
+/// whole passage is only typed in once it's over, and graded word by word.
+pub fn run(passages: usize, words_per_passage: usize, sentences: bool, wpm: Option<f32>, output: Output) -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load().unwrap_or_default();
+    let mut stats = UserStats::load()?;
+    let mut rng = rand::rng();
+    let difficulty = config.difficulty_level;
+    let station = Station::new(output, wpm, config.clone());
+
+    println!("Head copy: listen to the whole passage without writing, then type all of it.");
+    println!("In the comparison, [WORD] was missed, WORD→COPY miscopied and +WORD never sent.");
+    let start = Instant::now();
+    let mut copied = Vec::new();
+    for number in 1..=passages {
+        let text = if sentences {
+            exam::qso_sentence(&mut rng)
+        } else {
+            words(&config, words_per_passage, &mut rng).join(" ")
+        };
+        if text.is_empty() {
+            return Err("no words to send at your level yet, try --sentences".into());
+        }
+        println!("\n--- Passage {} of {} ---", number, passages);
+        station.send(&text);
+        let Some(copy) = net::answer("Your copy: ") else {
+            break;
+        };
+        let diff = grading::word_diff(&text, &copy);
+        let passage = HeadCopyPassage {
+            words: text.split_whitespace().count(),
+            words_copied: diff.iter().filter(|w| matches!(w, WordDiff::Copied(_))).count(),
+            sent: text,
+            copied: copy,
+        };
+        if passage.words_copied == passage.words && passage.words == diff.len() {
+            println!("✓ All {} words", passage.words);
+        } else {
+            println!("✗ {}/{} words: {}", passage.words_copied, passage.words, show(&diff));
+        }
+        copied.push(passage);
+    }
+    if copied.is_empty() {
+        return Ok(());
+    }
+
+    let words: usize = copied.iter().map(|p| p.words).sum();
+    let words_copied: usize = copied.iter().map(|p| p.words_copied).sum();
+    let perfect = copied.iter().filter(|p| p.words_copied == p.words).count();
+    println!("\nHead copy: {}/{} words ({:.0}%), {}/{} passages copied in full",
+        words_copied, words, words_copied as f32 / words as f32 * 100.0, perfect, copied.len());
+
+    let sent: Vec<String> = copied.iter().flat_map(|p| p.sent.split_whitespace().map(str::to_string)).collect();
+    stats.session_history.push(LearningSession {
+        timestamp: chrono::Local::now().fixed_offset(),
+        duration: start.elapsed().as_secs() as u32,
+        chars_practiced: sent.iter().flat_map(|w| w.chars()).collect::<BTreeSet<_>>().into_iter().collect(),
+        words_practiced: sent,
+        accuracy: words_copied as f32 / words as f32,
+        difficulty,
+        answers: vec![],
+        settings: Some(station.settings()),
+        external: None,
+        head_copy: copied,
+        passive: false,
+        sending: None,
+        ending: None,
+        outside_progression: true,
+    });
+    stats.save()
+}
@@ -0,0 +1,87 @@
+use std::{
+    io::{self, Write},
+    process::Stdio,
+    thread,
+};
+use serde_derive::{Serialize, Deserialize};
+use serde_json::Value;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HookConfig {
+    #[serde(default)]
+    pub on_session_start: Option<String>,
+    #[serde(default)]
+    pub on_answer: Option<String>,
+    #[serde(default)]
+    pub on_level_up: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum HookEvent {
+    SessionStart,
+    Answer,
+    LevelUp,
+}
+
+impl HookEvent {
+    fn name(self) -> &'static str {
+        match self {
+            HookEvent::SessionStart => "session_start",
+            HookEvent::Answer => "answer",
+            HookEvent::LevelUp => "level_up",
+        }
+    }
+}
+
+impl HookConfig {
+    fn command_for(&self, event: HookEvent) -> Option<&str> {
+        match event {
+            HookEvent::SessionStart => self.on_session_start.as_deref(),
+            HookEvent::Answer => self.on_answer.as_deref(),
+            HookEvent::LevelUp => self.on_level_up.as_deref(),
+        }
+        .filter(|c| !c.trim().is_empty())
+    }
+
+    /// Runs the command configured for `event`, if any, writing `payload` as JSON to its stdin.
+    /// The hook is not waited on, so a slow command never stalls the session.
+    pub fn fire(&self, event: HookEvent, mut payload: Value) {
+        let Some(command) = self.command_for(event) else {
+            return;
+        };
+
+        if let Value::Object(map) = &mut payload {
+            map.insert("event".to_string(), Value::from(event.name()));
+        }
+
+        if let Err(e) = run_hook(command, &payload, event) {
+            eprintln!("Error running {} hook: {}", event.name(), e);
+        }
+    }
+}
+
+fn run_hook(command: &str, payload: &Value, event: HookEvent) -> Result<(), Box<dyn std::error::Error>> {
+    let mut data = serde_json::to_vec(payload)?;
+    data.push(b'\n');
+    let mut child = morse_platform::shell_command(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit())
+        .spawn()?;
+
+    // The payload is written off the session's thread too, since a hook that doesn't read
+    // its stdin would block the write once the pipe is full.
+    thread::spawn(move || {
+        if let Some(mut stdin) = child.stdin.take() {
+            match stdin.write_all(&data) {
+                // Hooks that never read stdin may exit before the payload is written.
+                Err(e) if e.kind() != io::ErrorKind::BrokenPipe => {
+                    eprintln!("Error running {} hook: {}", event.name(), e);
+                }
+                _ => {}
+            }
+        }
+        let _ = child.wait();
+    });
+    Ok(())
+}
@@ -0,0 +1,245 @@
+use std::{fmt, fs, path::{Path, PathBuf}};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use clap::ValueEnum;
+use morse_stats::{ExternalResult, LearningSession, UserStats};
+use crate::{config::AppConfig, locale, logbook::{self, Logbook}};
+
+/// Characters per minute in one word per minute (PARIS is five letters).
+const CPM_PER_WPM: f32 = 5.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportSource {
+    #[value(name = "morserunner")]
+    MorseRunner,
+    #[value(name = "rufzxp")]
+    RufzXp,
+}
+
+impl ImportSource {
+    /// Guesses the program from the file name and the banner most result files start with.
+    pub fn detect(path: &Path, text: &str) -> Option<Self> {
+        let haystack = format!("{} {}", path.display(), text).to_lowercase();
+        if haystack.contains("rufz") {
+            Some(ImportSource::RufzXp)
+        } else if ["morserunner", "morse runner", "hstresults"].iter().any(|name| haystack.contains(name)) {
+            Some(ImportSource::MorseRunner)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for ImportSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            ImportSource::MorseRunner => "MorseRunner",
+            ImportSource::RufzXp => "RufzXP",
+        })
+    }
+}
+
+/// One run read from a result file.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ParsedResult {
+    pub date: Option<NaiveDate>,
+    pub time: Option<NaiveTime>,
+    pub score: Option<u32>,
+    pub wpm: Option<f32>,
+    /// Calls copied right, out of those sent.
+    pub correct: Option<(u32, u32)>,
+    pub seconds: Option<u32>,
+}
+
+fn parse_date(token: &str) -> Option<NaiveDate> {
+    ["%Y-%m-%d", "%Y/%m/%d", "%d.%m.%Y"].iter()
+        .find_map(|format| NaiveDate::parse_from_str(token, format).ok())
+}
+
+fn parse_time(token: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(token, "%H:%M:%S").ok()
+        .or_else(|| NaiveTime::parse_from_str(token, "%H:%M").ok())
+}
+
+/// Reads one line of MorseRunner's `HstResults.txt`, which it adds to after every run in
+/// HST mode: `CALL NAME DATE TIME WPM SCORE`, e.g. `DL1ABC ALEX 2025-03-01 13:28 30 12345`.
+/// The fields are read from the right, since the name may be missing or have spaces in it.
+fn parse_hst_line(line: &str) -> Option<ParsedResult> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [_, .., date, time, wpm, score] = fields[..] else {
+        return None;
+    };
+    Some(ParsedResult {
+        date: Some(parse_date(date)?),
+        time: Some(parse_time(time)?),
+        score: Some(score.parse().ok()?),
+        wpm: Some(wpm.parse().ok().filter(|wpm: &f32| *wpm > 0.0)?),
+        ..ParsedResult::default()
+    })
+}
+
+/// One attempt of a RufzXP report, built up a row at a time.
+#[derive(Default)]
+struct RufzAttempt {
+    result: ParsedResult,
+    points: u32,
+    top_cpm: Option<u32>,
+    sent: u32,
+    copied: u32,
+}
+
+impl RufzAttempt {
+    fn finish(mut self) -> Option<ParsedResult> {
+        if self.sent == 0 && self.result.score.is_none() {
+            return None;
+        }
+        self.result.score.get_or_insert(self.points);
+        self.result.wpm = self.top_cpm.map(|cpm| cpm as f32 / CPM_PER_WPM);
+        self.result.correct = (self.sent > 0).then_some((self.copied, self.sent));
+        Some(self.result)
+    }
+}
+
+/// Reads a RufzXP attempt report. Each attempt starts with the `RufzXP` banner line and a
+/// `Date:` line, then lists every call sent in a row of `Nr Call Entry Speed Points` (the
+/// entry is missing when nothing was typed, and the speed is in characters per minute),
+/// and ends with `Score: N`. A file can hold several attempts one after another.
+fn parse_rufzxp(text: &str) -> Vec<ParsedResult> {
+    let mut results = Vec::new();
+    let mut attempt = RufzAttempt::default();
+    for line in text.lines().map(str::trim) {
+        let lower = line.to_lowercase();
+        if lower.starts_with("rufzxp") {
+            results.extend(std::mem::take(&mut attempt).finish());
+        } else if let Some(rest) = lower.strip_prefix("date:") {
+            let mut fields = rest.split_whitespace();
+            attempt.result.date = fields.next().and_then(parse_date);
+            attempt.result.time = fields.next().and_then(parse_time);
+        } else if let Some(rest) = lower.strip_prefix("score:") {
+            attempt.result.score = rest.trim().parse().ok();
+        } else {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (call, entry, speed, points) = match fields[..] {
+                [nr, call, entry, speed, points] if nr.parse::<u32>().is_ok() => (call, entry, speed, points),
+                [nr, call, speed, points] if nr.parse::<u32>().is_ok() => (call, "", speed, points),
+                _ => continue,
+            };
+            let (Ok(cpm), Ok(points)) = (speed.parse::<u32>(), points.parse::<u32>()) else {
+                continue;
+            };
+            attempt.sent += 1;
+            attempt.copied += u32::from(entry.eq_ignore_ascii_case(call));
+            attempt.points += points;
+            attempt.top_cpm = attempt.top_cpm.max(Some(cpm));
+        }
+    }
+    results.extend(attempt.finish());
+    results
+}
+
+/// The runs in a result file written by `source`; lines that don't fit its layout are skipped.
+pub fn parse(source: ImportSource, text: &str) -> Vec<ParsedResult> {
+    match source {
+        ImportSource::MorseRunner => text.lines().filter_map(parse_hst_line).collect(),
+        ImportSource::RufzXp => parse_rufzxp(text),
+    }
+}
+
+fn to_session(result: &ParsedResult, source: ImportSource, fallback: DateTime<Local>, config: &AppConfig, overall_accuracy: f32) -> LearningSession {
+    let timestamp = result.date
+        .map(|date| NaiveDateTime::new(date, result.time.unwrap_or(NaiveTime::MIN)))
+        .and_then(|naive| Local.from_local_datetime(&naive).earliest())
+        .unwrap_or(fallback);
+    LearningSession {
+        timestamp: timestamp.fixed_offset(),
+        duration: result.seconds.unwrap_or(0),
+        chars_practiced: vec![],
+        words_practiced: vec![],
+        // Without a hit count the overall average is used so the import doesn't move it.
+        accuracy: result.correct
+            .map(|(correct, total)| correct as f32 / total as f32)
+            .unwrap_or(overall_accuracy),
+        difficulty: config.difficulty_level,
+        answers: vec![],
+        settings: None,
+        external: Some(ExternalResult {
+            source: source.to_string(),
+            score: result.score,
+            wpm: result.wpm,
+        }),
+        head_copy: vec![],
+        passive: false,
+        sending: None,
+        ending: None,
+        outside_progression: false,
+    }
+}
+
+fn describe(session: &LearningSession) -> String {
+    let Some(external) = &session.external else {
+        return String::new();
+    };
+    let mut parts = vec![format!("{:<11}", external.source)];
+    if let Some(score) = external.score {
+        parts.push(format!("score {}", score));
+    }
+    if let Some(wpm) = external.wpm {
+        parts.push(format!("{:.1} wpm", wpm));
+    }
+    parts.join("  ")
+}
+
+/// Adds MorseRunner and RufzXP results to the session history.
+pub fn results(files: &[PathBuf], source: Option<ImportSource>) -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load().unwrap_or_default();
+    let mut stats = UserStats::load()?;
+
+    let mut sessions = Vec::new();
+    for path in files {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        let source = source.or_else(|| ImportSource::detect(path, &text))
+            .ok_or_else(|| format!("cannot tell which program wrote {}; pass --source", path.display()))?;
+        let fallback = fs::metadata(path)
+            .and_then(|m| m.modified())
+            .map(DateTime::<Local>::from)
+            .unwrap_or_else(|_| Local::now());
+
+        let found = parse(source, &text);
+        if found.is_empty() {
+            eprintln!("No results found in {}", path.display());
+        }
+        for result in &found {
+            let session = to_session(result, source, fallback, &config, stats.accuracy);
+            println!("  {}  {}", locale::date_time(&session.timestamp), describe(&session));
+            sessions.push(session);
+        }
+    }
+
+    let found = sessions.len();
+    let added = stats.merge_sessions(sessions);
+    if added > 0 {
+        stats.save()?;
+    }
+    println!("Imported {} of {} results ({} already in your history).", added, found, found - added);
+    Ok(())
+}
+
+/// Adds the calls, names and exchanges of an ADIF log to the logbook.
+pub fn adif(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    let records = logbook::parse_adif(&text);
+    if records.is_empty() {
+        return Err(format!("no QSO records found in {}", path.display()).into());
+    }
+
+    let mut logbook = Logbook::load()?;
+    let added = logbook.add_records(&records);
+    if added > 0 {
+        logbook.save()?;
+    }
+    println!("Read {} QSOs, {} new items for practice --logbook.", records.len(), added);
+    println!("Logbook: {} calls, {} names, {} exchanges.",
+        logbook.calls.len(), logbook.names.len(), logbook.exchanges.len());
+    Ok(())
+}
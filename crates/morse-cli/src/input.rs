@@ -0,0 +1,114 @@
+//! Typed input for practice, read a keystroke at a time in a raw terminal so each key
+//! comes with the time it was pressed. Without a terminal, whole lines are read instead.
+
+use std::{io::{self, Write}, time::Instant};
+use morse_audio::Sidetone;
+use morse_platform::terminal::{self, Key, Keystrokes};
+use unicode_width::UnicodeWidthChar;
+
+/// A line of typed input.
+pub struct TypedLine {
+    pub text: String,
+    /// When each `.` and `-` still in the line was typed; pasted ones are left out.
+    pub keyed_at: Vec<Instant>,
+    /// When Enter was pressed.
+    pub answered: Instant,
+    /// Some of the line was pasted rather than typed.
+    pub pasted: bool,
+}
+
+fn interrupt(keys: Keystrokes) -> ! {
+    keys.disable();
+    println!();
+    std::process::exit(130);
+}
+
+/// Reads one line, sounding every `.` and `-` on `sidetone` as its key is pressed. Pasted
+/// text is not sounded but marks the line as pasted, and keys that type nothing, such as
+/// the arrows, are left out. Without a terminal it reads a plain line,
+/// with no key times. `None` when input ends, or Ctrl+D is pressed, before anything is typed.
+pub fn read_line(sidetone: Option<&Sidetone>) -> io::Result<Option<TypedLine>> {
+    let Ok(mut keys) = Keystrokes::enable() else {
+        return Ok(terminal::read_line().map(|line| {
+            let (text, pasted) = terminal::strip_paste_markers(&line);
+            TypedLine { text, keyed_at: Vec::new(), answered: Instant::now(), pasted }
+        }));
+    };
+    let mut stdout = io::stdout();
+    let mut line = String::new();
+    // Where in the line each element was typed, and when.
+    let mut keyed: Vec<(usize, Instant)> = Vec::new();
+    let mut pasted = false;
+
+    let answered = loop {
+        let Some(key) = keys.next() else {
+            if line.is_empty() {
+                return Ok(None);
+            }
+            break Instant::now();
+        };
+        match key.key {
+            Key::Enter => break key.at,
+            Key::Interrupt => interrupt(keys),
+            Key::EndOfInput if line.is_empty() => return Ok(None),
+            Key::EndOfInput => continue,
+            Key::Backspace => {
+                // Nothing to erase on an empty line, which would eat into the prompt.
+                let Some(c) = line.pop() else {
+                    continue;
+                };
+                keyed.retain(|(at, _)| *at < line.len());
+                // A wide character, such as a pasted CJK one, takes more than one cell.
+                let cells = c.width().unwrap_or(0);
+                write!(stdout, "{}{}{}", "\x08".repeat(cells), " ".repeat(cells), "\x08".repeat(cells))?;
+            }
+            Key::Paste(text) => {
+                // Only the first line, as Enter would have ended the answer there.
+                let text = text.lines().next().unwrap_or_default();
+                line.push_str(text);
+                pasted = true;
+                write!(stdout, "{}", text)?;
+            }
+            Key::Char(c) => {
+                if matches!(c, '.' | '-') {
+                    keyed.push((line.len(), key.at));
+                }
+                if let Some(sidetone) = sidetone {
+                    sidetone.key(c);
+                }
+                line.push(c);
+                write!(stdout, "{}", c)?;
+            }
+        }
+        stdout.flush()?;
+    };
+    drop(keys);
+    println!();
+    Ok(Some(TypedLine {
+        text: line,
+        keyed_at: keyed.into_iter().map(|(_, at)| at).collect(),
+        answered,
+        pasted,
+    }))
+}
+
+/// Reads a single key for a prompt, acting on it without waiting for Enter: Enter itself
+/// comes back as `'\n'`. Without a terminal it reads a line and takes its first character,
+/// or `'\n'` for an empty one. `None` when input ends.
+pub fn read_key() -> Option<char> {
+    let Ok(mut keys) = Keystrokes::enable() else {
+        return terminal::read_line().map(|line| line.trim().chars().next().unwrap_or('\n'));
+    };
+    let key = loop {
+        match keys.next()?.key {
+            Key::Enter => break '\n',
+            Key::Interrupt => interrupt(keys),
+            Key::EndOfInput => return None,
+            Key::Char(c) if c.is_ascii() => break c,
+            _ => continue,
+        }
+    };
+    drop(keys);
+    println!("{}", key.to_string().trim());
+    Some(key)
+}
@@ -0,0 +1,55 @@
+use clap::ValueEnum;
+use serde_derive::{Serialize, Deserialize};
+use morse_core::keyer::{KeyerMode, KeyerSettings};
+use crate::{cli, config::AppConfig};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Mode {
+    /// Iambic mode A: letting go of a squeeze stops after the current element
+    IambicA,
+    /// Iambic mode B (Curtis B): letting go of a squeeze adds one opposite element
+    #[default]
+    IambicB,
+    /// Semi-automatic bug: automatic dits, dahs held by hand
+    Bug,
+}
+
+/// The emulated keyer, set up like the one on your rig.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct KeyerConfig {
+    pub mode: Mode,
+    /// Swap the paddles so the right one sends dits, for left-handed operation.
+    pub reverse: bool,
+}
+
+impl KeyerConfig {
+    pub fn settings(&self) -> KeyerSettings {
+        KeyerSettings {
+            mode: match self.mode {
+                Mode::IambicA => KeyerMode::IambicA,
+                Mode::IambicB => KeyerMode::IambicB,
+                Mode::Bug => KeyerMode::Bug,
+            },
+            reverse: self.reverse,
+        }
+    }
+}
+
+/// Changes the given keyer options and shows the resulting setup.
+pub fn configure(mode: Option<Mode>, reverse: Option<bool>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    if mode.is_some() || reverse.is_some() {
+        config.keyer.mode = mode.unwrap_or(config.keyer.mode);
+        config.keyer.reverse = reverse.unwrap_or(config.keyer.reverse);
+        config.save()?;
+    }
+    println!("Keyer mode: {}", cli::value_name(&config.keyer.mode));
+    println!("Paddles:    {}", if config.keyer.reverse {
+        "reversed (right paddle sends dits)"
+    } else {
+        "normal (left paddle sends dits)"
+    });
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use morse_platform::terminal;
+
+/// Width of rules and banners on terminals wide enough for them.
+const FRAME_WIDTH: usize = 48;
+/// Narrowest width laid out for; smaller terminals wrap the lines themselves.
+const MIN_WIDTH: usize = 16;
+/// Space between table columns.
+const COLUMN_GAP: usize = 2;
+
+/// Columns available for output, or `None` when it goes to a pipe or file and isn't wrapped.
+pub fn columns() -> Option<usize> {
+    terminal::width().map(|w| w.max(MIN_WIDTH))
+}
+
+fn frame_width() -> usize {
+    columns().map_or(FRAME_WIDTH, |c| c.min(FRAME_WIDTH))
+}
+
+/// A horizontal rule of `ch` as wide as the frame, or the terminal when it's narrower.
+pub fn rule(ch: char) -> String {
+    ch.to_string().repeat(frame_width())
+}
+
+/// `title` centred between two `=` rules, or `short` when the title doesn't fit.
+pub fn banner(title: &str, short: &str) -> String {
+    let width = frame_width();
+    let title = if title.chars().count() <= width { title } else { short };
+    let indent = width.saturating_sub(title.chars().count()) / 2;
+    format!("{}\n{}{}\n{}", rule('='), " ".repeat(indent), title, rule('='))
+}
+
+/// `text` broken between words to fit the terminal, with continuation lines indented by
+/// `indent` spaces. Left as it is when output isn't to a terminal.
+pub fn wrap(text: &str, indent: usize) -> String {
+    let Some(width) = columns() else {
+        return text.to_string();
+    };
+    let words = text.trim_start_matches(' ');
+    let mut lead = text.len() - words.len();
+    let mut line = " ".repeat(lead);
+    let mut lines = Vec::new();
+    for word in words.split(' ') {
+        let length = line.chars().count();
+        if length > lead && length + 1 + word.chars().count() > width {
+            lines.push(line);
+            lead = indent.min(width / 2);
+            line = " ".repeat(lead);
+        } else if length > lead {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    lines.push(line);
+    lines.join("\n")
+}
+
+/// Parts of a status line joined with ` | `, or one per line when they don't fit on one.
+pub fn join_fitting(parts: &[String]) -> String {
+    let joined = parts.join(" | ");
+    match columns() {
+        Some(width) if joined.chars().count() > width => parts.join("\n"),
+        _ => joined,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Rows of cells printed in aligned columns or, on terminals too narrow for those, as a
+/// block per row: the first cell, then `Header: value` for each of the others.
+#[derive(Debug, Clone)]
+pub struct Table {
+    headers: Vec<(String, Align)>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[(&str, Align)]) -> Self {
+        Table {
+            headers: headers.iter().map(|(h, a)| (h.to_string(), *a)).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Adds a row; rows may have fewer cells than there are columns.
+    pub fn row(&mut self, cells: Vec<String>) {
+        self.rows.push(cells);
+    }
+
+    fn widths(&self) -> Vec<usize> {
+        self.headers.iter().enumerate()
+            .map(|(i, (header, _))| {
+                self.rows.iter()
+                    // A short row's last cell runs on past the columns it doesn't fill.
+                    .filter(|row| i + 1 < row.len() || row.len() == self.headers.len())
+                    .filter_map(|row| row.get(i))
+                    .map(|cell| cell.chars().count())
+                    .chain([header.chars().count()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+
+    fn line(&self, cells: &[String], widths: &[usize]) -> String {
+        let mut line = String::new();
+        for (i, cell) in cells.iter().enumerate() {
+            if i > 0 {
+                line.push_str(&" ".repeat(COLUMN_GAP));
+            }
+            let (width, last) = (widths[i], i + 1 == cells.len());
+            match self.headers[i].1 {
+                Align::Right => line.push_str(&format!("{:>w$}", cell, w = width)),
+                Align::Left if last => line.push_str(cell),
+                Align::Left => line.push_str(&format!("{:<w$}", cell, w = width)),
+            }
+        }
+        line
+    }
+
+    pub fn print(&self) {
+        let widths = self.widths();
+        let total = widths.iter().sum::<usize>() + COLUMN_GAP * widths.len().saturating_sub(1);
+        if columns().is_none_or(|c| total <= c) {
+            let headers: Vec<String> = self.headers.iter().map(|(h, _)| h.clone()).collect();
+            println!("{}", self.line(&headers, &widths));
+            for row in &self.rows {
+                println!("{}", self.line(row, &widths));
+            }
+            return;
+        }
+        for (n, row) in self.rows.iter().enumerate() {
+            if n > 0 {
+                println!();
+            }
+            for (i, cell) in row.iter().enumerate() {
+                // Spaces that padded the cell within its column only get in the way here.
+                let cell = cell.split_whitespace().collect::<Vec<_>>().join(" ");
+                match i {
+                    0 => println!("{}", wrap(&cell, 2)),
+                    _ => println!("{}", wrap(&format!("  {}: {}", self.headers[i].0, cell), 4)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+pub mod analyze;
+pub mod ban;
+pub mod broadcast;
+pub mod bundle;
+pub mod cli;
+pub mod compare;
+pub mod config;
+pub mod convert;
+pub mod decode_audio;
+pub mod endings;
+pub mod estimate;
+pub mod exam;
+pub mod export_audio;
+pub mod generate;
+pub mod goals;
+pub mod head_copy;
+pub mod hooks;
+pub mod import;
+pub mod input;
+pub mod keyer;
+pub mod layout;
+pub mod listen;
+pub mod locale;
+pub mod logbook;
+pub mod mic;
+pub mod midi;
+pub mod mouse;
+pub mod net;
+pub mod novelty;
+pub mod paddles;
+pub mod pdf;
+pub mod pipeline;
+pub mod placement;
+pub mod practice_time;
+pub mod probation;
+pub mod radiogram;
+pub mod recording;
+pub mod reload;
+pub mod research;
+pub mod scoring;
+pub mod sending;
+pub mod serial_key;
+pub mod share;
+pub mod sidetone;
+pub mod sound_check;
+pub mod spacebar;
+pub mod speed;
+pub mod speedtest;
+pub mod streak;
+pub mod strength;
+pub mod suggest;
+pub mod sync;
+pub mod template;
+pub mod vibration_check;
+pub mod winkeyer;
+pub mod worksheet;
@@ -0,0 +1,120 @@
+use std::collections::BTreeSet;
+use std::thread;
+use std::time::{Duration, Instant};
+use chrono::Local;
+use rand::seq::SliceRandom;
+use morse_core::material;
+use morse_platform::speech;
+use morse_stats::{LearningSession, UserStats};
+use crate::{cli::Output, config::AppConfig, net::Station, practice_time};
+
+/// Groups and words in each round, after the current level's new characters.
+const ROUND_GROUPS: usize = 4;
+const ROUND_WORDS: usize = 4;
+/// Silence after a spoken answer before the next item.
+const ITEM_GAP: Duration = Duration::from_millis(1500);
+
+/// How punctuation is read out.
+const PUNCTUATION_NAMES: [(char, &str); 16] = [
+    ('.', "period"), (',', "comma"), ('?', "question mark"), ('/', "slash"),
+    ('=', "equals"), ('+', "plus"), ('-', "dash"), ('\'', "apostrophe"),
+    ('!', "exclamation mark"), ('(', "open bracket"), (')', "close bracket"), ('"', "quote"),
+    ('@', "at"), (':', "colon"), (';', "semicolon"), ('&', "ampersand"),
+];
+
+/// What is said for an item: a word as it is, anything else letter by letter.
+fn spoken(item: &str, is_word: bool) -> String {
+    if is_word {
+        return item.to_lowercase();
+    }
+    item.chars()
+        .map(|c| PUNCTUATION_NAMES.iter()
+            .find(|(p, _)| *p == c)
+            .map(|(_, name)| name.to_string())
+            .unwrap_or_else(|| c.to_string()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Plays items from the current level for `minutes` without waiting for input, saying
+/// each answer `delay` seconds after it. The session is saved as passive practice after
+/// every round, so stopping with Ctrl+C keeps what was listened to.
+pub fn run(minutes: Option<u32>, delay: f32, wpm: Option<f32>) -> Result<(), Box<dyn std::error::Error>> {
+    if !morse_audio::audio_available() {
+        return Err("audio-only practice needs an audio device".into());
+    }
+    let config = AppConfig::load().unwrap_or_default();
+    let mut stats = UserStats::load()?;
+    let minutes = minutes.unwrap_or(config.session_duration);
+    let progression = config.progression();
+    let chars: Vec<char> = progression.chars_through(config.difficulty_level).into_iter()
+        .filter(|c| config.banned.allows_char(*c))
+        .collect();
+    let corpus: Vec<String> = progression.common_words.iter()
+        .filter(|word| config.banned.allows_word(word))
+        .cloned()
+        .collect();
+    let new_chars: Vec<String> = progression.level(config.difficulty_level)
+        .map(|l| l.chars_to_learn.iter().filter(|c| chars.contains(c)).map(char::to_string).collect())
+        .unwrap_or_default();
+    let station = Station::new(Output::Audio, wpm, config.clone());
+    let mut rng = rand::rng();
+
+    println!("Audio-only practice for {} minute(s): listen, answer in your head, and hear the answer.", minutes);
+    println!("Nothing needs to be typed. Press Ctrl+C to stop early.");
+    stats.session_history.push(LearningSession {
+        timestamp: Local::now().fixed_offset(),
+        duration: 0,
+        chars_practiced: vec![],
+        words_practiced: vec![],
+        // Nothing is graded, so the overall accuracy is kept as it is.
+        accuracy: stats.accuracy,
+        difficulty: config.difficulty_level,
+        answers: vec![],
+        settings: Some(station.settings()),
+        external: None,
+        head_copy: vec![],
+        passive: true,
+        sending: None,
+        ending: None,
+        outside_progression: false,
+    });
+
+    let start = Instant::now();
+    let mut speech_failed = false;
+    let mut practiced: BTreeSet<char> = BTreeSet::new();
+    let mut words_practiced = Vec::new();
+    while start.elapsed() < Duration::from_secs(minutes as u64 * 60) {
+        let mut round = new_chars.clone();
+        round.extend(material::groups_and_words(&chars, &corpus, ROUND_GROUPS, ROUND_WORDS, &mut rng));
+        round.shuffle(&mut rng);
+        for item in &round {
+            station.send(item);
+            thread::sleep(Duration::from_secs_f32(delay));
+            let is_word = corpus.contains(item);
+            if !speech_failed {
+                if let Err(e) = speech::speak(&spoken(item, is_word)) {
+                    eprintln!("Error speaking the answer: {}", e);
+                    println!("Showing the answers instead.");
+                    speech_failed = true;
+                }
+            }
+            println!("{}", item);
+            practiced.extend(item.chars());
+            if is_word {
+                words_practiced.push(item.clone());
+            }
+            thread::sleep(ITEM_GAP);
+        }
+
+        if let Some(session) = stats.session_history.last_mut() {
+            session.duration = start.elapsed().as_secs() as u32;
+            session.chars_practiced = practiced.iter().copied().collect();
+            session.words_practiced = words_practiced.clone();
+        }
+        stats.save()?;
+    }
+    println!("\nListened for {} minute(s), saved as passive practice.", start.elapsed().as_secs() / 60);
+    println!("{}", practice_time::summary(&stats.practice_time::<Local>(None)));
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use std::sync::OnceLock;
+use chrono::{DateTime, FixedOffset, Local, Locale, NaiveDate};
+use crate::config::AppConfig;
+
+/// Environment variables the system locale is read from, most specific first.
+const LOCALE_VARS: [&str; 3] = ["LC_ALL", "LC_TIME", "LANG"];
+
+/// Reads a locale name such as `de_DE`, `de-DE` or `de_DE.UTF-8`.
+pub fn parse(name: &str) -> Option<Locale> {
+    let name = name.split(['.', '@']).next().unwrap_or("").replace('-', "_");
+    match name.as_str() {
+        "C" | "POSIX" => Some(Locale::POSIX),
+        _ => Locale::try_from(name.as_str()).ok(),
+    }
+}
+
+/// The locale reports are formatted for: `locale` in the config, or else the system's,
+/// or else POSIX.
+pub fn current() -> Locale {
+    static LOCALE: OnceLock<Locale> = OnceLock::new();
+    *LOCALE.get_or_init(|| {
+        let config = AppConfig::load().unwrap_or_default();
+        config.locale.as_deref()
+            .and_then(parse)
+            .or_else(|| LOCALE_VARS.iter()
+                .filter_map(|var| std::env::var(var).ok())
+                .find(|value| !value.is_empty())
+                .and_then(|value| parse(&value)))
+            .unwrap_or(Locale::POSIX)
+    })
+}
+
+/// A date and time in the local time zone, e.g. `14.06.2025 18:30:00` in German.
+pub fn date_time(time: &DateTime<FixedOffset>) -> String {
+    time.with_timezone(&Local).format_localized("%x %X", current()).to_string()
+}
+
+pub fn date(date: NaiveDate) -> String {
+    date.format_localized("%x", current()).to_string()
+}
+
+/// `value` with `places` decimals and the locale's decimal point, e.g. `2,35` in German.
+pub fn decimal(value: f32, places: usize) -> String {
+    let point = pure_rust_locales::locale_match!(current() => LC_NUMERIC::DECIMAL_POINT);
+    format!("{:.*}", places, value).replace('.', point)
+}
+
+/// Like `decimal`, with a sign also in front of positive numbers.
+pub fn signed(value: f32, places: usize) -> String {
+    let sign = if value >= 0.0 { "+" } else { "" };
+    format!("{}{}", sign, decimal(value, places))
+}
@@ -0,0 +1,139 @@
+use std::{fs, path::PathBuf};
+use serde_derive::{Serialize, Deserialize};
+use morse_core::morse;
+use crate::ban::BanList;
+
+/// Exchange fields worth copying, received ones first.
+const EXCHANGE_FIELDS: [&str; 11] = [
+    "RST_RCVD", "SRX", "SRX_STRING", "STATE", "VE_PROV", "ARRL_SECT", "CQZ", "ITUZ",
+    "RST_SENT", "STX", "STX_STRING",
+];
+
+/// Callsigns, names and exchanges of worked stations, collected from imported ADIF logs.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Logbook {
+    pub calls: Vec<String>,
+    pub names: Vec<String>,
+    pub exchanges: Vec<String>,
+}
+
+/// One QSO record of an ADIF log as (field name, value) pairs, names upper-cased.
+pub type AdifRecord = Vec<(String, String)>;
+
+/// Reads the records of an ADIF (`.adi`) log, skipping the header.
+pub fn parse_adif(text: &str) -> Vec<AdifRecord> {
+    let mut records = Vec::new();
+    let mut record = AdifRecord::new();
+    // A file without a header starts straight with the first record.
+    let mut in_header = !text.trim_start().starts_with('<');
+    let mut rest = text;
+
+    while let Some(open) = rest.find('<') {
+        let Some(close) = rest[open..].find('>') else {
+            break;
+        };
+        let spec = &rest[open + 1..open + close];
+        rest = &rest[open + close + 1..];
+
+        let mut parts = spec.split(':');
+        let name = parts.next().unwrap_or_default().trim().to_uppercase();
+        let length: usize = parts.next().and_then(|l| l.trim().parse().ok()).unwrap_or(0);
+        let value: String = rest.chars().take(length).collect();
+        rest = &rest[value.len()..];
+
+        match name.as_str() {
+            "EOH" => {
+                in_header = false;
+                record.clear();
+            }
+            "EOR" => {
+                in_header = false;
+                if !record.is_empty() {
+                    records.push(std::mem::take(&mut record));
+                }
+            }
+            _ if !in_header && length > 0 => record.push((name, value)),
+            _ => {}
+        }
+    }
+    records
+}
+
+/// Upper-cased words of `value` that can be sent in Morse.
+fn sendable(value: &str) -> impl Iterator<Item = String> + '_ {
+    value.split_whitespace()
+        .map(|w| w.to_uppercase())
+        .filter(|w| w.chars().all(|c| morse::char_to_morse(c).is_some()))
+}
+
+fn add(list: &mut Vec<String>, item: String) -> bool {
+    if list.contains(&item) {
+        return false;
+    }
+    list.push(item);
+    true
+}
+
+impl Logbook {
+    pub fn path() -> PathBuf {
+        morse_platform::paths::data_file("morse_logbook.toml")
+    }
+
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Logbook::default());
+        }
+        let data = fs::read_to_string(&path)?;
+        toml::from_str(&data).map_err(|e| e.into())
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Adds the call, the operator's first name and the exchange of each QSO.
+    /// Returns how many new items were added.
+    pub fn add_records(&mut self, records: &[AdifRecord]) -> usize {
+        let mut added = 0;
+        for record in records {
+            for (name, value) in record {
+                let field = name.as_str();
+                let items: Vec<String> = match field {
+                    "NAME" => sendable(value).take(1).collect(),
+                    _ => sendable(value).collect(),
+                };
+                for item in items {
+                    let list = match field {
+                        "CALL" => &mut self.calls,
+                        "NAME" => &mut self.names,
+                        _ if EXCHANGE_FIELDS.contains(&field) => &mut self.exchanges,
+                        _ => continue,
+                    };
+                    added += usize::from(add(list, item));
+                }
+            }
+        }
+        added
+    }
+
+    /// Everything in the logbook that isn't banned, as practice words.
+    pub fn items(&self, banned: &BanList) -> Vec<String> {
+        self.calls.iter()
+            .chain(&self.names)
+            .chain(&self.exchanges)
+            .filter(|item| banned.allows_word(item))
+            .cloned()
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty() && self.names.is_empty() && self.exchanges.is_empty()
+    }
+}
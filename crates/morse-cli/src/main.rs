@@ -0,0 +1,1892 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{self, Write},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use rand::{rngs::ThreadRng, seq::SliceRandom, Rng};
+use serde_json::json;
+use std::thread::{self, JoinHandle};
+use clap::Parser;
+use morse_cli::{
+    analyze::{self, AnalyzeInput},
+    ban,
+    broadcast,
+    bundle::{self, BundleOptions},
+    cli::{self, BundleAction, Cli, Command, ConfigAction, Drill, ExportTarget, FeedbackStyle, GoalAction, ImportAction, Output, PracticeArgs, StatsAction, Subset, SyncAction},
+    compare,
+    endings,
+    estimate,
+    exam,
+    export_audio,
+    config::{self, AppConfig},
+    convert::{self, ConvertArgs},
+    decode_audio,
+    generate,
+    goals,
+    head_copy,
+    hooks::HookEvent,
+    import,
+    input::{self, TypedLine},
+    keyer,
+    layout,
+    listen,
+    logbook::Logbook,
+    mic,
+    midi,
+    mouse,
+    net,
+    novelty::{self, NoveltyTagger},
+    paddles,
+    pipeline::{self, Stage, StageKind, StageResult},
+    placement,
+    practice_time,
+    probation::{self, Probation, ProbationOutcome, PROBATION_SESSIONS},
+    radiogram,
+    recording::KeyingRecording,
+    reload::{self, ConfigWatch},
+    sending,
+    serial_key,
+    research,
+    share,
+    sidetone,
+    sound_check,
+    spacebar,
+    speed::DEFAULT_WPM,
+    speedtest,
+    streak,
+    strength,
+    suggest,
+    sync,
+    template,
+    vibration_check,
+    winkeyer,
+    worksheet::{self, WorksheetOptions},
+};
+use morse_audio::{
+    audio_available, capture::Capture, led, play_morse_code_at, play_rendered, Cue, Metronome, Sidetone,
+    render::ToneSettings,
+    terminal::{self, TerminalStyle},
+    vibration,
+};
+use morse_core::{
+    morse,
+    coverage,
+    grading,
+    keying::{self, SendingTally},
+    material,
+    progression::ProgressionSystem,
+    pseudowords::PseudoWordGenerator,
+    rehab,
+    memorization,
+    scheduler::{self, Scheduler, WORDS_PER_SESSION},
+    timing::{self, Spacing, TimingEvent},
+    verbal,
+    weakness,
+};
+use morse_stats::{AnswerRecord, EndReason, LearningSession, Novelty, SessionEnding, SessionSettings, StatsRecovery, UserStats};
+
+
+/// Character drills mixed into a word session for letters often missed in words.
+const CHAR_DRILLS_PER_SESSION: usize = 3;
+const CHAR_DRILL_EVERY: usize = 3;
+/// How many recent word answers the weak letters are judged by.
+const WORD_ANSWER_WINDOW: usize = 200;
+/// Code groups per minute of a pipeline's groups stage.
+const GROUPS_PER_MINUTE: usize = 4;
+/// Weakest characters reviewed when nothing was missed.
+const REVIEW_ITEMS: usize = 5;
+/// How much `+` and `-` between items change the volume, in percent.
+const VOLUME_STEP: u8 = 10;
+/// Typed at the answer prompt to see the code.
+const HINT: &str = "?";
+/// Pause between a missed answer played back and the right code.
+const ECHO_PAUSE: Duration = Duration::from_millis(600);
+/// Breather between items of an echo session, which runs on without a prompt.
+const ECHO_ROUND_PAUSE: Duration = Duration::from_millis(1200);
+/// Smallest changes from the rolling baseline that get an up or down arrow.
+const BASELINE_ACCURACY_STEP: f32 = 0.01;
+const BASELINE_TIME_STEP: f32 = 0.05;
+/// Typed as the answer, ends an echo session.
+const QUIT: &str = "Q";
+
+struct MorseTutor {
+    options: PracticeArgs,
+    config: AppConfig,
+    stats: UserStats,
+    progression: ProgressionSystem,
+    practice_queue: Scheduler,
+    session_start: Instant,
+    correct_answers: u32,
+    total_answers: u32,
+    is_word_level: bool,
+    /// Stages of a `--pipeline` session, empty for a plain session.
+    stages: Vec<Stage>,
+    stage_results: Vec<StageResult>,
+    /// Items answered wrong this session, for the review stage.
+    missed: Vec<String>,
+    /// Open while typed answers, or keys on the keyboard, are sounded as they are keyed.
+    sidetone: Option<Sidetone>,
+    /// Pitch the current item is played at.
+    pitch_hz: f32,
+    /// Open while answers are keyed on an oscillator and heard through the microphone.
+    capture: Option<Capture>,
+    /// Dot length of the keying heard so far.
+    mic_unit_ms: f32,
+    /// The session's keyed answers, when they are recorded to a WAV file.
+    recording: Option<KeyingRecording>,
+    /// Element and gap lengths of the answers keyed this session.
+    sending: SendingTally,
+    /// Answers are keyed by holding the spacebar.
+    spacebar: bool,
+    /// Answers are keyed on two keys used as iambic paddles.
+    paddles: bool,
+    /// Answers are keyed on the mouse buttons, as paddles with `--paddles`.
+    mouse: bool,
+    /// Open while answers are keyed on a key wired to a serial port.
+    serial_key: Option<morse_platform::serial::SerialKey>,
+    /// Open while answers are keyed on a MIDI device.
+    midi: Option<morse_audio::midi::MidiKeys>,
+    /// Open while items are played and answers keyed on a WinKeyer.
+    winkeyer: Option<Arc<Mutex<morse_platform::winkeyer::WinKeyer>>>,
+    /// Picks up sound settings edited in the config file during the session.
+    config_watch: ConfigWatch,
+    /// Why the drill stopped, and the item on screen when it was stopped mid-answer.
+    end_reason: Option<(EndReason, Option<String>)>,
+    /// Tags each answer as new material, a retention check or review.
+    tagger: NoveltyTagger,
+    /// Items that kept needing hints, left out of normal queues until relearned.
+    rehab: BTreeSet<String>,
+    feedback: FeedbackStyle,
+    /// Set when the session is quit from an answer prompt rather than between items.
+    quit: bool,
+    rng: ThreadRng,
+}
+
+impl MorseTutor {
+    fn new(mut options: PracticeArgs) -> Self {
+        // A WinKeyer plays the items itself; whether it can is known once it's opened.
+        if options.output == Output::Audio && options.winkeyer.is_none() && !audio_available() {
+            match morse_audio::output_device() {
+                Some(name) => println!("Audio device '{}' not found, showing the code as text instead (see `config device`).", name),
+                None => println!("No audio device found, showing the code as text instead (try --output bell)."),
+            }
+            options.output = Output::Text;
+        }
+        let config = AppConfig::load().unwrap_or_default();
+        let stats = match UserStats::load_or_recover() {
+            Ok((stats, recovery)) => {
+                if let Some(recovery) = recovery {
+                    report_stats_recovery(&recovery);
+                }
+                stats
+            }
+            Err(e) => {
+                eprintln!("Error loading stats: {}", e);
+                UserStats::default()
+            }
+        };
+        if options.output == Output::Vibration && config.vibration_port.is_none() {
+            println!("No vibration device set, showing the code as text instead (see `config vibration`).");
+            options.output = Output::Text;
+        }
+        let progression = config.progression();
+        
+        // Words are all letters, so other subsets and focused drills stay on characters even at word level.
+        let is_word_level = options.is_word_material()
+            || (config.difficulty_level >= 9 && options.focus.is_none() && options.subset.is_none_or(|s| s == Subset::Letters));
+        
+        let rehab = rehab_items(&stats);
+        let tagger = NoveltyTagger::new(&stats, &progression, config.difficulty_level);
+        let feedback = options.feedback.unwrap_or(config.feedback);
+        
+        MorseTutor {
+            options,
+            config: config.clone(),
+            stats,
+            progression,
+            practice_queue: Scheduler::default(),
+            session_start: Instant::now(),
+            correct_answers: 0,
+            total_answers: 0,
+            is_word_level,
+            stages: Vec::new(),
+            stage_results: Vec::new(),
+            missed: Vec::new(),
+            sidetone: None,
+            pitch_hz: config.pitch_hz(),
+            capture: None,
+            mic_unit_ms: timing::unit_ms_for_wpm(config.character_wpm.unwrap_or(DEFAULT_WPM)),
+            recording: None,
+            sending: SendingTally::default(),
+            spacebar: false,
+            paddles: false,
+            mouse: false,
+            serial_key: None,
+            winkeyer: None,
+            midi: None,
+            config_watch: ConfigWatch::new(),
+            end_reason: None,
+            tagger,
+            rehab,
+            feedback,
+            quit: false,
+            rng: rand::rng(),
+        }
+    }
+
+    fn generate_practice_queue(&mut self) {
+        self.practice_queue = if self.options.rehab {
+            let mut items: Vec<String> = self.rehab.iter()
+                .flat_map(|item| std::iter::repeat_n(item.clone(), rehab::CLEAN_TO_GRADUATE))
+                .collect();
+            items.shuffle(&mut self.rng);
+            Scheduler::from_items(items)
+        } else if self.options.pseudo_words {
+            let generator = PseudoWordGenerator::new(&self.session_chars(), &self.progression.common_words);
+            Scheduler::for_pseudo_words(&generator, &mut self.rng)
+        } else if let Some(cover) = &self.options.cover {
+            let mut targets = generate::target_chars(Some(cover), &self.config, &self.progression);
+            if let Some(subset) = self.options.subset {
+                targets.retain(|c| subset.chars().contains(c));
+            }
+            let mut allowed = self.session_chars();
+            allowed.extend(&targets);
+            Scheduler::from_items(coverage::coverage_text(
+                &targets,
+                self.options.min_count,
+                &self.progression.common_words,
+                Some(&allowed),
+                &mut self.rng,
+            ))
+        } else if self.options.logbook {
+            let logbook = Logbook::load().unwrap_or_else(|e| {
+                eprintln!("Error loading logbook: {}", e);
+                Logbook::default()
+            });
+            Scheduler::for_words(&logbook.items(&self.config.banned), &mut self.rng)
+        } else if let Some(drill) = self.options.drill {
+            let count = self.config.session_duration as usize * GROUPS_PER_MINUTE;
+            let mut items = match drill {
+                Drill::Grids => material::grid_squares(count, &mut self.rng),
+                Drill::Frequencies => material::frequencies(count, &mut self.rng),
+            };
+            items.retain(|item| self.config.banned.allows_word(item));
+            Scheduler::from_items(items)
+        } else if self.is_word_level {
+            let mut scheduler = Scheduler::for_words(&self.word_pool(), &mut self.rng);
+            let drills: Vec<String> = self.weak_word_letters().iter()
+                .filter(|c| self.config.banned.allows_char(**c))
+                .take(CHAR_DRILLS_PER_SESSION)
+                .map(|c| c.to_string())
+                .collect();
+            if !drills.is_empty() {
+                println!("Mixing in character drills for letters you often miss in words: {}", drills.concat());
+            }
+            scheduler.mix_in(drills, CHAR_DRILL_EVERY);
+            scheduler
+        } else {
+            self.char_drills(self.config.session_duration)
+        };
+        if !self.options.rehab {
+            self.practice_queue.remove_all(&self.rehab);
+        }
+        if let Some(items) = self.options.items {
+            self.practice_queue.truncate(items);
+        }
+    }
+
+    fn char_drills(&mut self, minutes: u32) -> Scheduler {
+        if self.options.subset.is_some() || self.options.focus.is_some() {
+            return Scheduler::for_session(&self.session_chars(), None, minutes, &mut self.rng);
+        }
+        // Levels come without banned characters already, see `AppConfig::progression`.
+        Scheduler::for_session(
+            &self.config.banned.filter_chars(&self.config.known_chars),
+            self.progression.level(self.config.difficulty_level),
+            minutes,
+            &mut self.rng,
+        )
+    }
+
+    fn stage_queue(&mut self, stage: &Stage) -> Scheduler {
+        let chars = self.session_chars();
+        match stage.kind {
+            StageKind::Chars => self.char_drills(stage.minutes),
+            StageKind::Groups => Scheduler::from_items(material::code_groups(
+                &chars,
+                stage.minutes.max(1) as usize * GROUPS_PER_MINUTE,
+                material::GROUP_LENGTH,
+                &mut self.rng,
+            )),
+            StageKind::Words if self.is_word_level => Scheduler::for_words(&self.word_pool(), &mut self.rng),
+            StageKind::Words => {
+                let words = material::words_within(&chars, &self.progression.common_words, WORDS_PER_SESSION, &mut self.rng);
+                if words.len() >= WORDS_PER_SESSION / 2 {
+                    let mut scheduler = Scheduler::for_words(&words, &mut self.rng);
+                    scheduler.truncate(words.len());
+                    scheduler
+                } else {
+                    let generator = PseudoWordGenerator::new(&chars, &self.progression.common_words);
+                    Scheduler::for_pseudo_words(&generator, &mut self.rng)
+                }
+            }
+            StageKind::Review => {
+                let mut items = self.missed.clone();
+                if items.is_empty() {
+                    let strengths = self.stats.strengths(&chrono::Local::now());
+                    items = strength::weakest(&strengths, &chars).into_iter()
+                        .take(REVIEW_ITEMS)
+                        .map(|c| c.to_string())
+                        .collect();
+                }
+                let mut queue: Vec<String> = items.iter().chain(&items).cloned().collect();
+                queue.shuffle(&mut self.rng);
+                Scheduler::from_items(queue)
+            }
+        }
+    }
+
+    /// Common words minus the ones that have been memorized. Once memorization shows up, words
+    /// never practiced are preferred, and pseudo-words make up for a list that runs short.
+    fn word_pool(&mut self) -> Vec<String> {
+        let answers: Vec<(&str, f32, bool)> = self.stats.session_history.iter()
+            .filter(|s| s.difficulty >= 9)
+            .flat_map(|s| s.answers.iter())
+            .filter(|a| !a.suspect)
+            .map(|a| (a.item.as_str(), a.response_time, a.correct))
+            .collect();
+        let memorized = memorization::memorized_words(answers);
+        if memorized.is_empty() {
+            return self.progression.common_words.clone();
+        }
+
+        let mut pool: Vec<String> = self.progression.common_words.iter()
+            .filter(|w| !memorized.contains(*w))
+            .cloned()
+            .collect();
+        let unseen: Vec<String> = pool.iter()
+            .filter(|w| !self.stats.word_response_times.contains_key(*w))
+            .cloned()
+            .collect();
+        if unseen.len() >= WORDS_PER_SESSION {
+            pool = unseen;
+        }
+        println!("You seem to know these words by heart, so they're rotated out: {}",
+            memorized.iter().cloned().collect::<Vec<_>>().join(" "));
+
+        if pool.len() < WORDS_PER_SESSION {
+            let generator = PseudoWordGenerator::new(&self.progression.chars_through(8), &self.progression.common_words);
+            for _ in 0..WORDS_PER_SESSION * 5 {
+                if pool.len() >= WORDS_PER_SESSION {
+                    break;
+                }
+                let word = generator.generate(&mut self.rng);
+                if !pool.contains(&word) && !self.config.banned.words.contains(&word) {
+                    pool.push(word);
+                }
+            }
+        }
+        pool
+    }
+
+    /// Letters that keep going wrong inside words, judged by the most recent word answers.
+    fn weak_word_letters(&self) -> Vec<char> {
+        let answers: Vec<(&str, &str)> = self.stats.session_history.iter().rev()
+            .filter(|s| s.difficulty >= 9)
+            .flat_map(|s| s.answers.iter().rev())
+            .filter(|a| a.item.chars().count() > 1)
+            .take(WORD_ANSWER_WINDOW)
+            .map(|a| (a.item.as_str(), a.answer.as_str()))
+            .collect();
+        weakness::weak_letters(&weakness::letter_stats(answers))
+    }
+
+    /// Characters of the session, without banned ones.
+    fn session_chars(&self) -> Vec<char> {
+        if let Some(subset) = self.options.subset {
+            return self.config.banned.filter_chars(&subset.chars());
+        }
+        if let Some(focus) = &self.options.focus {
+            let chars: Vec<char> = focus.to_uppercase().chars().filter(|c| morse::char_to_morse(*c).is_some()).collect();
+            return self.config.banned.filter_chars(&chars);
+        }
+        let mut chars: Vec<char> = self.config.banned.filter_chars(&self.config.known_chars);
+        if let Some(level) = self.progression.level(self.config.difficulty_level) {
+            for c in &level.chars_to_learn {
+                if !chars.contains(c) {
+                    chars.push(*c);
+                }
+            }
+        }
+        chars
+    }
+
+    fn end_session(&mut self) {
+        let duration = self.session_start.elapsed().as_secs() as u32;
+        let accuracy = if self.total_answers > 0 {
+            self.correct_answers as f32 / self.total_answers as f32
+        } else {
+            0.0
+        };
+        
+        if let Some(session) = self.stats.session_history.last_mut() {
+            session.duration = duration;
+            session.accuracy = accuracy;
+            session.sending = self.sending.quality().map(sending::record);
+            let planned_minutes = match self.stages.is_empty() {
+                true => self.config.session_duration,
+                false => self.stages.iter().map(|s| s.minutes).sum(),
+            };
+            session.ending = self.end_reason.clone().map(|(reason, item)| SessionEnding {
+                reason,
+                items: self.total_answers as usize,
+                item,
+                planned_secs: planned_minutes * 60,
+            });
+            
+            if self.is_word_level {
+                session.words_practiced = self.practice_queue.iter().cloned().collect();
+            } else {
+                // Decode items hold a code, so count the character it stands for.
+                session.chars_practiced = self.practice_queue.iter()
+                    .filter_map(|s| match scheduler::decode_code(s) {
+                        Some(code) => morse::morse_to_char(code),
+                        None => s.chars().next(),
+                    })
+                    .collect();
+            }
+        }
+        
+        self.stats.sessions_completed += 1;
+        self.stats.accuracy = (self.stats.accuracy * (self.stats.sessions_completed - 1) as f32 + accuracy) / 
+                            self.stats.sessions_completed as f32;
+
+        if self.total_answers > 0 {
+            self.ramp_speed(accuracy);
+        }
+
+        if let Err(e) = self.config.save() {
+            eprintln!("Error saving configuration: {}", e);
+        }
+
+        if let Err(e) = self.stats.save() {
+            eprintln!("Error saving stats: {}", e);
+        }
+
+        if let Some(recording) = self.recording.as_ref().filter(|r| !r.is_empty()) {
+            match recording.save(self.config.pitch_hz()) {
+                Ok(path) => println!("Your keying was recorded to {}", path.display()),
+                Err(e) => eprintln!("Error saving the keying recording: {}", e),
+            }
+        }
+        
+        self.show_summary();
+        self.update_progression();
+
+        let mut summary = format!("{} of {} right ({:.0}%)", self.correct_answers, self.total_answers, accuracy * 100.0);
+        if let Some(line) = self.config.streak.streak(&self.stats).ok().as_ref().and_then(streak::summary) {
+            summary = format!("{}\n{}", summary, line);
+        }
+        self.notify("Session finished", &summary);
+    }
+
+    /// Shows a desktop notification, if they're turned on.
+    fn notify(&self, title: &str, body: &str) {
+        if !self.config.notifications {
+            return;
+        }
+        if let Err(e) = morse_platform::notify::notify(title, body) {
+            eprintln!("Error showing a notification: {}", e);
+        }
+    }
+
+    fn ramp_speed(&mut self, accuracy: f32) {
+        let current = self.config.ramped_wpm();
+        let speed = &mut self.config.speed;
+        if let Some(wpm) = speed.record_session(current, accuracy) {
+            println!("\n🚀 {} session(s) in a row at {:.0}% or better: playback speed raised to {:.0} WPM.",
+                speed.sessions_required, speed.min_accuracy * 100.0, wpm);
+            self.config.set_ramped_wpm(wpm);
+        } else if speed.ramp && current < speed.max_wpm {
+            println!("\nSpeed ramp: {} of {} sessions at {:.0}% or better toward {:.0} WPM.",
+                speed.streak, speed.sessions_required, speed.min_accuracy * 100.0,
+                (current + speed.increment).min(speed.max_wpm));
+        }
+    }
+
+    /// Whether answers are keyed, on an oscillator, the keyboard or a serial key, rather than typed.
+    fn keyed(&self) -> bool {
+        self.capture.is_some() || self.serial_key.is_some() || self.winkeyer.is_some() || self.midi.is_some() || self.spacebar || self.paddles || self.mouse
+    }
+
+    /// Reads one typed line, sounding it on the sidetone if one is open.
+    fn read_typed(&self) -> Option<TypedLine> {
+        match input::read_line(self.sidetone.as_ref()) {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("Error reading input: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Reads one keyed answer and adds it to the session's sending stats and recording.
+    fn read_keying(&mut self) -> Option<(String, Vec<TimingEvent>, Instant)> {
+        let unit_ms = self.spacing().unit_ms;
+        // Not with the microphone, which would hear the clicks.
+        let _metronome = match self.config.metronome && self.capture.is_none() && audio_available() {
+            true => Metronome::start(self.config.beat(self.options.wpm)),
+            false => None,
+        };
+        let (code, events, ended) = match (&self.capture, &mut self.serial_key) {
+            (Some(capture), _) => mic::read_keying(capture, &mut self.mic_unit_ms),
+            (None, _) if self.winkeyer.is_some() => winkeyer::read_answer(self.winkeyer.as_ref()?, unit_ms)?,
+            (None, _) if self.midi.is_some() => midi::read_keying(self.midi.as_ref()?, self.sidetone.as_ref(), &mut self.mic_unit_ms),
+            (None, Some(key)) if self.options.paddles => {
+                serial_key::read_paddles(key, self.config.keyer.settings(), unit_ms, self.sidetone.as_ref())?
+            }
+            (None, Some(key)) => serial_key::read_straight(key, self.sidetone.as_ref(), &mut self.mic_unit_ms)?,
+            (None, None) if self.mouse && self.options.paddles => {
+                mouse::read_paddles(self.config.keyer.settings(), unit_ms, self.sidetone.as_ref())?
+            }
+            (None, None) if self.mouse => mouse::read_straight(self.sidetone.as_ref(), &mut self.mic_unit_ms)?,
+            (None, None) if self.paddles => paddles::read_keying(self.config.keyer.settings(), unit_ms, self.sidetone.as_ref())?,
+            (None, None) => spacebar::read_keying(self.sidetone.as_ref(), &mut self.mic_unit_ms)?,
+        };
+        self.sending.add(&events, self.mic_unit_ms);
+        if let Some(recording) = &mut self.recording {
+            recording.add(&events);
+        }
+        Some((code, events, ended))
+    }
+
+    /// Asks for one item, returning whether it was answered right, or `None` if input
+    /// closed before an answer came.
+    fn practice_item(&mut self, item: &str) -> Option<bool> {
+        if self.options.random_pitch {
+            let range = self.config.pitch_range;
+            self.pitch_hz = self.rng.random_range(range.min_hz..=range.max_hz);
+        }
+        if self.options.copy {
+            return self.copy_item(item);
+        }
+        if self.options.verbal {
+            return self.verbal_item(item);
+        }
+        if self.options.echo {
+            return self.echo_item(item);
+        }
+        if let Some(code) = scheduler::decode_code(item) {
+            return self.decode_item(item, code);
+        }
+        // Word sessions can include single-character drills, and pipelines words and groups at any level.
+        let is_word = item.chars().count() > 1;
+        let morse_code = if is_word {
+            morse::encode_word(item)
+        } else {
+            morse::char_to_morse(item.chars().next().unwrap())
+                .map(|s| s.to_string())
+                .unwrap_or_default()
+        };
+        
+        self.heading(if is_word { "New Word" } else { "New Character" });
+        println!("{}: {}", if is_word { "Word" } else { "Character" }, item);
+        
+        // Rehab is recalling without help, and a keyed answer can't ask for one.
+        let hints = !self.keyed() && !self.options.rehab;
+        let prompt = match (self.keyed(), hints) {
+            (true, _) => "Key your answer: ",
+            (false, true) => "Your Morse code (? for a hint): ",
+            (false, false) => "Your Morse code: ",
+        };
+        print!("{}", prompt);
+        io::stdout().flush().unwrap();
+        
+        let start_time = Instant::now();
+        let mut hinted = false;
+        let (input, answered, pasted) = loop {
+            let (input, answered, pasted) = match self.keyed() {
+                true => {
+                    let (code, _, ended) = self.read_keying()?;
+                    (code, ended, false)
+                }
+                false => {
+                    let line = self.read_typed()?;
+                    (line.text, line.answered, line.pasted)
+                }
+            };
+            if hints && input.trim() == HINT {
+                hinted = true;
+                println!("Hint: {} ({})", morse_code, verbal::verbalize(&morse_code));
+                print!("{}", prompt);
+                io::stdout().flush().unwrap();
+                continue;
+            }
+            break (input, answered, pasted);
+        };
+        let response_time = answered.duration_since(start_time).as_secs_f32();
+        
+        let input = input.trim().to_uppercase();
+        let correct = input == morse_code;
+        // Keyed answers take as long as the keying, however fast it is.
+        let suspect = pasted || (!self.keyed() && grading::implausibly_fast(&input, response_time));
+        
+        self.total_answers += 1;
+        
+        if hinted {
+            // Reading the code off the screen says nothing about recall speed.
+        } else if suspect {
+            println!("⚠️ {} - not counted toward your speed stats.",
+                if pasted { "Pasted answer" } else { "That answer came in faster than anyone can type" });
+        } else if self.options.is_custom_material() {
+            // Generated material is throwaway and would only clutter word statistics.
+        } else if is_word {
+            self.stats.word_response_times.insert(item.to_string(), response_time);
+            self.stats.words_learned += 1;
+        } else {
+            if let Some(c) = item.chars().next() {
+                self.stats.response_times.insert(c, response_time);
+                self.stats.chars_learned += 1;
+            }
+        }
+
+        if correct && hinted {
+            println!("{}", if self.terse() { "✓ (hint)" } else { "✓ Correct, with a hint." });
+        } else {
+            if correct {
+                self.correct_answers += 1;
+            }
+            let miss = match self.options.rehab {
+                true => format!("{} is {}, {} (your: {})", item, morse_code, verbal::verbalize(&morse_code), input),
+                false => format!("Correct code: {} (your: {})", morse_code, input),
+            };
+            self.verdict(correct, response_time, format!("{} {}", item, morse_code), miss, item, &morse_code);
+        }
+
+        self.record_answer(AnswerRecord {
+            item: item.to_string(),
+            expected: morse_code.clone(),
+            answer: input.clone(),
+            correct,
+            response_time,
+            suspect,
+            hinted,
+            ..Default::default()
+        });
+        if hinted && !self.rehab.contains(item) {
+            self.rehab = rehab_items(&self.stats);
+            if self.rehab.contains(item) {
+                println!("{} keeps needing hints, so it leaves the normal queues for `practice --rehab`.", item);
+            }
+        }
+
+        if !correct && input.chars().all(|c| matches!(c, '.' | '-' | ' ' | '/')) {
+            self.echo(&input);
+        }
+        self.play(&morse_code);
+        Some(correct && !hinted)
+    }
+
+    /// First steps of a rehab session: each item is played and spelled out by sound, then
+    /// typed with its code in view. Recalling it from memory follows in the drill.
+    fn study_rehab(&mut self) {
+        println!("\nLearn each item again: listen to it, then type it with its code in view.");
+        for item in self.rehab.clone() {
+            let code = morse::encode_word(&item);
+            println!("\n{} sounds like {}: {}", item, verbal::verbalize(&code), code);
+            if let Some(playback) = self.play(&code) {
+                let _ = playback.join();
+            }
+            loop {
+                print!("Type {}: ", code);
+                io::stdout().flush().unwrap();
+                let Some(line) = self.read_typed() else {
+                    return;
+                };
+                if line.text.trim() == code {
+                    break;
+                }
+                println!("✗ Not quite, once more.");
+            }
+        }
+        println!("\nNow recall each one {} times without the code.", rehab::CLEAN_TO_GRADUATE);
+    }
+
+    /// Spoken-form drill for `--verbal`: either the item is shown and its spoken form typed,
+    /// or the other way round. Nothing is played, so it works without speakers.
+    fn verbal_item(&mut self, item: &str) -> Option<bool> {
+        let item = match scheduler::decode_code(item) {
+            Some(code) => morse::morse_to_char(code).map(|c| c.to_string()).unwrap_or_default(),
+            None => item.to_string(),
+        };
+        let code = morse::encode_word(&item);
+        let spoken = verbal::verbalize(&code);
+        let say_it = self.rng.random_bool(0.5);
+
+        self.heading(if say_it { "Say it" } else { "Name it" });
+        if say_it {
+            println!("{}: {}", if item.chars().count() > 1 { "Word" } else { "Character" }, item);
+            print!("How it sounds (e.g. dah-di-dit): ");
+        } else {
+            println!("Sounds like: {}", spoken);
+            print!("What it is: ");
+        }
+        io::stdout().flush().unwrap();
+
+        let start_time = Instant::now();
+        let line = self.read_typed()?;
+        let response_time = line.answered.duration_since(start_time).as_secs_f32();
+
+        let pasted = line.pasted;
+        let input = line.text.trim().to_string();
+        let (expected, correct) = if say_it {
+            // One character can be said without spaces between its syllables.
+            let heard = verbal::parse(&input);
+            let correct = match item.chars().count() {
+                1 => heard.map(|c| c.replace(' ', "")) == Some(code.clone()),
+                _ => heard == Some(code.clone()),
+            };
+            (spoken.clone(), correct)
+        } else {
+            (item.clone(), input.eq_ignore_ascii_case(&item))
+        };
+
+        self.total_answers += 1;
+        if correct {
+            self.correct_answers += 1;
+        }
+        let miss = match say_it {
+            true => format!("{} sounds like {} (your: {})", item, spoken, input),
+            false => format!("{} is {} (your: {})", spoken, item, input),
+        };
+        self.verdict(correct, response_time, format!("{} {}", item, spoken), miss, &item, &code);
+
+        self.record_answer(AnswerRecord {
+            item: item.clone(),
+            expected: expected.clone(),
+            answer: input.clone(),
+            correct,
+            response_time,
+            suspect: pasted,
+            ..Default::default()
+        });
+        Some(correct)
+    }
+
+    /// Adds an answer to the session, tagged with how hard and how new its item is, and
+    /// passes it to the answer hook.
+    fn record_answer(&mut self, mut answer: AnswerRecord) {
+        answer.difficulty = Some(novelty::difficulty(&answer.item));
+        answer.novelty = Some(self.tagger.novelty(&answer.item));
+        self.config.hooks.fire(HookEvent::Answer, json!({
+            "level": self.config.difficulty_level,
+            "item": answer.item,
+            "expected": answer.expected,
+            "answer": answer.answer,
+            "correct": answer.correct,
+            "response_time": answer.response_time,
+            "rhythm": answer.rhythm,
+        }));
+        if let Some(session) = self.stats.session_history.last_mut() {
+            session.answers.push(answer);
+        }
+    }
+
+    /// Reverse drill for small alphabets: the code is shown and the character is typed.
+    /// These answers count toward accuracy but not toward per-character speed.
+    fn decode_item(&mut self, item: &str, code: &str) -> Option<bool> {
+        let expected = morse::morse_to_char(code).map(|c| c.to_string()).unwrap_or_default();
+
+        self.heading("Decode");
+        println!("Code: {}", code);
+
+        print!("Your character: ");
+        io::stdout().flush().unwrap();
+
+        let start_time = Instant::now();
+        let line = self.read_typed()?;
+        let response_time = line.answered.duration_since(start_time).as_secs_f32();
+
+        let pasted = line.pasted;
+        let input = line.text.trim().to_uppercase();
+        let correct = input == expected;
+
+        self.total_answers += 1;
+        if correct {
+            self.correct_answers += 1;
+        }
+        let miss = format!("{} is {} (your: {})", code, expected, input);
+        self.verdict(correct, response_time, format!("{} {}", code, expected), miss, &expected, code);
+
+        self.record_answer(AnswerRecord {
+            item: item.to_string(),
+            expected: expected.clone(),
+            answer: input.clone(),
+            correct,
+            response_time,
+            suspect: pasted,
+            ..Default::default()
+        });
+
+        if !correct {
+            self.echo(&morse::encode_word(&input));
+        }
+        self.play(code);
+        Some(correct)
+    }
+
+    /// Receive drill for `--copy`: the item is only played, and what was heard is typed.
+    /// Like decoding, these answers count toward accuracy but not toward keying speed.
+    fn copy_item(&mut self, item: &str) -> Option<bool> {
+        let expected = match scheduler::decode_code(item).and_then(morse::morse_to_char) {
+            Some(c) => c.to_string(),
+            None => item.to_string(),
+        };
+        let morse_code = morse::encode_word(&expected);
+
+        // Copying by touch is the same drill, felt on the vibration device instead of heard.
+        let felt = self.options.output == Output::Vibration;
+        self.heading("Copy");
+        println!("{}", if felt { "Feel..." } else { "Listen..." });
+        if let Some(playback) = self.play(&morse_code) {
+            let _ = playback.join();
+        }
+
+        print!("What did you {}: ", if felt { "feel" } else { "hear" });
+        io::stdout().flush().unwrap();
+
+        let start_time = Instant::now();
+        let line = self.read_typed()?;
+        let response_time = line.answered.duration_since(start_time).as_secs_f32();
+
+        let pasted = line.pasted;
+        let input = line.text.trim().to_uppercase();
+        let correct = input == expected;
+
+        self.total_answers += 1;
+        if correct {
+            self.correct_answers += 1;
+        }
+        let miss = format!("It was {} ({}) (your: {})", expected, morse_code, input);
+        self.verdict(correct, response_time, format!("{} {}", expected, morse_code), miss, &expected, &morse_code);
+
+        self.record_answer(AnswerRecord {
+            item: item.to_string(),
+            expected: expected.clone(),
+            answer: input.clone(),
+            correct,
+            response_time,
+            suspect: pasted,
+            ..Default::default()
+        });
+
+        if !correct {
+            self.echo(&morse::encode_word(&input));
+            if let Some(playback) = self.play(&morse_code) {
+                let _ = playback.join();
+            }
+        }
+        Some(correct)
+    }
+
+    /// Echo drill for `--echo`: the item is played and keyed straight back, typed or on a
+    /// key through the microphone, and scored on the code and on how closely its rhythm
+    /// follows what was played. Like copying, it doesn't count toward keying speed.
+    fn echo_item(&mut self, item: &str) -> Option<bool> {
+        let expected = match scheduler::decode_code(item).and_then(morse::morse_to_char) {
+            Some(c) => c.to_string(),
+            None => item.to_string(),
+        };
+        let morse_code = morse::encode_word(&expected);
+
+        self.heading("Echo");
+        if let Some(playback) = self.play(&morse_code) {
+            let _ = playback.join();
+        }
+        print!("{}", if self.keyed() { "Key it back: " } else { "Type it back: " });
+        io::stdout().flush().unwrap();
+
+        let start_time = Instant::now();
+        let (input, onsets, pasted) = match self.keyed() {
+            true => {
+                let (code, events, _) = self.read_keying()?;
+                (code, keying::onsets(&events), false)
+            }
+            false => {
+                let line = self.read_typed()?;
+                let onsets = line.keyed_at.iter()
+                    .map(|at| at.duration_since(line.keyed_at[0]).as_secs_f32() * 1000.0)
+                    .collect();
+                (line.text, onsets, line.pasted)
+            }
+        };
+        let response_time = start_time.elapsed().as_secs_f32();
+        let input = input.split_whitespace().collect::<Vec<_>>().join(" ").to_uppercase();
+        if input == QUIT {
+            self.quit = true;
+            return None;
+        }
+
+        let correct = input == morse_code;
+        let ideal = timing::timings_with_spacing(&morse_code, self.spacing());
+        let rhythm = keying::rhythm(&onsets, &ideal, &self.config.key.norms()).filter(|_| correct && !pasted);
+
+        self.total_answers += 1;
+        if correct {
+            self.correct_answers += 1;
+        }
+        let miss = format!("It was {} (your: {})", morse_code, input);
+        self.verdict(correct, response_time, format!("{} {}", expected, morse_code), miss, &expected, &morse_code);
+        if let Some(rhythm) = rhythm {
+            println!("Rhythm: {:.0}%", rhythm);
+        }
+
+        self.record_answer(AnswerRecord {
+            item: item.to_string(),
+            expected: morse_code.clone(),
+            answer: input.clone(),
+            correct,
+            response_time,
+            suspect: pasted,
+            rhythm,
+            ..Default::default()
+        });
+
+        if !correct {
+            self.echo(&input);
+            if let Some(playback) = self.play(&morse_code) {
+                let _ = playback.join();
+            }
+        }
+        Some(correct)
+    }
+
+    /// Whether only the verdict is printed for each answer.
+    fn terse(&self) -> bool {
+        matches!(self.feedback, FeedbackStyle::Terse | FeedbackStyle::Echo)
+    }
+
+    /// Starts an exercise on a new line; unless feedback is terse, with a heading and the
+    /// exercises left.
+    fn heading(&self, title: &str) {
+        println!();
+        if !self.terse() {
+            println!("--- {} ---", title);
+            println!("{}", layout::join_fitting(&[
+                format!("Level: {}", self.config.difficulty_level),
+                format!("Exercises left: {}", self.practice_queue.len()),
+            ]));
+        }
+    }
+
+    /// Prints the verdict on an answer in the session's feedback style: terse feedback
+    /// shows just `right` on a miss, the others the whole `miss` line. Mnemonic feedback
+    /// then says how `item` sounds, and gives a word to remember it by. With answer sounds
+    /// on, a sound tells right from wrong too.
+    fn verdict(&self, correct: bool, response_time: f32, right: String, miss: String, item: &str, code: &str) {
+        match (self.terse(), correct) {
+            (true, true) => println!("✓"),
+            (true, false) => println!("✗ {}", right),
+            (false, true) => println!("✓ Correct! (time: {:.1}s)", response_time),
+            (false, false) => println!("✗ Incorrect! {}", miss),
+        }
+        if self.feedback == FeedbackStyle::Mnemonic {
+            println!("  {}", memory_aid(item, code));
+        }
+        if self.config.answer_sounds && self.options.output == Output::Audio {
+            morse_audio::play_cue(if correct { Cue::Correct } else { Cue::Incorrect });
+        }
+    }
+
+    /// With echo feedback or `replay_attempt`, plays back a missed answer and pauses, so
+    /// that it can be compared by ear with the right code played next.
+    fn echo(&self, answer_code: &str) {
+        if (self.feedback != FeedbackStyle::Echo && !self.config.replay_attempt) || answer_code.trim().is_empty() {
+            return;
+        }
+        println!("You sent, then the right code:");
+        if let Some(playback) = self.play(answer_code) {
+            let _ = playback.join();
+        }
+        thread::sleep(ECHO_PAUSE);
+    }
+
+    /// Timing from `--wpm` and the speed settings.
+    fn spacing(&self) -> Spacing {
+        self.config.spacing(self.options.wpm)
+    }
+
+    /// Sends `morse_code` to the chosen output. Audio and LED play in the background;
+    /// join the returned handle to wait for them.
+    fn play(&self, morse_code: &str) -> Option<JoinHandle<()>> {
+        let morse_audio = morse_code.to_string();
+        let spacing = self.spacing();
+        let pitch_hz = self.pitch_hz;
+        let tone = ToneSettings { pitch_hz, ..self.config.tone_settings() };
+        if let Some(keyer) = self.winkeyer.as_ref().filter(|_| self.options.output == Output::Audio) {
+            let keyer = Arc::clone(keyer);
+            return Some(thread::spawn(move || winkeyer::play(&keyer, &morse_audio, spacing)));
+        }
+        match self.options.output {
+            // Band conditions need the whole item rendered up front.
+            Output::Audio if tone.has_conditions() => Some(thread::spawn(move || {
+                play_rendered(&morse_audio, spacing, &tone)
+            })),
+            Output::Audio => Some(thread::spawn(move || play_morse_code_at(&morse_audio, spacing, pitch_hz))),
+            Output::Led => Some(thread::spawn(move || led::blink_morse_code(&morse_audio))),
+            Output::Vibration => {
+                let (port, unit_ms) = (self.config.vibration_port.clone().unwrap_or_default(), self.config.vibration_unit_ms());
+                Some(thread::spawn(move || vibration::vibrate_morse_code(&port, &morse_audio, unit_ms)))
+            }
+            // Terminal output shares the screen with the prompt, so finish before moving on.
+            Output::Bell => {
+                terminal::play_morse_code(&morse_audio, TerminalStyle::Bell, spacing);
+                None
+            }
+            Output::Text => {
+                terminal::play_morse_code(&morse_audio, TerminalStyle::Text, spacing);
+                None
+            }
+        }
+    }
+
+    fn session_settings(&self) -> SessionSettings {
+        let unit_ms = match self.options.output {
+            Output::Led => led::LED_UNIT_MS,
+            Output::Vibration => self.config.vibration_unit_ms(),
+            _ => self.spacing().unit_ms,
+        };
+        let (dot_ms, dash_ms) = (unit_ms.round() as u32, (3.0 * unit_ms).round() as u32);
+        SessionSettings {
+            output: cli::value_name(&self.options.output),
+            pitch_hz: self.config.pitch_hz(),
+            dot_ms,
+            dash_ms,
+        }
+    }
+
+    fn start_session(&mut self) {
+        if self.stages.is_empty() {
+            self.generate_practice_queue();
+        }
+        
+        println!("\nNew session started!");
+        println!("Difficulty level: {}", self.config.difficulty_level);
+        if self.stats.session_history.is_empty() && self.config.difficulty_level == 1 {
+            println!("Already know some Morse? Run `placement` to start at your level and speed.");
+        }
+        
+        if self.options.rehab {
+            println!("Mode: Rehab ({})", self.rehab.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(" "));
+        } else if !self.stages.is_empty() {
+            println!("Mode: Pipeline ({})", pipeline::describe(&self.stages));
+        } else if self.options.pseudo_words {
+            println!("Mode: Pseudo-word Practice (built from: {})", self.session_chars().iter().collect::<String>());
+        } else if let Some(cover) = &self.options.cover {
+            println!("Mode: Coverage Practice ({} at least {} times each)", cover.to_uppercase(), self.options.min_count);
+        } else if let Some(drill) = self.options.drill {
+            println!("Mode: {} Drill", match drill {
+                Drill::Grids => "Grid Square",
+                Drill::Frequencies => "Frequency",
+            });
+        } else if self.options.logbook {
+            println!("Mode: Logbook Practice (calls, names and exchanges you've worked)");
+        } else if self.is_word_level {
+            println!("Mode: Word Practice (10 common words)");
+        } else {
+            println!("Characters to learn: {}", self.session_chars().iter().collect::<String>());
+        }
+        if let Some(subset) = self.options.subset {
+            println!("Subset: {} only", cli::value_name(&subset));
+        }
+        if self.options.copy && self.options.output == Output::Vibration {
+            println!("Direction: copy by touch (feel each item and type what it was, at {:.0} WPM)",
+                1200.0 / self.config.vibration_unit_ms());
+        } else if self.options.copy {
+            println!("Direction: copy (listen and type what you hear)");
+        }
+        if self.options.verbal {
+            println!("Direction: spoken form (say each item as dits and dahs, or name it)");
+        }
+        if self.options.echo {
+            println!("Direction: echo (key back what you hear; items follow on their own, answer q to stop)");
+        }
+        if self.options.random_pitch {
+            let range = self.config.pitch_range;
+            println!("Pitch: random, {:.0} to {:.0} Hz", range.min_hz, range.max_hz);
+        }
+        
+        if self.stages.is_empty() {
+            println!("Exercise number: {}", self.practice_queue.len());
+        }
+        if matches!(self.options.output, Output::Audio | Output::Bell) {
+            println!("Between items, + and - change the volume and m mutes.");
+        }
+        println!("{}", layout::rule('-'));
+
+        self.session_start = Instant::now();
+        self.stats.session_history.push(LearningSession {
+            timestamp: chrono::Local::now().fixed_offset(),
+            duration: 0,
+            chars_practiced: vec![],
+            words_practiced: vec![],
+            accuracy: 0.0,
+            difficulty: self.config.difficulty_level,
+            answers: vec![],
+            settings: Some(self.session_settings()),
+            external: None,
+            head_copy: vec![],
+            passive: false,
+            sending: None,
+            ending: None,
+            outside_progression: !self.options.counts_toward_progression(),
+        });
+
+        self.correct_answers = 0;
+        self.total_answers = 0;
+
+        self.config.hooks.fire(HookEvent::SessionStart, json!({
+            "timestamp": self.stats.session_history.last().map(|s| s.timestamp),
+            "level": self.config.difficulty_level,
+            "word_level": self.is_word_level,
+            "items": self.practice_queue.iter().collect::<Vec<_>>(),
+        }));
+    }
+
+    fn run(&mut self) {
+        if let Some(spec) = &self.options.pipeline {
+            match pipeline::resolve(spec, &self.config.pipelines) {
+                Ok(stages) => self.stages = stages,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    return;
+                }
+            }
+        }
+        let _paste_detection = morse_platform::terminal::BracketedPaste::enable();
+        if self.options.mic {
+            match Capture::open() {
+                Ok(capture) => {
+                    self.capture = Some(capture);
+                    if self.config.record_keying {
+                        self.recording = Some(KeyingRecording::default());
+                    }
+                }
+                Err(e) => println!("Can't listen to the microphone ({}), type your answers instead.", e),
+            }
+        }
+        if let Some(port) = &self.options.winkeyer {
+            let spacing = self.spacing();
+            match winkeyer::open(port, spacing, self.config.keyer.settings()) {
+                Ok(keyer) => {
+                    println!("WinKeyer version {} ready: items play on its sidetone, key your answers on its paddles.", keyer.version);
+                    self.winkeyer = Some(Arc::new(Mutex::new(keyer)));
+                }
+                Err(e) => {
+                    println!("Can't use the WinKeyer on {} ({}), type your answers instead.", port, e);
+                    if self.options.output == Output::Audio && !audio_available() {
+                        println!("No audio device found either, showing the code as text instead.");
+                        self.options.output = Output::Text;
+                    }
+                }
+            }
+        }
+        if let Some(name) = &self.options.midi {
+            match midi::open(name) {
+                Ok(keys) => {
+                    println!("Keying on {}: hold any note or pedal as the key.", keys.name);
+                    self.midi = Some(keys);
+                    if self.config.record_keying {
+                        self.recording = Some(KeyingRecording::default());
+                    }
+                }
+                Err(e) => println!("Can't key on MIDI ({}), type your answers instead.", e),
+            }
+        }
+        if let Some(port) = &self.options.serial {
+            match serial_key::open(port) {
+                Ok(key) => {
+                    self.serial_key = Some(key);
+                    if self.config.record_keying {
+                        self.recording = Some(KeyingRecording::default());
+                    }
+                }
+                Err(e) => println!("Can't read the key on {} ({}), type your answers instead.", port, e),
+            }
+        }
+        if self.options.mouse && self.capture.is_none() {
+            match mouse::available() {
+                Ok(()) => {
+                    println!("Keying with the mouse: {}.", match self.options.paddles {
+                        true => "left button for dits, right button for dahs",
+                        false => "hold a button as the key",
+                    });
+                    self.mouse = true;
+                    if self.config.record_keying {
+                        self.recording = Some(KeyingRecording::default());
+                    }
+                }
+                Err(e) => println!("Can't key with the mouse ({}), type your answers instead.", e),
+            }
+        }
+        let keyboard_key = match (self.options.spacebar, self.options.paddles) {
+            (true, _) => Some("the spacebar"),
+            (_, true) => Some("paddle keys"),
+            _ => None,
+        };
+        if let Some(name) = keyboard_key.filter(|_| self.capture.is_none() && self.options.serial.is_none() && self.options.midi.is_none() && !self.options.mouse) {
+            match spacebar::available() {
+                Ok(()) => {
+                    self.spacebar = self.options.spacebar;
+                    self.paddles = self.options.paddles;
+                    if self.config.record_keying {
+                        self.recording = Some(KeyingRecording::default());
+                    }
+                }
+                Err(e) => println!("Can't key with {} ({}), type your answers instead.", name, e),
+            }
+        }
+        // Keys on the keyboard, the mouse, a serial port or MIDI are keyed by ear, so they always get a sidetone.
+        if (self.config.keyed_sidetone || self.serial_key.is_some() || self.midi.is_some() || self.spacebar || self.paddles || self.mouse) && self.capture.is_none() && morse_platform::terminal::is_interactive() && audio_available() {
+            let unit_ms = self.spacing().unit_ms;
+            self.sidetone = Sidetone::open(self.config.pitch_hz(), unit_ms);
+        }
+        if self.options.rehab && self.rehab.is_empty() {
+            println!("Nothing needs rehab. Items go there after {} hints.", rehab::HINTS_TO_REHAB);
+            return;
+        }
+        self.start_session();
+        if self.options.rehab {
+            self.study_rehab();
+        }
+        if self.stages.is_empty() {
+            self.drill(self.config.session_duration);
+        } else {
+            self.run_pipeline();
+        }
+        if self.options.rehab {
+            let before = std::mem::replace(&mut self.rehab, rehab_items(&self.stats));
+            let relearned: Vec<&String> = before.difference(&self.rehab).collect();
+            if !relearned.is_empty() {
+                println!("\nBack in the normal queues: {}", relearned.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(" "));
+            }
+            if !self.rehab.is_empty() {
+                println!("Still in rehab: {}", self.rehab.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(" "));
+            }
+        }
+        self.end_session();
+    }
+
+    /// Works through the queue for up to `minutes`, returning false if the learner quit.
+    fn drill(&mut self, minutes: u32) -> bool {
+        let start = Instant::now();
+        while let Some(current_item) = self.practice_queue.next_item().map(|s| s.to_string()) {
+            if start.elapsed().as_secs() > minutes as u64 * 60 
+            {
+                println!("\n⏰ Time passed!");
+                self.end_reason = Some((EndReason::Timeout, None));
+                return true;
+            }
+            self.reload_config(false);
+            
+            let Some(correct) = self.practice_item(&current_item) else {
+                match self.quit {
+                    true => println!("\nSession interrupted"),
+                    false => println!("\nInput closed, ending the session."),
+                }
+                let reason = if self.quit { EndReason::Quit } else { EndReason::Closed };
+                self.end_reason = Some((reason, Some(current_item)));
+                return false;
+            };
+            // An item just sent to rehab leaves the queue instead of coming round again.
+            let retired = !self.options.rehab && self.rehab.contains(&current_item);
+            self.practice_queue.record(correct || retired);
+            if !correct && !self.missed.contains(&current_item) {
+                self.missed.push(current_item);
+            }
+            self.show_status();
+            if self.options.echo {
+                thread::sleep(ECHO_ROUND_PAUSE);
+                continue;
+            }
+            
+            loop {
+                print!("Press 'q' to quit or Enter to continue: ");
+                io::stdout().flush().unwrap();
+
+                let Some(key) = input::read_key().map(|k| k.to_ascii_lowercase()) else {
+                    println!("\nInput closed, ending the session.");
+                    self.end_reason = Some((EndReason::Closed, None));
+                    return false;
+                };
+
+                match key {
+                    'q' => {
+                        println!("\nSession interrupted");
+                        self.end_reason = Some((EndReason::Quit, None));
+                        return false;
+                    }
+                    '+' | '-' | 'm' => self.adjust_volume(key),
+                    'r' => self.reload_config(true),
+                    _ => break,
+                }
+            }
+        }
+        self.end_reason = Some((EndReason::Completed, None));
+        true
+    }
+
+    fn run_pipeline(&mut self) {
+        for (i, stage) in self.stages.clone().iter().enumerate() {
+            self.practice_queue = self.stage_queue(stage);
+            self.practice_queue.remove_all(&self.rehab);
+            if self.practice_queue.is_empty() {
+                println!("\nSkipping the {} stage, there is nothing to practice in it yet.", stage.kind);
+                continue;
+            }
+            println!("\n=== Stage {}/{}: {} ({} min, {} exercises) ===",
+                i + 1, self.stages.len(), stage.kind, stage.minutes, self.practice_queue.len());
+
+            let (correct, total, start) = (self.correct_answers, self.total_answers, Instant::now());
+            let finished = self.drill(stage.minutes);
+            self.stage_results.push(StageResult {
+                stage: *stage,
+                correct: self.correct_answers - correct,
+                total: self.total_answers - total,
+                seconds: start.elapsed().as_secs() as u32,
+            });
+            if !finished {
+                break;
+            }
+        }
+    }
+
+    /// Handles a volume hotkey: `+` and `-` step the volume, `m` toggles muting. The
+    /// change is kept for later sessions.
+    fn adjust_volume(&mut self, key: char) {
+        let volume = self.config.volume();
+        let (volume, muted) = match key {
+            '+' => (volume.saturating_add(VOLUME_STEP).min(100), false),
+            '-' => (volume.saturating_sub(VOLUME_STEP), false),
+            _ => (volume, !self.config.muted),
+        };
+        self.config.volume = Some(volume);
+        self.config.muted = muted;
+        morse_audio::set_volume(self.config.output_volume());
+        match self.config.muted {
+            true => println!("Muted."),
+            false => println!("Volume: {}%", self.config.volume()),
+        }
+        if let Err(e) = self.config.save() {
+            eprintln!("Error saving configuration: {}", e);
+        }
+    }
+
+    /// Applies sound and feedback settings edited in the config file since the last item;
+    /// with `force`, reads the file even if it looks unchanged.
+    fn reload_config(&mut self, force: bool) {
+        let edited = match force {
+            true => reload::load(),
+            false => self.config_watch.changed(),
+        };
+        let Some(edited) = edited else {
+            return;
+        };
+        let pitch_hz = self.config.pitch_hz;
+        let changes = reload::apply_safe(&mut self.config, &edited);
+        if changes.is_empty() {
+            if force {
+                println!("No sound or feedback settings have changed.");
+            }
+            return;
+        }
+        self.config.apply_audio_settings();
+        if self.config.pitch_hz != pitch_hz && !self.options.random_pitch {
+            self.pitch_hz = self.config.pitch_hz();
+            if self.sidetone.take().is_some() {
+                let unit_ms = self.spacing().unit_ms;
+                self.sidetone = Sidetone::open(self.pitch_hz, unit_ms);
+            }
+        }
+        println!("Settings reloaded: {}.", changes.join(", "));
+    }
+
+    /// One line on how the session is going: accuracy, average response time, and when the
+    /// session counts toward progression, how it compares with the level requirements.
+    fn show_status(&self) {
+        let accuracy = self.correct_answers as f32 / self.total_answers.max(1) as f32;
+        let times: Vec<f32> = self.stats.session_history.last()
+            .map(|s| s.answers.iter().filter(|a| !a.suspect && !a.hinted).map(|a| a.response_time).collect())
+            .unwrap_or_default();
+        let average = match times.len() {
+            0 => None,
+            n => Some(format!("avg {:.1}s", times.iter().sum::<f32>() / n as f32)),
+        };
+        let counts = self.options.counts_toward_progression() && !self.is_word_level;
+        let level = self.progression.levels.iter()
+            .find(|l| l.level == self.config.difficulty_level)
+            .filter(|_| counts);
+        let Some(level) = level else {
+            let so_far = format!("So far: {}/{} correct ({:.1}%)", self.correct_answers, self.total_answers, accuracy * 100.0);
+            println!("{}", layout::join_fitting(&[so_far].into_iter().chain(average).collect::<Vec<_>>()));
+            return;
+        };
+        let speeds = self.progression.speed_check(self.config.difficulty_level, &self.stats.response_times);
+        let on_target = speeds.iter().filter(|s| s.passed()).count();
+        let mut parts = vec![format!("So far: {}/{} correct ({:.1}%, need {:.0}%)",
+            self.correct_answers, self.total_answers, accuracy * 100.0, level.accuracy_requirement * 100.0)];
+        parts.extend(average);
+        parts.push(format!("{} of {} characters within target speed", on_target, speeds.len()));
+        println!("{}", layout::join_fitting(&parts));
+    }
+
+    fn show_summary(&self) {
+        let duration = self.session_start.elapsed().as_secs() as u32;
+        let minutes = duration / 60;
+        let seconds = duration % 60;
+        let accuracy = if self.total_answers > 0 {
+            (self.correct_answers as f32 / self.total_answers as f32) * 100.0
+        } else {
+            0.0
+        };
+
+        if self.terse() {
+            println!("\nSession: {:02}:{:02}, {}/{} correct ({:.1}%)",
+                minutes, seconds, self.correct_answers, self.total_answers, accuracy);
+            return;
+        }
+        
+        println!("\n{}", layout::banner("SESSION SUMMARY", "SUMMARY"));
+        println!("Duration:      {:02}:{:02}", minutes, seconds);
+        println!("Exercise number:    {}", self.total_answers);
+        println!("Correct answers: {}/{} ({:.1}%)", 
+            self.correct_answers, self.total_answers, accuracy);
+        self.show_baseline();
+        let rhythms: Vec<f32> = self.stats.session_history.last()
+            .map(|s| s.answers.iter().filter_map(|a| a.rhythm).collect())
+            .unwrap_or_default();
+        if !rhythms.is_empty() {
+            println!("Rhythm:          {:.0}% on average", rhythms.iter().sum::<f32>() / rhythms.len() as f32);
+        }
+        let answers = self.stats.session_history.last().map(|s| s.answers.as_slice()).unwrap_or_default();
+        let by_novelty: Vec<(&str, usize, usize)> = [
+            (Novelty::New, "New material:"),
+            (Novelty::RetentionCheck, "Retention checks:"),
+            (Novelty::Review, "Review:"),
+        ].into_iter()
+            .map(|(novelty, label)| {
+                let tagged: Vec<_> = answers.iter().filter(|a| a.novelty == Some(novelty)).collect();
+                (label, tagged.iter().filter(|a| a.correct).count(), tagged.len())
+            })
+            .filter(|(_, _, total)| *total > 0)
+            .collect();
+        if by_novelty.len() > 1 {
+            for (label, correct, total) in by_novelty {
+                println!("  {:<18} {}/{} ({:.1}%)", label, correct, total, correct as f32 / total as f32 * 100.0);
+            }
+        }
+        if let Some(keyed) = self.stats.session_history.last().and_then(|s| s.sending.as_ref()) {
+            println!("Sending:");
+            for line in sending::report(keyed, &self.config.key.norms()) {
+                println!("{}", line);
+            }
+        }
+        println!("Difficulty:  {}", self.config.difficulty_level);
+
+        if !self.stage_results.is_empty() {
+            println!("\nStages:");
+            for result in &self.stage_results {
+                let accuracy = if result.total > 0 { result.correct as f32 / result.total as f32 * 100.0 } else { 0.0 };
+                println!("  {:<7} {:02}:{:02}  {}/{} ({:.1}%)",
+                    result.stage.kind, result.seconds / 60, result.seconds % 60, result.correct, result.total, accuracy);
+            }
+        }
+
+        if self.is_word_level {
+            if !self.stats.word_response_times.is_empty() {
+                println!("\nWord statistics:");
+                for (word, time) in &self.stats.word_response_times {
+                    println!("  {}: {:.1}s", word, time);
+                }
+                
+                let avg_time: f32 = self.stats.word_response_times.values().sum::<f32>() / 
+                                   self.stats.word_response_times.len() as f32;
+                println!("Average reaction time: {:.1}s", avg_time);
+            }
+        } else {
+            if !self.stats.response_times.is_empty() {
+                println!("\nCharacter statistics:");
+                for (c, time) in &self.stats.response_times {
+                    println!("  {}: {:.1}s", c, time);
+                }
+                
+                let avg_time: f32 = self.stats.response_times.values().sum::<f32>() / 
+                                   self.stats.response_times.len() as f32;
+                println!("Average reaction time: {:.1}s", avg_time);
+            }
+        }
+
+        if self.feedback == FeedbackStyle::Mnemonic && !self.missed.is_empty() {
+            println!("\nTo remember:");
+            for item in &self.missed {
+                let item = match scheduler::decode_code(item) {
+                    Some(code) => morse::morse_to_char(code).map(|c| c.to_string()).unwrap_or_default(),
+                    None => item.clone(),
+                };
+                println!("  {}", memory_aid(&item, &morse::encode_word(&item)));
+            }
+        }
+        
+        println!("{}", layout::rule('='));
+    }
+
+    /// How this session's accuracy and response time compare with the sessions before it.
+    fn show_baseline(&self) {
+        let history = &self.stats.session_history;
+        let (Some(session), Some(baseline)) = (history.last(), self.stats.baseline(history.len().saturating_sub(1))) else {
+            return;
+        };
+        if session.answers.is_empty() {
+            return;
+        }
+        let change = session.accuracy - baseline.accuracy;
+        let arrow = if change.abs() < BASELINE_ACCURACY_STEP { "=" } else if change > 0.0 { "▲" } else { "▼" };
+        let mut line = format!("vs. your last {} session(s): accuracy {} {:+.1} pts (usual {:.1}%)",
+            baseline.sessions, arrow, change * 100.0, baseline.accuracy * 100.0);
+        if let (Some(time), Some(usual)) = (session.mean_response_time(), baseline.response_time) {
+            let change = time - usual;
+            let (arrow, detail) = match change {
+                c if c.abs() < BASELINE_TIME_STEP => ("=", "as usual".to_string()),
+                c if c < 0.0 => ("▲", format!("{:.1}s faster", -c)),
+                c => ("▼", format!("{:.1}s slower", c)),
+            };
+            line.push_str(&format!(" | speed {} {} (usual {:.1}s)", arrow, detail, usual));
+        }
+        println!("{}", line);
+    }
+
+    /// Counts a session toward the probation of the last promotion, reverting the promotion
+    /// if accuracy collapsed.
+    fn check_probation(&mut self, accuracy: f32) {
+        match probation::settle(&mut self.config, accuracy, self.total_answers) {
+            Some(ProbationOutcome::Reverted { from }) => {
+                self.is_word_level = self.config.difficulty_level >= 9;
+                println!("\n↩️ Accuracy dropped to {:.1}% on probation, so level {} is reverted to level {} to consolidate.",
+                    accuracy * 100.0, from, self.config.difficulty_level);
+                self.generate_practice_queue();
+            }
+            Some(ProbationOutcome::Passed) => {
+                println!("\n✅ Probation passed, level {} is yours.", self.config.difficulty_level);
+            }
+            Some(ProbationOutcome::Continues { sessions_left }) => {
+                println!("\nProbation on level {}: {} more session(s) to go.",
+                    self.config.difficulty_level, sessions_left);
+            }
+            None => {
+                println!("\nNothing was answered, so probation on level {} is unchanged.", self.config.difficulty_level);
+            }
+        }
+    }
+
+    fn update_progression(&mut self) {
+        let current_level = self.config.difficulty_level;
+
+        if !self.options.counts_toward_progression() {
+            let kind = if self.options.is_custom_material() {
+                "Sessions on generated material"
+            } else if self.options.copy {
+                "Copy sessions"
+            } else if self.options.verbal {
+                "Spoken-form sessions"
+            } else if self.options.echo {
+                "Echo sessions"
+            } else {
+                "These sessions"
+            };
+            println!("\n{} don't count toward level progression.", kind);
+            return;
+        }
+        
+        let accuracy = if self.total_answers > 0 {
+            self.correct_answers as f32 / self.total_answers as f32
+        } else {
+            0.0
+        };
+
+        if self.config.probation.is_some() {
+            self.check_probation(accuracy);
+            if let Err(e) = self.config.save() {
+                eprintln!("Error saving configuration: {}", e);
+            }
+            return;
+        }
+
+        if self.is_word_level {
+            println!("\nCongrats! You're practicing words!");
+            println!("Continue to improve your word encoding speed.");
+            return;
+        }
+        
+        if let Some(level) = self.progression.levels.iter().find(|l| l.level == current_level) {
+            let speeds = self.progression.speed_check(current_level, &self.stats.response_times);
+            let slow: Vec<_> = speeds.iter().filter(|s| !s.passed()).collect();
+            
+            println!("\nLevel requirements {}:", current_level);
+            println!("- Accuracy: {:.1}% (required: {:.1}%)", 
+                accuracy * 100.0, level.accuracy_requirement * 100.0);
+
+            println!("- Speed: {} of {} characters within their targets (new characters: {:.1}s, others: {:.1}s)",
+                speeds.len() - slow.len(), speeds.len(),
+                level.speed_requirement * level.new_char_slack, level.speed_requirement);
+            for s in &slow {
+                match s.time {
+                    Some(time) => println!("    {}: {:.1}s (target: {:.1}s)", s.ch, time, s.target),
+                    None => println!("    {}: not practiced yet (target: {:.1}s)", s.ch, s.target),
+                }
+            }
+
+            if slow.is_empty() && accuracy >= level.accuracy_requirement {
+                self.config.probation = Some(Probation::new(
+                    current_level,
+                    self.config.known_chars.clone(),
+                    level.accuracy_requirement,
+                ));
+                self.config.difficulty_level += 1;
+                println!("\n🎉 Advanced to level {}!", self.config.difficulty_level);
+                println!("The next {} sessions are on probation: if they go badly, you'll return to level {}.",
+                    PROBATION_SESSIONS, current_level);
+                self.notify("Level up", &format!("Advanced to level {}", self.config.difficulty_level));
+                let known_before = self.config.known_chars.len();
+                
+                if self.config.difficulty_level == 9 {
+                    self.is_word_level = true;
+                    println!("🌟 CONGRATULATIONS! You've reached word level!");
+                    println!("Now you'll practice encoding common words.");
+                } else {
+                    if let Some(next_level) = self.progression.levels.iter()
+                        .find(|l| l.level == self.config.difficulty_level) 
+                    {
+                        for c in &next_level.chars_to_learn {
+                            if !self.config.known_chars.contains(c) {
+                                self.config.known_chars.push(*c);
+                                println!("+ New char added: {}", c);
+                            }
+                        }
+                    }
+                }
+
+                self.config.hooks.fire(HookEvent::LevelUp, json!({
+                    "from": current_level,
+                    "to": self.config.difficulty_level,
+                    "word_level": self.is_word_level,
+                    "new_chars": self.config.known_chars[known_before..],
+                    "accuracy": accuracy,
+                    "average_time": speeds.iter().filter_map(|s| s.time).sum::<f32>() / speeds.len().max(1) as f32,
+                    "response_times": speeds.iter().filter_map(|s| s.time.map(|t| (s.ch, t))).collect::<BTreeMap<_, _>>(),
+                }));
+                
+                self.generate_practice_queue();
+            } else {
+                println!("\nℹ️ Continue practicing on current level.");
+            }
+
+            if let Err(e) = self.config.save() {
+                eprintln!("Error saving configuration: {}", e);
+            }
+
+            if let Some(outlook) = estimate::summary(&estimate::remaining(&self.stats, &self.progression, &self.config)) {
+                println!("{}", outlook);
+            }
+            match self.config.streak.streak(&self.stats) {
+                Ok(streak) => if let Some(line) = streak::summary(&streak) {
+                    println!("{}", line);
+                },
+                Err(e) => eprintln!("Error counting the streak: {}", e),
+            }
+        }
+    }
+}
+
+/// How `item` sounds, with a word to remember it by when it is a letter.
+fn memory_aid(item: &str, code: &str) -> String {
+    let spoken = verbal::verbalize(code);
+    let mut chars = item.chars();
+    match (chars.next().and_then(verbal::mnemonic), chars.next()) {
+        (Some(word), None) => format!("{} sounds like {}, think {}", item, spoken, word),
+        _ => format!("{} sounds like {}", item, spoken),
+    }
+}
+
+fn rehab_items(stats: &UserStats) -> BTreeSet<String> {
+    rehab::in_rehab(stats.session_history.iter()
+        .flat_map(|s| &s.answers)
+        .map(|a| (a.item.as_str(), a.correct, a.hinted)))
+}
+
+fn report_stats_recovery(recovery: &StatsRecovery) {
+    println!("\n⚠️ Your stats file could not be read: {}", recovery.error);
+    println!("The damaged file was moved to {}", recovery.quarantined_to.display());
+    println!("Recovered {} of {} sessions from it.",
+        recovery.recovered_sessions,
+        recovery.recovered_sessions + recovery.lost_sessions
+    );
+    if let Some(backup) = &recovery.backup {
+        println!("A backup from your last successful save is at {}.", backup.display());
+        println!("To restore it instead, quit now and copy it over {}.", UserStats::stats_path().display());
+    }
+}
+
+fn practice(options: PracticeArgs) {
+    if options.pseudo_words && options.subset.is_some_and(|s| s != Subset::Letters) {
+        eprintln!("Error: pseudo-words are made of letters and can't be limited to another subset");
+        return;
+    }
+    if options.logbook && Logbook::load().is_ok_and(|l| l.is_empty()) {
+        eprintln!("Error: your logbook is empty; add a log with `import adif <file>` first");
+        return;
+    }
+    println!("{}", layout::banner("MORSE CODE LEARNER", "MORSE"));
+    println!("Progression system:");
+    println!("- Levels 1-8: Character encoding");
+    println!("- Level 9: Word encoding");
+    println!("{}", layout::rule('='));
+    
+    let mut app = MorseTutor::new(options);
+    app.run();
+}
+
+/// The items of a session as `practice` would generate it, for rendering to audio.
+fn session_items(template: Option<String>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut options = template::resolve(PracticeArgs { template, ..PracticeArgs::default() })?;
+    if options.pipeline.is_some() {
+        return Err("pipeline sessions can't be rendered to audio".into());
+    }
+    options.output = Output::Text;
+    let mut app = MorseTutor::new(options);
+    app.generate_practice_queue();
+    Ok(app.practice_queue.iter()
+        .map(|item| match scheduler::decode_code(item).and_then(morse::morse_to_char) {
+            Some(c) => c.to_string(),
+            None => item.clone(),
+        })
+        .collect())
+}
+
+fn main() {
+    let cli = Cli::parse();
+    AppConfig::load().unwrap_or_default().apply_audio_settings();
+    let result = match cli.command {
+        None => {
+            practice(suggest::offer().unwrap_or_default());
+            Ok(())
+        }
+        Some(Command::Practice(options)) => template::resolve(options).map(practice),
+        Some(Command::Encode { text, clipboard }) => {
+            convert::encode(ConvertArgs { input: text, clipboard })
+        }
+        Some(Command::Decode { code, clipboard }) => {
+            convert::decode(ConvertArgs { input: code, clipboard })
+        }
+        Some(Command::DecodeAudio { file, pitch, wpm }) => decode_audio::run(&file, pitch, wpm),
+        Some(Command::Analyze { code, timings, timings_file, expected, key }) => {
+            let input = match (code, timings, timings_file) {
+                (_, Some(trace), _) => AnalyzeInput::Timings(trace),
+                (_, _, Some(path)) => AnalyzeInput::TimingsFile(path),
+                (code, _, _) => AnalyzeInput::Code(code.unwrap_or_default()),
+            };
+            let key = key.unwrap_or_else(|| AppConfig::load().unwrap_or_default().key);
+            analyze::run(input, expected.as_deref(), key)
+        }
+        Some(Command::Generate { chars, min_count, known_only }) => {
+            generate::run(chars, min_count, known_only)
+        }
+        Some(Command::Worksheet { level, out, groups, words }) => {
+            worksheet::run(WorksheetOptions { level, groups, words }, &out)
+        }
+        Some(Command::Bundle { action: BundleAction::Export { level, wpm, pitch, drills, items, pause_ms, out } }) => {
+            bundle::export(BundleOptions { level, wpm, pitch_hz: pitch, drills, items, pause_ms }, &out)
+        }
+        Some(Command::Share { session, out }) => share::run(session.as_deref(), out.as_deref()),
+        Some(Command::Stats { action: StatsAction::Compare { from, to } }) => compare::run(&from, &to),
+        Some(Command::Stats { action: StatsAction::Endings }) => endings::run(),
+        Some(Command::Stats { action: StatsAction::Estimate }) => estimate::run(),
+        Some(Command::Stats { action: StatsAction::Streak }) => streak::run(),
+        Some(Command::Stats { action: StatsAction::Strength }) => strength::run(),
+        Some(Command::Stats { action: StatsAction::Time }) => practice_time::run(),
+        Some(Command::Goal { action: GoalAction::Add { kind, target, priority } }) => goals::add(kind, &target, priority),
+        Some(Command::Goal { action: GoalAction::List }) => goals::list(),
+        Some(Command::Goal { action: GoalAction::Remove { number } }) => goals::remove(number),
+        Some(Command::Sync { action: SyncAction::Push { server } }) => sync::push(server),
+        Some(Command::Sync { action: SyncAction::Pull { server } }) => sync::pull(server),
+        Some(Command::Config { action: ConfigAction::Ban { items } }) => ban::ban(&items),
+        Some(Command::Config { action: ConfigAction::Unban { items } }) => ban::unban(&items),
+        Some(Command::Config { action: ConfigAction::Envelope { ms } }) => config::envelope(ms),
+        Some(Command::Config { action: ConfigAction::Device { name } }) => config::device(name),
+        Some(Command::Config { action: ConfigAction::Vibration { port } }) => config::vibration(port),
+        Some(Command::Config { action: ConfigAction::Streak { timezone, day_starts_at } }) => streak::configure(timezone, day_starts_at),
+        Some(Command::Config { action: ConfigAction::Volume { percent } }) => config::volume(percent),
+        Some(Command::Config { action: ConfigAction::Mute { muted } }) => config::mute(muted),
+        Some(Command::Config { action: ConfigAction::Pitch { hz } }) => config::pitch(hz),
+        Some(Command::Config { action: ConfigAction::PitchRange { min_hz, max_hz } }) => config::pitch_range(min_hz, max_hz),
+        Some(Command::Config { action: ConfigAction::Noise { snr_db, off } }) => config::noise(snr_db, off),
+        Some(Command::Config { action: ConfigAction::Qsb { depth, period, off } }) => config::qsb(depth, period, off),
+        Some(Command::Config { action: ConfigAction::Qrm { offset, level, off } }) => config::qrm(offset, level, off),
+        Some(Command::Config { action: ConfigAction::Stereo { ear, left, right, off } }) => config::stereo(ear, left, right, off),
+        Some(Command::Config { action: ConfigAction::Fist { elements, spacing, off } }) => config::fist(elements, spacing, off),
+        Some(Command::Config { action: ConfigAction::Keyer { mode, reverse } }) => keyer::configure(mode, reverse),
+        Some(Command::Config { action: ConfigAction::Feedback { style } }) => config::feedback(style),
+        Some(Command::Config { action: ConfigAction::Locale { name } }) => config::locale(name),
+        Some(Command::Config { action: ConfigAction::Sidetone { enabled } }) => sidetone::configure(enabled),
+        Some(Command::Config { action: ConfigAction::AnswerSounds { enabled } }) => config::answer_sounds(enabled),
+        Some(Command::Config { action: ConfigAction::ReplayAttempt { enabled } }) => config::replay_attempt(enabled),
+        Some(Command::Config { action: ConfigAction::Metronome { enabled } }) => config::metronome(enabled),
+        Some(Command::Config { action: ConfigAction::Notifications { enabled } }) => config::notifications(enabled),
+        Some(Command::Config { action: ConfigAction::RecordKeying { enabled } }) => config::record_keying(enabled),
+        Some(Command::Config { action: ConfigAction::Templates }) => template::list(),
+        Some(Command::Config { action: ConfigAction::RemoveTemplate { name } }) => template::remove(&name),
+        Some(Command::Import { action: ImportAction::Results { files, source } }) => import::results(&files, source),
+        Some(Command::Import { action: ImportAction::Adif { file } }) => import::adif(&file),
+        Some(Command::Export { target: ExportTarget::Audio { text, template, pause_ms, split, format, out } }) => {
+            let config = AppConfig::load().unwrap_or_default();
+            let (items, title) = match text {
+                Some(text) => (Ok(export_audio::text_items(&text, split)), text),
+                None => (session_items(template), export_audio::lesson_title(&config.progression(), config.difficulty_level)),
+            };
+            items.and_then(|items| export_audio::write(&items, pause_ms, split, format.map(cli::AudioFormat::encoding), &title, &out))
+        }
+        Some(Command::Export { target: ExportTarget::Course { format, items, pause_ms, out } }) => {
+            export_audio::course(format.encoding(), items, pause_ms, &out)
+        }
+        Some(Command::Net { list: true, .. }) => net::list(),
+        Some(Command::Net { scenario, call, rules, output, .. }) => net::run(scenario.as_deref(), call.as_deref(), rules.as_deref(), output),
+        Some(Command::Exam { list: true, .. }) => {
+            exam::list();
+            Ok(())
+        }
+        Some(Command::Exam { preset, rules, output, .. }) => exam::run(&preset.unwrap_or_default(), rules.as_deref(), output),
+        Some(Command::Radiogram { count, wpm, output }) => radiogram::drill(count, wpm, output),
+        Some(Command::Speedtest { history: true, .. }) => speedtest::history(),
+        Some(Command::Speedtest { wpm, rules, output, .. }) => speedtest::run(wpm.unwrap_or(DEFAULT_WPM), rules.as_deref(), output),
+        Some(Command::HeadCopy { passages, words, sentences, wpm, output }) => head_copy::run(passages, words, sentences, wpm, output),
+        Some(Command::Placement { output }) => placement::run(output),
+        Some(Command::Listen { minutes, delay, wpm }) => listen::run(minutes, delay, wpm),
+        Some(Command::SoundCheck) => sound_check::run(),
+        Some(Command::VibrationCheck) => vibration_check::run(),
+        Some(Command::Broadcast { text, template, bind, pause_ms, repeat }) => {
+            let items = match text {
+                Some(text) => Ok(export_audio::text_items(&text, false)),
+                None => session_items(template),
+            };
+            items.and_then(|items| broadcast::run(&items, &bind, pause_ms, repeat))
+        }
+        Some(Command::Export { target: ExportTarget::Research { out, yes } }) => {
+            research::export(&out, yes)
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
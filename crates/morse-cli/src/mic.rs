@@ -0,0 +1,51 @@
+use std::time::Instant;
+use morse_audio::capture::Capture;
+use morse_core::timing::{self, TimingDecoder, TimingEvent};
+use crate::spacebar;
+
+/// The dot length to decode with: when the answer has both dots and dashes it is
+/// measured from them, otherwise `unit_ms` (what the last answers were keyed at) is used.
+fn starting_unit(events: &[TimingEvent], unit_ms: f32) -> f32 {
+    let marks = events.iter().filter_map(|e| match e {
+        TimingEvent::Mark(ms) => Some(*ms),
+        TimingEvent::Space(_) => None,
+    });
+    let (shortest, longest) = marks.fold((f32::MAX, 0.0f32), |(lo, hi), ms| (lo.min(ms), hi.max(ms)));
+    if longest >= 2.0 * shortest {
+        timing::estimate_unit_ms(events).unwrap_or(unit_ms)
+    } else {
+        unit_ms
+    }
+}
+
+/// Decodes keying heard from a tone, following the sender's speed from `unit_ms` on.
+pub(crate) fn decode(events: &[TimingEvent], unit_ms: f32) -> TimingDecoder {
+    let mut decoder = TimingDecoder::new(starting_unit(events, unit_ms)).adaptive();
+    for event in events {
+        decoder.feed(*event);
+    }
+    decoder.finish();
+    decoder
+}
+
+/// Listens for one answer keyed on an oscillator and decodes it to code like `.- -...`.
+/// `unit_ms` carries the sender's dot length from one answer to the next. Returns the
+/// code and when the keying ended.
+pub fn read_answer(capture: &Capture, unit_ms: &mut f32) -> (String, Instant) {
+    let (code, _, ended) = read_keying(capture, unit_ms);
+    (code, ended)
+}
+
+/// Like `read_answer`, also returning the marks and spaces that were heard.
+pub fn read_keying(capture: &Capture, unit_ms: &mut f32) -> (String, Vec<TimingEvent>, Instant) {
+    let (events, ended) = capture.listen(spacebar::end_after(*unit_ms), spacebar::GIVE_UP);
+    if events.is_empty() {
+        println!("(nothing heard)");
+        return (String::new(), events, ended);
+    }
+    let mut decoder = decode(&events, *unit_ms);
+    *unit_ms = decoder.unit_ms();
+    let code = decoder.take_code();
+    println!("{}  (heard at {:.0} WPM)", code, 1200.0 / *unit_ms);
+    (code, events, ended)
+}
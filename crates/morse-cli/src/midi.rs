@@ -0,0 +1,53 @@
+use std::{collections::BTreeSet, time::Instant};
+use morse_audio::{midi::{self, MidiKeys}, Sidetone};
+use morse_core::{keyer::KeyChange, timing::TimingEvent};
+use crate::spacebar;
+
+/// Opens the MIDI input matching `name`, or the first one when it's empty, naming the
+/// inputs there are when it can't be opened.
+pub fn open(name: &str) -> Result<MidiKeys, String> {
+    MidiKeys::open(Some(name).filter(|n| !n.is_empty())).map_err(|e| match midi::input_ports() {
+        ports if ports.is_empty() => e.to_string(),
+        ports => format!("{}; MIDI inputs: {}", e, ports.join(", ")),
+    })
+}
+
+/// Reads one answer keyed on a MIDI device used as a straight key: the key is down while
+/// any note or pedal is held. Sounds it on `sidetone` and decodes it like keying on the
+/// spacebar, carrying the sender's dot length in `unit_ms`.
+pub fn read_keying(keys: &MidiKeys, sidetone: Option<&Sidetone>, unit_ms: &mut f32) -> (String, Vec<TimingEvent>, Instant) {
+    keys.clear();
+    let end_after = spacebar::end_after(*unit_ms);
+    let start = Instant::now();
+    let mut changes: Vec<KeyChange> = Vec::new();
+    let mut held = BTreeSet::new();
+    let mut last = start;
+
+    loop {
+        let waited = last.elapsed();
+        let limit = if changes.is_empty() { spacebar::GIVE_UP } else { end_after };
+        if held.is_empty() && waited >= limit {
+            break;
+        }
+        let timeout = if held.is_empty() { limit - waited } else { end_after };
+        let Some((key, down, at)) = keys.next(timeout) else {
+            continue;
+        };
+        let was_down = !held.is_empty();
+        match down {
+            true => held.insert(key),
+            false => held.remove(&key),
+        };
+        if held.is_empty() == was_down {
+            if let Some(sidetone) = sidetone {
+                match was_down {
+                    false => sidetone.down(),
+                    true => sidetone.up(),
+                }
+            }
+            changes.push(KeyChange { at_ms: at.duration_since(start).as_secs_f32() * 1000.0, down: !was_down });
+            last = at;
+        }
+    }
+    spacebar::decode(&changes, unit_ms, last)
+}
@@ -0,0 +1,126 @@
+use std::{io, time::Instant};
+use morse_audio::Sidetone;
+use morse_core::{keyer::{KeyChange, KeyerSettings, Paddles}, timing::TimingEvent};
+use morse_platform::terminal::{MouseButton, MouseInput, MouseReports};
+use crate::{paddles::{self, PaddleInput}, spacebar};
+
+/// Whether mouse buttons can be read, which needs input from a terminal.
+pub fn available() -> Result<(), String> {
+    MouseReports::enable().map(drop).map_err(|e| e.to_string())
+}
+
+fn open() -> Option<MouseReports> {
+    match MouseReports::enable() {
+        Ok(mouse) => Some(mouse),
+        Err(e) => {
+            eprintln!("Error reading the mouse: {}", e);
+            None
+        }
+    }
+}
+
+/// Reads one answer keyed by holding any mouse button as a straight key, sounding it on
+/// `sidetone` while it's held, and decodes it like keying on the spacebar. Enter ends the
+/// answer early. `unit_ms` carries the sender's dot length from one answer to the next.
+pub fn read_straight(sidetone: Option<&Sidetone>, unit_ms: &mut f32) -> Option<(String, Vec<TimingEvent>, Instant)> {
+    let mut mouse = open()?;
+    let end_after = spacebar::end_after(*unit_ms);
+    let start = Instant::now();
+    let mut changes: Vec<KeyChange> = Vec::new();
+    // Buttons held, so one let go while another is still down leaves the key down.
+    let mut held: Vec<MouseButton> = Vec::new();
+    let mut last = start;
+
+    loop {
+        let waited = last.elapsed();
+        let limit = if changes.is_empty() { spacebar::GIVE_UP } else { end_after };
+        if held.is_empty() && waited >= limit {
+            break;
+        }
+        let timeout = if held.is_empty() { limit - waited } else { end_after };
+        let input = match mouse.next(timeout) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Error reading the mouse: {}", e);
+                return None;
+            }
+        };
+        let Some((input, at)) = input else {
+            continue;
+        };
+        let report = match input {
+            MouseInput::Interrupt => {
+                drop(mouse);
+                println!();
+                std::process::exit(130);
+            }
+            MouseInput::Enter if held.is_empty() => break,
+            MouseInput::Enter => continue,
+            MouseInput::Button(report) => report,
+        };
+        let was_down = !held.is_empty();
+        held.retain(|b| *b != report.button);
+        if report.down {
+            held.push(report.button);
+        }
+        let down = !held.is_empty();
+        if down == was_down {
+            continue;
+        }
+        if let Some(sidetone) = sidetone {
+            match down {
+                true => sidetone.down(),
+                false => sidetone.up(),
+            }
+        }
+        changes.push(KeyChange { at_ms: at.duration_since(start).as_secs_f32() * 1000.0, down });
+        last = at;
+    }
+    drop(mouse);
+    Some(spacebar::decode(&changes, unit_ms, last))
+}
+
+/// The left and right mouse buttons as paddles.
+struct MousePaddles {
+    mouse: MouseReports,
+    paddles: Paddles,
+    enter: bool,
+}
+
+impl PaddleInput for MousePaddles {
+    fn follow(&mut self, deadline: Instant, until_change: bool, mut touched: impl FnMut(Paddles)) -> io::Result<()> {
+        while let Some(timeout) = deadline.checked_duration_since(Instant::now()) {
+            let Some((input, _)) = self.mouse.next(timeout)? else {
+                continue;
+            };
+            match input {
+                MouseInput::Interrupt => return Err(io::ErrorKind::Interrupted.into()),
+                MouseInput::Enter => self.enter = true,
+                MouseInput::Button(report) if report.button == MouseButton::Left => self.paddles.left = report.down,
+                MouseInput::Button(report) if report.button == MouseButton::Right => self.paddles.right = report.down,
+                _ => continue,
+            }
+            touched(self.paddles);
+            if until_change {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn paddles(&self) -> Paddles {
+        self.paddles
+    }
+
+    fn ended(&self) -> bool {
+        self.enter
+    }
+}
+
+/// Reads one answer keyed on the left and right mouse buttons used as paddles, through the
+/// keyer as with the paddle keys on the keyboard. An answer ends after a pause or with Enter.
+pub fn read_paddles(settings: KeyerSettings, unit_ms: f32, sidetone: Option<&Sidetone>) -> Option<(String, Vec<TimingEvent>, Instant)> {
+    let mouse = open()?;
+    let keyed = paddles::key(MousePaddles { mouse, paddles: Paddles::default(), enter: false }, settings, unit_ms, sidetone);
+    paddles::decode(keyed, unit_ms, "the mouse")
+}
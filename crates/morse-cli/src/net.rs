@@ -0,0 +1,274 @@
+use std::{fs, io::{self, Write}, path::{Path, PathBuf}, time::Instant};
+use serde_derive::Deserialize;
+use morse_audio::{led, terminal::{self, TerminalStyle}, vibration};
+use morse_core::{grading, morse, scoring::{Rule, Scorecard}, timing::Spacing};
+use morse_stats::SessionSettings;
+use crate::{cli::{self, Output}, config::AppConfig, scoring};
+
+/// What the calls and traffic copied in a net are judged by unless configured otherwise.
+const DEFAULT_RULES: [Rule; 2] = [Rule::MinAccuracy(90.0), Rule::MaxConsecutiveErrors(5)];
+
+/// Stands for your own callsign in a scenario's roll.
+const YOUR_CALL: &str = "{call}";
+
+/// The net that comes with the program, also a template for writing your own.
+const BUILTIN: &str = r#"
+name = "Slow speed practice net"
+net_control = "W1SSN"
+wpm = 10.0
+opening = "QST QST QST DE W1SSN W1SSN SLOW SPEED NET QNI"
+roll = ["K1ABC", "N2DEF", "{call}", "W3GHI"]
+traffic = [
+    "GM ALL WX HR CLOUDY ES COOL",
+    "NEXT NET SAT 1900 LOCAL",
+]
+closing = "TNX ALL FER QNI NET CLOSED"
+"#;
+
+/// A scripted net session, read from a TOML file.
+#[derive(Debug, Deserialize)]
+pub struct NetScenario {
+    pub name: String,
+    pub net_control: String,
+    /// Character speed; your configured speed when unset.
+    #[serde(default)]
+    pub wpm: Option<f32>,
+    #[serde(default)]
+    pub opening: String,
+    /// Stations called in order; `{call}` is you.
+    pub roll: Vec<String>,
+    /// Lines of traffic net control passes on after the roll.
+    #[serde(default)]
+    pub traffic: Vec<String>,
+    #[serde(default)]
+    pub closing: String,
+}
+
+impl NetScenario {
+    fn parse(text: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut scenario: NetScenario = toml::from_str(text)?;
+        scenario.net_control = scenario.net_control.to_uppercase();
+        if !scenario.roll.iter().any(|call| call == YOUR_CALL) {
+            return Err(format!("the roll has no {} entry for your own callsign", YOUR_CALL).into());
+        }
+        Ok(scenario)
+    }
+}
+
+/// Directory of the user's own scenario files.
+pub fn dir() -> PathBuf {
+    morse_platform::paths::data_file("morse_nets")
+}
+
+fn scenario_files() -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir())
+        .map(|entries| entries.filter_map(|e| e.ok().map(|e| e.path())).collect())
+        .unwrap_or_default();
+    files.retain(|path| path.extension().is_some_and(|ext| ext == "toml"));
+    files.sort();
+    files
+}
+
+/// The built-in net, a scenario file, or one of the user's scenarios by file stem.
+fn load(name: Option<&str>) -> Result<NetScenario, Box<dyn std::error::Error>> {
+    let Some(name) = name else {
+        return NetScenario::parse(BUILTIN);
+    };
+    let path = Path::new(name);
+    let path = if path.exists() {
+        path.to_path_buf()
+    } else {
+        scenario_files().into_iter()
+            .find(|file| file.file_stem().is_some_and(|stem| stem.eq_ignore_ascii_case(name)))
+            .ok_or_else(|| format!("no net scenario '{}' (see `net --list`)", name))?
+    };
+    let text = fs::read_to_string(&path)
+        .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    NetScenario::parse(&text).map_err(|e| format!("{}: {}", path.display(), e).into())
+}
+
+pub fn list() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Built-in:  {}", NetScenario::parse(BUILTIN)?.name);
+    for path in scenario_files() {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        match fs::read_to_string(&path).map_err(|e| e.into()).and_then(|text| NetScenario::parse(&text)) {
+            Ok(scenario) => println!("{:<10} {}", stem, scenario.name),
+            Err(e) => println!("{:<10} (unreadable: {})", stem, e),
+        }
+    }
+    println!("Add your own nets as .toml files in {}", dir().display());
+    Ok(())
+}
+
+/// Sends text in Morse to the chosen output at a given speed.
+#[derive(Clone)]
+pub(crate) struct Station {
+    output: Output,
+    spacing: Spacing,
+    config: AppConfig,
+}
+
+impl Station {
+    /// Falls back to text output without an audio or vibration device.
+    pub(crate) fn new(mut output: Output, wpm: Option<f32>, config: AppConfig) -> Self {
+        if output == Output::Audio && !morse_audio::audio_available() {
+            println!("No audio device found, showing the code as text instead.");
+            output = Output::Text;
+        }
+        if output == Output::Vibration && config.vibration_port.is_none() {
+            println!("No vibration device set, showing the code as text instead (see `config vibration`).");
+            output = Output::Text;
+        }
+        let spacing = config.spacing(wpm);
+        Station { output, spacing, config }
+    }
+
+    /// Exactly `wpm`, without the configured Farnsworth spacing.
+    pub(crate) fn at_speed(output: Output, wpm: f32, config: AppConfig) -> Self {
+        let mut station = Station::new(output, Some(wpm), config);
+        station.spacing = Spacing::farnsworth(wpm, wpm);
+        station
+    }
+
+    /// Sends `text`, returning how many minutes it took.
+    pub(crate) fn send_timed(&self, text: &str) -> f32 {
+        let start = Instant::now();
+        self.send(text);
+        start.elapsed().as_secs_f32() / 60.0
+    }
+
+    /// The playback settings to record with a session.
+    pub(crate) fn settings(&self) -> SessionSettings {
+        let unit_ms = match self.output {
+            Output::Led => led::LED_UNIT_MS,
+            Output::Vibration => self.config.vibration_unit_ms(),
+            _ => self.spacing.unit_ms,
+        };
+        SessionSettings {
+            output: cli::value_name(&self.output),
+            pitch_hz: self.config.pitch_hz(),
+            dot_ms: unit_ms.round() as u32,
+            dash_ms: (3.0 * unit_ms).round() as u32,
+        }
+    }
+
+    pub(crate) fn send(&self, text: &str) {
+        let code = morse::encode(text);
+        match self.output {
+            Output::Audio => morse_audio::play_rendered(&code, self.spacing, &self.config.tone_settings()),
+            Output::Led => led::blink_morse_code(&code),
+            Output::Vibration => {
+                let port = self.config.vibration_port.as_deref().unwrap_or_default();
+                vibration::vibrate_morse_code(port, &code, self.config.vibration_unit_ms())
+            }
+            Output::Bell => terminal::play_morse_code(&code, TerminalStyle::Bell, self.spacing),
+            Output::Text => terminal::play_morse_code(&code, TerminalStyle::Text, self.spacing),
+        }
+    }
+}
+
+/// One line of input, upper-cased with single spaces, or `None` at the end of input.
+pub(crate) fn answer(prompt: &str) -> Option<String> {
+    print!("{}", prompt);
+    io::stdout().flush().ok()?;
+    let mut line = String::new();
+    match io::stdin().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line.split_whitespace().collect::<Vec<_>>().join(" ").to_uppercase()),
+    }
+}
+
+/// Runs a net: net control calls the roll, you copy each call and check in when yours
+/// comes up, then copy the traffic.
+pub fn run(name: Option<&str>, call: Option<&str>, rules: Option<&str>, output: Output) -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load().unwrap_or_default();
+    let rules = scoring::resolve(rules, config.scoring.net.as_deref(), &DEFAULT_RULES)?;
+    let call = call.map(str::to_string)
+        .or_else(|| config.callsign.clone())
+        .ok_or("set your callsign with --call or `callsign` in morse_config.toml")?
+        .to_uppercase();
+    let scenario = load(name)?;
+    let station = Station::new(output, scenario.wpm, config);
+    let ncs = &scenario.net_control;
+    let check_in = format!("{} DE {} K", ncs, call);
+
+    println!("{} (net control {})", scenario.name, ncs);
+    println!("Copy each call net control sends. When it's yours, check in with `{}`.", check_in);
+    if !scenario.opening.is_empty() {
+        println!("\nNet control opens the net...");
+        station.send(&scenario.opening);
+    }
+
+    let mut calls_copied = 0;
+    let mut checked_in = false;
+    let mut card = Scorecard::default();
+    println!("\n--- Roll call ---");
+    for entry in &scenario.roll {
+        let mine = entry == YOUR_CALL;
+        let called = if mine { call.clone() } else { entry.to_uppercase() };
+        let minutes = station.send_timed(&format!("{} DE {} K", called, ncs));
+        let Some(reply) = answer("> ") else {
+            return Ok(());
+        };
+        if mine {
+            checked_in = reply == check_in;
+            if checked_in {
+                println!("✓ Checked in.");
+            } else {
+                println!("✗ That was your call. Check in with: {} (your: {})", check_in, reply);
+            }
+        } else {
+            card.add(&Scorecard::compare(&called, &reply, minutes));
+            if reply == called {
+                calls_copied += 1;
+                println!("✓ {}", called);
+            } else {
+                println!("✗ It was {} (your: {})", called, reply);
+            }
+            // The station answers net control.
+            station.send(&format!("{} DE {} K", ncs, called));
+        }
+    }
+
+    let mut words_copied = 0;
+    let mut words_sent = 0;
+    if !scenario.traffic.is_empty() {
+        println!("\n--- Traffic ---");
+        for line in &scenario.traffic {
+            let minutes = station.send_timed(&format!("BT {} BT", line));
+            let Some(copy) = answer("> ") else {
+                return Ok(());
+            };
+            card.add(&Scorecard::compare(line, &copy, minutes));
+            let copied = grading::copied_words(line, &copy);
+            let words = line.split_whitespace().count();
+            words_copied += copied;
+            words_sent += words;
+            if copied == words {
+                println!("✓ All {} words", words);
+            } else {
+                println!("✗ {}/{} words. It was: {}", copied, words, line.to_uppercase());
+            }
+        }
+    }
+    if !scenario.closing.is_empty() {
+        station.send(&format!("{} DE {} SK", scenario.closing, ncs));
+    }
+
+    let others = scenario.roll.iter().filter(|entry| *entry != YOUR_CALL).count();
+    println!("\nNet closed.");
+    println!("Roll call: {}/{} calls copied, {}", calls_copied, others,
+        if checked_in { "checked in" } else { "missed your check-in" });
+    if words_sent > 0 {
+        println!("Traffic: {}/{} words copied ({:.0}%)", words_copied, words_sent,
+            words_copied as f32 / words_sent as f32 * 100.0);
+    }
+    println!();
+    let copied = scoring::print_verdict(&card, &rules);
+    println!("{}", match (checked_in, copied) {
+        (true, true) => "Well done, see you at the next net.",
+        (false, _) => "Check in with the right procedure next time.",
+        (true, false) => "Your copy didn't meet the net's rules this time.",
+    });
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+use chrono::{DateTime, FixedOffset, Local};
+use morse_core::{difficulty, morse, progression::ProgressionSystem, scheduler};
+use morse_stats::{Novelty, UserStats};
+use crate::suggest::RETENTION_GAP_DAYS;
+
+/// The text an item stands for, with code items decoded back to their character.
+fn text(item: &str) -> String {
+    match scheduler::decode_code(item).and_then(morse::morse_to_char) {
+        Some(c) => c.to_string(),
+        None => item.to_uppercase(),
+    }
+}
+
+pub fn difficulty(item: &str) -> f32 {
+    difficulty::word_difficulty(&text(item))
+}
+
+/// Tags items as new, a retention check or review, against the history before the session.
+#[derive(Debug)]
+pub struct NoveltyTagger {
+    new_chars: Vec<char>,
+    last_answered: HashMap<String, DateTime<FixedOffset>>,
+    now: DateTime<Local>,
+}
+
+impl NoveltyTagger {
+    pub fn new(stats: &UserStats, progression: &ProgressionSystem, level: u8) -> Self {
+        let mut last_answered = HashMap::new();
+        for session in &stats.session_history {
+            for answer in &session.answers {
+                last_answered.insert(text(&answer.item), session.timestamp);
+            }
+        }
+        NoveltyTagger {
+            new_chars: progression.level(level).map(|l| l.chars_to_learn.clone()).unwrap_or_default(),
+            last_answered,
+            now: Local::now(),
+        }
+    }
+
+    pub fn novelty(&self, item: &str) -> Novelty {
+        let text = text(item);
+        let Some(last) = self.last_answered.get(&text) else {
+            return Novelty::New;
+        };
+        if text.chars().any(|c| self.new_chars.contains(&c)) {
+            return Novelty::New;
+        }
+        match (self.now.date_naive() - last.date_naive()).num_days() >= RETENTION_GAP_DAYS {
+            true => Novelty::RetentionCheck,
+            false => Novelty::Review,
+        }
+    }
+}
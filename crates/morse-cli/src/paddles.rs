@@ -0,0 +1,182 @@
+use std::{io, time::{Duration, Instant}};
+use morse_audio::Sidetone;
+use morse_core::{keyer::{Element, Keyer, KeyerSettings, Paddles}, timing::TimingEvent};
+use morse_platform::terminal::{KeyAction, KeyReports, LEFT_ARROW, RIGHT_ARROW};
+use crate::{mic, spacebar::{self, GIVE_UP}};
+
+const LEFT_KEYS: [u32; 2] = ['z' as u32, LEFT_ARROW];
+const RIGHT_KEYS: [u32; 2] = ['x' as u32, RIGHT_ARROW];
+const ENTER: u32 = 13;
+const CTRL_C: u32 = 99;
+
+/// Paddles read as they change, from the keyboard or a key wired to a serial port.
+pub trait PaddleInput {
+    /// Follows the paddles until `deadline`, or until the first change with `until_change`,
+    /// calling `touched` with the paddles whenever they change.
+    fn follow(&mut self, deadline: Instant, until_change: bool, touched: impl FnMut(Paddles)) -> io::Result<()>;
+
+    fn paddles(&self) -> Paddles;
+
+    /// Whether the answer was ended before the pause, as with Enter.
+    fn ended(&self) -> bool;
+}
+
+/// The paddles as the keys on the keyboard hold them.
+struct KeyPaddles {
+    keys: KeyReports,
+    paddles: Paddles,
+    enter: bool,
+}
+
+impl PaddleInput for KeyPaddles {
+    fn follow(&mut self, deadline: Instant, until_change: bool, mut touched: impl FnMut(Paddles)) -> io::Result<()> {
+        while let Some(timeout) = deadline.checked_duration_since(Instant::now()) {
+            let Some((key, _)) = self.keys.next(timeout)? else {
+                continue;
+            };
+            let down = match key.action {
+                KeyAction::Press => true,
+                KeyAction::Release => false,
+                KeyAction::Repeat => continue,
+            };
+            if key.code == CTRL_C && key.ctrl {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            if LEFT_KEYS.contains(&key.code) {
+                self.paddles.left = down;
+            } else if RIGHT_KEYS.contains(&key.code) {
+                self.paddles.right = down;
+            } else if key.code == ENTER && down {
+                self.enter = true;
+            } else {
+                continue;
+            }
+            touched(self.paddles);
+            if until_change {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn paddles(&self) -> Paddles {
+        self.paddles
+    }
+
+    fn ended(&self) -> bool {
+        self.enter
+    }
+}
+
+fn push_space(events: &mut Vec<TimingEvent>, ms: f32) {
+    match events.last_mut() {
+        Some(TimingEvent::Space(gap)) => *gap += ms,
+        Some(_) => events.push(TimingEvent::Space(ms)),
+        None => {}
+    }
+}
+
+/// Reads one answer keyed on two keys used as paddles, Z or ← on the left and X or → on
+/// the right, through an electronic keyer that times the elements at `unit_ms`. Each
+/// element sounds on `sidetone` as it is sent. An answer ends after a pause or with Enter.
+/// Returns the code, the marks and spaces, and when the keying ended; `None` if the
+/// terminal stopped reporting keys.
+pub fn read_keying(settings: KeyerSettings, unit_ms: f32, sidetone: Option<&Sidetone>) -> Option<(String, Vec<TimingEvent>, Instant)> {
+    let keys = match KeyReports::enable() {
+        Ok(keys) => keys,
+        Err(e) => {
+            eprintln!("Error reading the paddle keys: {}", e);
+            return None;
+        }
+    };
+    let keyed = key(KeyPaddles { keys, paddles: Paddles::default(), enter: false }, settings, unit_ms, sidetone);
+    decode(keyed, unit_ms, "the paddle keys")
+}
+
+/// Decodes and shows an answer keyed through the keyer on `what`, or exits on Ctrl+C.
+pub fn decode(keyed: io::Result<(Vec<TimingEvent>, Instant)>, unit_ms: f32, what: &str) -> Option<(String, Vec<TimingEvent>, Instant)> {
+    match keyed {
+        Ok((events, ended)) => {
+            if events.is_empty() {
+                println!("(nothing keyed)");
+                return Some((String::new(), events, ended));
+            }
+            let code = mic::decode(&events, unit_ms).take_code();
+            println!("{}", code);
+            Some((code, events, ended))
+        }
+        Err(e) if e.kind() == io::ErrorKind::Interrupted => {
+            println!();
+            std::process::exit(130);
+        }
+        Err(e) => {
+            eprintln!("Error reading {}: {}", what, e);
+            None
+        }
+    }
+}
+
+/// Keys one answer from `input` through a keyer timing the elements at `unit_ms`, returning
+/// the marks and spaces and when the keying ended.
+pub fn key(mut input: impl PaddleInput, settings: KeyerSettings, unit_ms: f32, sidetone: Option<&Sidetone>) -> io::Result<(Vec<TimingEvent>, Instant)> {
+    let unit = Duration::from_secs_f32(unit_ms / 1000.0);
+    let mut keyer = Keyer::new(settings);
+    let mut events = Vec::new();
+    let mut idle_since = Instant::now();
+    let mut idle = true;
+    let end_after = spacebar::end_after(unit_ms);
+
+    loop {
+        let Some(element) = keyer.next(input.paddles()) else {
+            let limit = if events.is_empty() { GIVE_UP } else { end_after };
+            if input.ended() || idle_since.elapsed() >= limit {
+                break;
+            }
+            input.follow(idle_since + limit, true, |_| {})?;
+            idle = true;
+            continue;
+        };
+        // Elements follow each other on the keyer's clock; only a pause adds to the gap.
+        let start = match idle {
+            true => {
+                push_space(&mut events, idle_since.elapsed().as_secs_f32() * 1000.0);
+                Instant::now()
+            }
+            false => idle_since,
+        };
+        idle = false;
+        let length = match element {
+            Element::Dit | Element::Dah => {
+                let length = if element == Element::Dit { unit } else { unit * 3 };
+                if let Some(sidetone) = sidetone {
+                    sidetone.key(if element == Element::Dit { '.' } else { '-' });
+                }
+                input.follow(start + length + unit, false, |paddles| keyer.touch(paddles))?;
+                length
+            }
+            // A bug's dah lasts as long as its paddle is held.
+            Element::Manual => {
+                if let Some(sidetone) = sidetone {
+                    sidetone.down();
+                }
+                let dah = |p: Paddles| if settings.reverse { p.left } else { p.right };
+                while dah(input.paddles()) && start.elapsed() < GIVE_UP {
+                    input.follow(start + GIVE_UP, true, |_| {})?;
+                }
+                if let Some(sidetone) = sidetone {
+                    sidetone.up();
+                }
+                let length = start.elapsed();
+                input.follow(Instant::now() + unit, false, |_| {})?;
+                length
+            }
+        };
+        events.push(TimingEvent::Mark(length.as_secs_f32() * 1000.0));
+        events.push(TimingEvent::Space(unit_ms));
+        idle_since = start + length + unit;
+    }
+    if let Some(TimingEvent::Space(_)) = events.last() {
+        events.pop();
+    }
+    Ok((events, idle_since))
+}
@@ -0,0 +1,109 @@
+//! Just enough of PDF to lay out pages of plain text with the standard base-14 fonts.
+
+use std::fmt::Write;
+
+pub const PAGE_WIDTH: f32 = 595.0;
+pub const PAGE_HEIGHT: f32 = 842.0;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Font {
+    Helvetica,
+    HelveticaBold,
+    Courier,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Helvetica => "F1",
+            Font::HelveticaBold => "F2",
+            Font::Courier => "F3",
+        }
+    }
+}
+
+const FONTS: [(&str, &str); 3] = [("F1", "Helvetica"), ("F2", "Helvetica-Bold"), ("F3", "Courier")];
+
+#[derive(Debug, Default)]
+pub struct Page {
+    content: String,
+}
+
+impl Page {
+    /// Places `text` with its baseline at `y` points from the top of the page.
+    pub fn text(&mut self, x: f32, y: f32, font: Font, size: f32, text: &str) {
+        let escaped = text.replace('\\', "\\\\").replace('(', "\\(").replace(')', "\\)");
+        let _ = writeln!(
+            self.content,
+            "BT /{} {} Tf {:.1} {:.1} Td ({}) Tj ET",
+            font.resource(), size, x, PAGE_HEIGHT - y, escaped
+        );
+    }
+
+    pub fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32) {
+        let _ = writeln!(
+            self.content,
+            "0.5 w {:.1} {:.1} m {:.1} {:.1} l S",
+            x1, PAGE_HEIGHT - y1, x2, PAGE_HEIGHT - y2
+        );
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Document {
+    pages: Vec<Page>,
+}
+
+impl Document {
+    pub fn add_page(&mut self, page: Page) {
+        self.pages.push(page);
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // Objects: 1 catalog, 2 page tree, 3..=5 fonts, then a page and its content per page.
+        let first_page = 3 + FONTS.len();
+        let mut objects: Vec<String> = Vec::new();
+
+        objects.push("<< /Type /Catalog /Pages 2 0 R >>".to_string());
+        let kids: Vec<String> = (0..self.pages.len())
+            .map(|i| format!("{} 0 R", first_page + 2 * i))
+            .collect();
+        objects.push(format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), self.pages.len()));
+        for (_, name) in FONTS {
+            objects.push(format!("<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>", name));
+        }
+
+        let font_resources: Vec<String> = FONTS.iter()
+            .enumerate()
+            .map(|(i, (resource, _))| format!("/{} {} 0 R", resource, 3 + i))
+            .collect();
+        for (i, page) in self.pages.iter().enumerate() {
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << {} >> >> /Contents {} 0 R >>",
+                PAGE_WIDTH, PAGE_HEIGHT, font_resources.join(" "), first_page + 2 * i + 1
+            ));
+            objects.push(format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                page.content.len(), page.content
+            ));
+        }
+
+        let mut out = String::from("%PDF-1.4\n");
+        let mut offsets = Vec::new();
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            let _ = write!(out, "{} 0 obj\n{}\nendobj\n", i + 1, object);
+        }
+        let xref = out.len();
+        let _ = write!(out, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(out, "{:010} 00000 n ", offset);
+        }
+        let _ = write!(
+            out,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1, xref
+        );
+        out.into_bytes()
+    }
+}
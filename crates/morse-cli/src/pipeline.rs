@@ -0,0 +1,82 @@
+use std::{collections::BTreeMap, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageKind {
+    /// Single characters of the session.
+    Chars,
+    /// Five-character code groups.
+    Groups,
+    /// Words spelled with the session's characters, or pseudo-words when there are too few.
+    Words,
+    /// Items missed earlier in the pipeline, or the weakest characters by strength if nothing was missed.
+    Review,
+}
+
+impl StageKind {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name.trim().to_lowercase().as_str() {
+            "chars" => Ok(StageKind::Chars),
+            "groups" => Ok(StageKind::Groups),
+            "words" => Ok(StageKind::Words),
+            "review" => Ok(StageKind::Review),
+            other => Err(format!("unknown stage '{}', expected chars, groups, words or review", other)),
+        }
+    }
+}
+
+impl fmt::Display for StageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            StageKind::Chars => "chars",
+            StageKind::Groups => "groups",
+            StageKind::Words => "words",
+            StageKind::Review => "review",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stage {
+    pub kind: StageKind,
+    pub minutes: u32,
+}
+
+/// Parses stages written as `KIND:MINUTES` separated by commas, e.g.
+/// `chars:2,groups:3,words:3,review:2`.
+pub fn parse(spec: &str) -> Result<Vec<Stage>, String> {
+    let stages = spec.split(',')
+        .filter(|s| !s.trim().is_empty())
+        .map(|stage| {
+            let (kind, minutes) = stage.split_once(':')
+                .ok_or_else(|| format!("stage '{}' needs a duration, e.g. {}:2", stage.trim(), stage.trim()))?;
+            let minutes: u32 = minutes.trim().parse()
+                .map_err(|_| format!("invalid duration '{}' in stage '{}'", minutes.trim(), stage.trim()))?;
+            Ok(Stage { kind: StageKind::parse(kind)?, minutes })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    if stages.is_empty() {
+        return Err("a pipeline needs at least one stage".to_string());
+    }
+    Ok(stages)
+}
+
+/// A pipeline named in the config, or else `name_or_spec` read as stages.
+pub fn resolve(name_or_spec: &str, pipelines: &BTreeMap<String, String>) -> Result<Vec<Stage>, String> {
+    parse(pipelines.get(name_or_spec).map(String::as_str).unwrap_or(name_or_spec))
+}
+
+pub fn describe(stages: &[Stage]) -> String {
+    stages.iter()
+        .map(|s| format!("{} {} min", s.kind, s.minutes))
+        .collect::<Vec<_>>()
+        .join(" → ")
+}
+
+/// What one stage of a finished pipeline amounted to.
+#[derive(Debug, Clone, Copy)]
+pub struct StageResult {
+    pub stage: Stage,
+    pub correct: u32,
+    pub total: u32,
+    pub seconds: u32,
+}
@@ -0,0 +1,104 @@
+use rand::seq::SliceRandom;
+use morse_core::{material, scoring::Scorecard};
+use crate::{cli::Output, config::AppConfig, net::{self, Station}, speed::DEFAULT_WPM};
+
+/// Speeds the copy part tries in turn, stopping at the first one that isn't copied well.
+const PROBE_SPEEDS: [f32; 7] = [10.0, 15.0, 20.0, 25.0, 30.0, 35.0, 40.0];
+/// Copy accuracy a speed has to be copied at to count.
+const SPEED_ACCURACY: f32 = 0.9;
+/// Five-character groups sent at each speed.
+const GROUPS_PER_SPEED: usize = 3;
+const GROUP_LENGTH: usize = 5;
+
+/// Sends each new character of every level in turn until a level's characters aren't
+/// recognized well enough, then copies groups of the known characters at rising speeds.
+/// The level that stopped the test, the characters before it and the fastest speed copied
+/// well become the starting point.
+pub fn run(output: Output) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load().unwrap_or_default();
+    let progression = config.progression();
+    let mut rng = rand::rng();
+
+    println!("Placement test: find out where to start, so you don't relearn what you already know.");
+    println!("Type each character you hear. Characters you don't know can be left empty.");
+    let station = Station::at_speed(output, DEFAULT_WPM, config.clone());
+    let mut placed = 9;
+    for level in &progression.levels {
+        let mut chars: Vec<char> = level.chars_to_learn.iter().copied()
+            .filter(|c| config.banned.allows_char(*c))
+            .collect();
+        if chars.is_empty() {
+            continue;
+        }
+        chars.shuffle(&mut rng);
+        println!("\n--- Level {} ---", level.level);
+        let mut correct = 0;
+        for c in &chars {
+            station.send(&c.to_string());
+            let Some(answer) = net::answer("> ") else {
+                return Ok(());
+            };
+            if answer == c.to_string() {
+                correct += 1;
+            }
+        }
+        let accuracy = correct as f32 / chars.len() as f32;
+        println!("{}/{} recognized", correct, chars.len());
+        if accuracy < level.accuracy_requirement {
+            placed = level.level;
+            break;
+        }
+    }
+
+    let known: Vec<char> = progression.chars_through(placed - 1).into_iter()
+        .filter(|c| config.banned.allows_char(*c))
+        .collect();
+    let mut fastest = None;
+    if !known.is_empty() {
+        println!("\nNow copy groups of the characters you know, getting faster each time.");
+        for wpm in PROBE_SPEEDS {
+            println!("\n--- {} WPM ---", wpm);
+            let station = Station::at_speed(output, wpm, config.clone());
+            let groups = material::code_groups(&known, GROUPS_PER_SPEED, GROUP_LENGTH, &mut rng);
+            let mut copy = Vec::new();
+            for group in &groups {
+                station.send(group);
+                let Some(answer) = net::answer("> ") else {
+                    return Ok(());
+                };
+                copy.push(answer);
+            }
+            let card = Scorecard::compare(&groups.join(" "), &copy.join(" "), 0.0);
+            let accuracy = 1.0 - card.errors as f32 / card.sent.max(1) as f32;
+            println!("{:.0}% copied", accuracy * 100.0);
+            if accuracy < SPEED_ACCURACY {
+                break;
+            }
+            fastest = Some(wpm);
+        }
+    }
+
+    config.difficulty_level = placed;
+    config.known_chars = progression.chars_through(placed);
+    config.probation = None;
+    config.speed.streak = 0;
+    if let Some(wpm) = fastest {
+        config.character_wpm = Some(wpm);
+        config.effective_wpm = config.effective_wpm.filter(|e| *e < wpm);
+    }
+    config.save()?;
+
+    println!("\nPlacement result:");
+    if placed >= 9 {
+        println!("  Level {}: you know every character, so practice moves on to words.", placed);
+    } else {
+        println!("  Level {}, learning {}", placed, progression.level(placed)
+            .map(|l| l.chars_to_learn.iter().collect::<String>())
+            .unwrap_or_default());
+    }
+    match fastest {
+        Some(wpm) => println!("  Speed: {} WPM, the fastest you copied at {:.0}% or better", wpm, SPEED_ACCURACY * 100.0),
+        None => println!("  Speed: unchanged at {} WPM", config.character_wpm.unwrap_or(DEFAULT_WPM)),
+    }
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use chrono::{Local, TimeDelta};
+use morse_stats::{PracticeTime, UserStats};
+use crate::estimate::format_minutes;
+
+/// Recent periods the time is totalled over, in days, before the total of all time.
+const PERIODS: [i64; 2] = [7, 30];
+
+fn minutes(secs: u64) -> String {
+    format_minutes(secs as f32 / 60.0)
+}
+
+/// One line with the total of all time, shown after audio-only sessions.
+pub fn summary(time: &PracticeTime) -> String {
+    format!(
+        "Total exposure {}: {} in graded drills, {} listening.",
+        minutes(time.exposure_secs()),
+        minutes(time.active_secs),
+        minutes(time.passive_secs)
+    )
+}
+
+/// Shows active, passive and total practice time over the last week, month and all time.
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let stats = UserStats::load()?;
+    let now = Local::now();
+    let mut rows: Vec<(String, PracticeTime)> = PERIODS.iter()
+        .map(|days| (format!("Last {} days", days), stats.practice_time(Some(&(now - TimeDelta::days(*days))))))
+        .collect();
+    rows.push(("All time".to_string(), stats.practice_time::<Local>(None)));
+
+    println!("{:<13} {:>10} {:>10} {:>10}", "", "Active", "Passive", "Exposure");
+    for (period, time) in rows {
+        println!(
+            "{:<13} {:>10} {:>10} {:>10}",
+            period,
+            minutes(time.active_secs),
+            minutes(time.passive_secs),
+            minutes(time.exposure_secs())
+        );
+    }
+    println!("\nActive time is spent answering in drills, passive time listening without answering.");
+    Ok(())
+}
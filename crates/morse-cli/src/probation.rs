@@ -0,0 +1,70 @@
+use serde_derive::{Serialize, Deserialize};
+use crate::config::AppConfig;
+
+/// Sessions after a promotion during which it can still be taken back.
+pub const PROBATION_SESSIONS: u8 = 3;
+/// A probation session collapses when its accuracy falls below this share of the
+/// accuracy required by the level the learner was promoted from.
+pub const COLLAPSE_FACTOR: f32 = 0.8;
+
+/// A recent promotion, remembered with what to restore if it turns out to be premature.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Probation {
+    pub previous_level: u8,
+    pub previous_known_chars: Vec<char>,
+    /// Accuracy requirement of the level the learner was promoted from.
+    pub accuracy_requirement: f32,
+    pub sessions_left: u8,
+}
+
+impl Probation {
+    pub fn new(previous_level: u8, previous_known_chars: Vec<char>, accuracy_requirement: f32) -> Self {
+        Probation {
+            previous_level,
+            previous_known_chars,
+            accuracy_requirement,
+            sessions_left: PROBATION_SESSIONS,
+        }
+    }
+
+    pub fn collapsed(&self, accuracy: f32) -> bool {
+        accuracy < self.accuracy_requirement * COLLAPSE_FACTOR
+    }
+}
+
+/// What a finished session did to the probation of the last promotion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbationOutcome {
+    /// Accuracy collapsed, so the promotion to level `from` was taken back.
+    Reverted { from: u8 },
+    /// The last probation session went well enough, so the level is kept.
+    Passed,
+    /// More sessions have to go well before the level is kept.
+    Continues { sessions_left: u8 },
+}
+
+/// Counts a session toward the probation of the last promotion, reverting the promotion
+/// if accuracy collapsed. A session without answers says nothing about the new level, so
+/// it leaves the probation as it is. `None` when there is no probation to count toward.
+pub fn settle(config: &mut AppConfig, accuracy: f32, answers: u32) -> Option<ProbationOutcome> {
+    if answers == 0 {
+        return None;
+    }
+    let probation = config.probation.as_mut()?;
+
+    if probation.collapsed(accuracy) {
+        let probation = config.probation.take()?;
+        let from = config.difficulty_level;
+        config.difficulty_level = probation.previous_level;
+        config.known_chars = probation.previous_known_chars;
+        return Some(ProbationOutcome::Reverted { from });
+    }
+
+    probation.sessions_left = probation.sessions_left.saturating_sub(1);
+    if probation.sessions_left == 0 {
+        config.probation = None;
+        Some(ProbationOutcome::Passed)
+    } else {
+        Some(ProbationOutcome::Continues { sessions_left: probation.sessions_left })
+    }
+}
@@ -0,0 +1,57 @@
+use morse_core::{grading, radiogram::{Field, Radiogram}};
+use crate::{cli::Output, config::AppConfig, net::{self, Station}};
+
+/// Whether a copied field matches; the text is graded by words.
+fn grade(field: &Field, copy: &str) -> (usize, usize) {
+    if field.name == "Text" {
+        (grading::copied_words(&field.value, copy), field.value.split_whitespace().count())
+    } else {
+        (usize::from(copy == field.value), 1)
+    }
+}
+
+/// Sends `count` random NTS radiograms part by part; after each part, the fields copied
+/// from it are filled in one by one and graded.
+pub fn drill(count: usize, wpm: Option<f32>, output: Output) -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load().unwrap_or_default();
+    let words: Vec<String> = config.progression().common_words.into_iter()
+        .filter(|word| config.banned.allows_word(word))
+        .collect();
+    let station = Station::new(output, wpm, config);
+    let mut rng = rand::rng();
+    // (field name, correct, total) in the order fields are sent; the text counts words.
+    let mut scores: Vec<(&'static str, usize, usize)> = Vec::new();
+
+    println!("Copy each part of the message, then fill in its fields.");
+    for number in 1..=count {
+        let message = Radiogram::random(&words, &mut rng);
+        println!("\n--- Message {} of {} ---", number, count);
+        for (sent, fields) in message.parts() {
+            station.send(&sent);
+            for field in fields {
+                let Some(copy) = net::answer(&format!("  {}: ", field.name)) else {
+                    return Ok(());
+                };
+                let (correct, total) = grade(field, &copy);
+                if correct == total {
+                    println!("  ✓");
+                } else {
+                    println!("  ✗ {} (your: {})", field.value, copy);
+                }
+                match scores.iter_mut().find(|(name, _, _)| *name == field.name) {
+                    Some(score) => {
+                        score.1 += correct;
+                        score.2 += total;
+                    }
+                    None => scores.push((field.name, correct, total)),
+                }
+            }
+        }
+    }
+
+    println!("\nCopied by field:");
+    for (name, correct, total) in scores {
+        println!("  {:<18} {:>3}/{:<3} {:>4.0}%", name, correct, total, correct as f32 / total as f32 * 100.0);
+    }
+    Ok(())
+}
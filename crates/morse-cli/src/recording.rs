@@ -0,0 +1,48 @@
+use std::{fs, path::PathBuf};
+use chrono::Local;
+use morse_audio::render::{self, ToneSettings};
+use morse_core::timing::TimingEvent;
+
+/// Silence between two answers in a recording.
+const ANSWER_GAP_MS: f32 = 1000.0;
+
+/// Directory the keying recordings are written to.
+pub fn dir() -> PathBuf {
+    morse_platform::paths::data_file("morse_recordings")
+}
+
+/// The answers keyed in a session, exactly as they were heard, kept to be written to one WAV file.
+#[derive(Debug, Default)]
+pub struct KeyingRecording {
+    events: Vec<TimingEvent>,
+}
+
+impl KeyingRecording {
+    /// Adds one answer, without the silence before and after it.
+    pub fn add(&mut self, events: &[TimingEvent]) {
+        let start = events.iter().position(|e| matches!(e, TimingEvent::Mark(_)));
+        let end = events.iter().rposition(|e| matches!(e, TimingEvent::Mark(_)));
+        let (Some(start), Some(end)) = (start, end) else {
+            return;
+        };
+        if !self.events.is_empty() {
+            self.events.push(TimingEvent::Space(ANSWER_GAP_MS));
+        }
+        self.events.extend_from_slice(&events[start..=end]);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Renders the keying as a clean tone at `pitch_hz` to `keying-<date>-<time>.wav`,
+    /// returning the file's path.
+    pub fn save(&self, pitch_hz: f32) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let dir = dir();
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("keying-{}.wav", Local::now().format("%Y-%m-%d-%H%M%S")));
+        let samples = render::render_events(&self.events, &ToneSettings { pitch_hz, ..ToneSettings::default() });
+        render::write_wav(fs::File::create(&path)?, &samples)?;
+        Ok(path)
+    }
+}
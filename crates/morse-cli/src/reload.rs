@@ -0,0 +1,115 @@
+use std::{fs, time::SystemTime};
+use crate::{cli, config::AppConfig};
+
+fn modified() -> Option<SystemTime> {
+    fs::metadata(AppConfig::config_path()).and_then(|m| m.modified()).ok()
+}
+
+/// Notices edits to `morse_config.toml` made while a session runs.
+#[derive(Debug)]
+pub struct ConfigWatch {
+    modified: Option<SystemTime>,
+}
+
+impl ConfigWatch {
+    pub fn new() -> Self {
+        ConfigWatch { modified: modified() }
+    }
+
+    /// The config as it is on disk, if the file changed since the last check. An edit that
+    /// doesn't parse is reported and skipped until the file changes again.
+    pub fn changed(&mut self) -> Option<AppConfig> {
+        let now = modified();
+        if now == self.modified {
+            return None;
+        }
+        self.modified = now;
+        // Loading a missing file would write the defaults over the session's settings.
+        now.and_then(|_| load())
+    }
+}
+
+impl Default for ConfigWatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The config on disk, reporting why it can't be read.
+pub fn load() -> Option<AppConfig> {
+    match AppConfig::load() {
+        Ok(config) => Some(config),
+        Err(e) => {
+            eprintln!("Error reloading configuration: {}", e);
+            None
+        }
+    }
+}
+
+fn optional<T>(value: &Option<T>, describe: impl Fn(&T) -> String) -> String {
+    value.as_ref().map(describe).unwrap_or_else(|| "off".to_string())
+}
+
+/// Takes the settings that can change mid-session from `edited`: sound, band conditions,
+/// the key preset and answer feedback. Everything else waits for the next session. Returns
+/// what changed, e.g. `pitch 700 Hz`.
+pub fn apply_safe(config: &mut AppConfig, edited: &AppConfig) -> Vec<String> {
+    let mut changes = Vec::new();
+    if (config.volume, config.muted) != (edited.volume, edited.muted) {
+        config.volume = edited.volume;
+        config.muted = edited.muted;
+        changes.push(match config.muted {
+            true => "muted".to_string(),
+            false => format!("volume {}%", config.volume()),
+        });
+    }
+    if config.pitch_hz != edited.pitch_hz {
+        config.pitch_hz = edited.pitch_hz;
+        changes.push(format!("pitch {:.0} Hz", config.pitch_hz()));
+    }
+    if config.ramp_ms != edited.ramp_ms {
+        config.ramp_ms = edited.ramp_ms;
+        changes.push(format!("envelope {}", optional(&config.ramp_ms, |ms| format!("{} ms", ms))));
+    }
+    if config.noise_snr_db != edited.noise_snr_db {
+        config.noise_snr_db = edited.noise_snr_db;
+        changes.push(format!("noise {}", optional(&config.noise_snr_db, |snr| format!("{} dB SNR", snr))));
+    }
+    if config.qsb != edited.qsb {
+        config.qsb = edited.qsb;
+        changes.push(format!("QSB {}", optional(&config.qsb, |q| format!("{} dB", q.depth_db))));
+    }
+    if config.qrm != edited.qrm {
+        config.qrm = edited.qrm;
+        changes.push(format!("QRM {}", optional(&config.qrm, |q| format!("{:+} Hz", q.offset_hz))));
+    }
+    if config.stereo != edited.stereo {
+        config.stereo = edited.stereo;
+        changes.push(format!("stereo {}", optional(&config.stereo, |s| cli::value_name(&s.ear))));
+    }
+    if config.fist != edited.fist {
+        config.fist = edited.fist;
+        changes.push(format!("fist {}", optional(&config.fist, |f| format!("{}%/{}%", f.element_pct, f.spacing_pct))));
+    }
+    if config.key != edited.key {
+        config.key = edited.key;
+        changes.push(format!("key {}", cli::value_name(&config.key)));
+    }
+    if config.answer_sounds != edited.answer_sounds {
+        config.answer_sounds = edited.answer_sounds;
+        changes.push(format!("answer sounds {}", if config.answer_sounds { "on" } else { "off" }));
+    }
+    if config.replay_attempt != edited.replay_attempt {
+        config.replay_attempt = edited.replay_attempt;
+        changes.push(format!("replay attempt {}", if config.replay_attempt { "on" } else { "off" }));
+    }
+    if config.metronome != edited.metronome {
+        config.metronome = edited.metronome;
+        changes.push(format!("metronome {}", if config.metronome { "on" } else { "off" }));
+    }
+    if config.notifications != edited.notifications {
+        config.notifications = edited.notifications;
+        changes.push(format!("notifications {}", if config.notifications { "on" } else { "off" }));
+    }
+    changes
+}
@@ -0,0 +1,126 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+use chrono::NaiveDate;
+use serde_derive::Serialize;
+use morse_core::{grading, progression::ProgressionSystem};
+use morse_stats::{LearningSession, Novelty, UserStats};
+
+/// Bumped whenever fields are renamed or removed; see docs/research-export.md.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize)]
+pub struct ResearchExport {
+    schema: &'static str,
+    schema_version: u32,
+    curriculum: Vec<CurriculumLevel>,
+    sessions: Vec<ResearchSession>,
+}
+
+#[derive(Debug, Serialize)]
+struct CurriculumLevel {
+    level: u8,
+    chars: Vec<char>,
+    speed_requirement: f32,
+    new_char_slack: f32,
+    accuracy_requirement: f32,
+}
+
+#[derive(Debug, Serialize)]
+struct ResearchSession {
+    day: Option<i64>,
+    duration_s: u32,
+    difficulty: u8,
+    word_level: bool,
+    /// Listened to without answering; the accuracy is then the overall one at the time.
+    passive: bool,
+    accuracy: f32,
+    answers: Vec<ResearchAnswer>,
+}
+
+#[derive(Debug, Serialize)]
+struct ResearchAnswer {
+    item: String,
+    expected: String,
+    correct: bool,
+    error: Option<&'static str>,
+    response_time_s: f32,
+    difficulty: Option<f32>,
+    novelty: Option<Novelty>,
+}
+
+fn session_date(session: &LearningSession) -> NaiveDate {
+    session.timestamp.date_naive()
+}
+
+/// Builds the export. Timestamps are replaced by day offsets from the first session and
+/// nothing else that could identify the learner is included.
+pub fn build(stats: &UserStats, progression: &ProgressionSystem) -> ResearchExport {
+    let curriculum = progression.levels.iter()
+        .map(|l| CurriculumLevel {
+            level: l.level,
+            chars: l.chars_to_learn.clone(),
+            speed_requirement: l.speed_requirement,
+            new_char_slack: l.new_char_slack,
+            accuracy_requirement: l.accuracy_requirement,
+        })
+        .collect();
+
+    let first_day = stats.session_history.iter().map(session_date).min();
+    let sessions = stats.session_history.iter()
+        .map(|session| ResearchSession {
+            day: first_day.map(|first| (session_date(session) - first).num_days()),
+            duration_s: session.duration,
+            difficulty: session.difficulty,
+            word_level: session.difficulty >= 9,
+            passive: session.passive,
+            accuracy: session.accuracy,
+            answers: session.answers.iter()
+                .map(|a| ResearchAnswer {
+                    item: a.item.clone(),
+                    expected: a.expected.clone(),
+                    correct: a.correct,
+                    error: grading::classify(&a.expected, &a.answer).map(|e| e.name()),
+                    response_time_s: a.response_time,
+                    difficulty: a.difficulty,
+                    novelty: a.novelty,
+                })
+                .collect(),
+        })
+        .collect();
+
+    ResearchExport {
+        schema: "morse_code_learner.research",
+        schema_version: SCHEMA_VERSION,
+        curriculum,
+        sessions,
+    }
+}
+
+pub fn export(out: &Path, yes: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let stats = UserStats::load()?;
+    
+    println!("This creates an anonymized research dataset containing:");
+    println!("- the curriculum (levels, characters and requirements)");
+    println!("- per-session difficulty, duration, accuracy and day offset (no dates or times)");
+    println!("- per-answer items, correctness, error type, response time, difficulty and novelty");
+    println!("Sessions to export: {}", stats.session_history.len());
+
+    if !yes {
+        print!("Create {}? [y/N]: ", out.display());
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Export cancelled.");
+            return Ok(());
+        }
+    }
+
+    let export = build(&stats, &ProgressionSystem::with_words(Vec::new()));
+    fs::write(out, serde_json::to_string_pretty(&export)?)?;
+    println!("Research export written to {}", out.display());
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use serde_derive::{Serialize, Deserialize};
+use morse_core::scoring::{self, Rule, Scorecard};
+
+/// Rule lists, e.g. `net = "errors-per-minute:2,consecutive:3"`, replacing the defaults.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ScoringConfig {
+    /// For every exam preset, instead of the preset's own rules.
+    pub exam: Option<String>,
+    pub net: Option<String>,
+    pub speedtest: Option<String>,
+}
+
+/// The rules from `--rules`, else from the config, else the defaults.
+pub fn resolve(flag: Option<&str>, configured: Option<&str>, default: &[Rule]) -> Result<Vec<Rule>, Box<dyn std::error::Error>> {
+    match flag.or(configured) {
+        Some(spec) => Ok(scoring::parse_rules(spec)?),
+        None => Ok(default.to_vec()),
+    }
+}
+
+pub fn describe(rules: &[Rule]) -> String {
+    rules.iter().map(|rule| rule.describe()).collect::<Vec<_>>().join(", ")
+}
+
+/// Prints the copy's figures and a line per rule, returning whether all were met.
+pub fn print_verdict(card: &Scorecard, rules: &[Rule]) -> bool {
+    println!("{} characters sent, {} errors ({:.1} per minute), {:.0}% copied.",
+        card.sent, card.errors, card.errors_per_minute(), card.accuracy() * 100.0);
+    println!("Longest solid copy {} characters, longest run of errors {}.", card.solid, card.consecutive_errors);
+    let verdict = card.judge(rules);
+    for (rule, passed) in &verdict {
+        println!("  {} {}", if *passed { "✓" } else { "✗" }, rule.describe());
+    }
+    verdict.iter().all(|(_, passed)| *passed)
+}
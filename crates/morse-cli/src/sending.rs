@@ -0,0 +1,66 @@
+use morse_core::keying::{KeyingNorms, SendingQuality};
+use morse_stats::SendingRecord;
+
+const IDEAL_DASH_DOT_RATIO: f32 = 3.0;
+const IDEAL_ELEMENT_GAP: f32 = 1.0;
+const IDEAL_LETTER_GAP: f32 = 3.0;
+
+pub fn record(quality: SendingQuality) -> SendingRecord {
+    SendingRecord {
+        dash_dot_ratio: quality.dash_dot_ratio,
+        element_gap: quality.element_gap,
+        element_gap_spread: quality.element_gap_spread,
+        letter_gap: quality.letter_gap,
+        letter_gap_spread: quality.letter_gap_spread,
+    }
+}
+
+/// `long` or `short` when `value` is further from `ideal` than the key's tolerance.
+fn off(value: f32, ideal: f32, norms: &KeyingNorms, long: &str, short: &str) -> Option<String> {
+    let deviation = value / ideal - 1.0;
+    if deviation > norms.tolerance {
+        Some(long.to_string())
+    } else if deviation < -norms.tolerance {
+        Some(short.to_string())
+    } else {
+        None
+    }
+}
+
+fn line(label: &str, value: String, tips: Vec<String>) -> String {
+    match tips.is_empty() {
+        true => format!("  {:<24} {}", label, value),
+        false => format!("  {:<24} {} - {}", label, value, tips.join(", ")),
+    }
+}
+
+/// Summary lines for a session's sending, each with a tip when it's off by more than the
+/// tolerance of the key in use.
+pub fn report(sending: &SendingRecord, norms: &KeyingNorms) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(ratio) = sending.dash_dot_ratio {
+        let tips = off(ratio, IDEAL_DASH_DOT_RATIO, norms, "dashes are long", "dashes are short").into_iter().collect();
+        lines.push(line("Dash to dot:", format!("{:.1} (aim for {:.0})", ratio, IDEAL_DASH_DOT_RATIO), tips));
+    }
+    for (label, gap, spread, ideal, inside) in [
+        ("Gaps within characters:", sending.element_gap, sending.element_gap_spread, IDEAL_ELEMENT_GAP, true),
+        ("Gaps between characters:", sending.letter_gap, sending.letter_gap_spread, IDEAL_LETTER_GAP, false),
+    ] {
+        let Some(gap) = gap else {
+            continue;
+        };
+        let mut tips: Vec<String> = match inside {
+            true => off(gap, ideal, norms, "elements run apart", "elements run together"),
+            false => off(gap, ideal, norms, "characters are spread out", "characters run together"),
+        }.into_iter().collect();
+        let mut value = format!("{:.1} dots (aim for {:.0})", gap, ideal);
+        if let Some(spread) = spread {
+            value.push_str(&format!(", ±{:.0}%", spread * 100.0));
+            if spread > norms.tolerance {
+                tips.push("uneven".to_string());
+            }
+        }
+        lines.push(line(label, value, tips));
+    }
+    lines
+}
@@ -0,0 +1,92 @@
+use std::{io, thread, time::{Duration, Instant}};
+use morse_audio::Sidetone;
+use morse_core::{keyer::{KeyChange, KeyerSettings, Paddles}, timing::TimingEvent};
+use morse_platform::serial::{self, SerialKey};
+use crate::{paddles::{self, PaddleInput}, spacebar};
+
+/// How often the key's contacts are read.
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Opens the key on `port`, naming the ports there are when it can't be opened.
+pub fn open(port: &str) -> Result<SerialKey, String> {
+    SerialKey::open(port).map_err(|e| match serial::ports() {
+        ports if ports.is_empty() => format!("{}; no serial ports were found", e),
+        ports => format!("{}; serial ports found: {}", e, ports.join(", ")),
+    })
+}
+
+/// Reads one answer on a straight key closing CTS or DSR, sounding it on `sidetone` while
+/// it's down, and decodes it like keying on the spacebar. `unit_ms` carries the sender's dot
+/// length from one answer to the next.
+pub fn read_straight(key: &mut SerialKey, sidetone: Option<&Sidetone>, unit_ms: &mut f32) -> Option<(String, Vec<TimingEvent>, Instant)> {
+    let end_after = spacebar::end_after(*unit_ms);
+    let start = Instant::now();
+    let mut changes: Vec<KeyChange> = Vec::new();
+    let mut down = false;
+    let mut last = start;
+
+    loop {
+        let contacts = match key.contacts() {
+            Ok(contacts) => contacts,
+            Err(e) => {
+                eprintln!("Error reading the serial key: {}", e);
+                return None;
+            }
+        };
+        let now = Instant::now();
+        if (contacts.cts || contacts.dsr) != down {
+            down = !down;
+            if let Some(sidetone) = sidetone {
+                match down {
+                    true => sidetone.down(),
+                    false => sidetone.up(),
+                }
+            }
+            changes.push(KeyChange { at_ms: now.duration_since(start).as_secs_f32() * 1000.0, down });
+            last = now;
+        } else if !down && now.duration_since(last) >= if changes.is_empty() { spacebar::GIVE_UP } else { end_after } {
+            break;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+    Some(spacebar::decode(&changes, unit_ms, last))
+}
+
+/// Paddles wired to a serial port: the dit paddle closing DSR, the dah paddle CTS.
+struct SerialPaddles<'a> {
+    key: &'a mut SerialKey,
+    paddles: Paddles,
+}
+
+impl PaddleInput for SerialPaddles<'_> {
+    fn follow(&mut self, deadline: Instant, until_change: bool, mut touched: impl FnMut(Paddles)) -> io::Result<()> {
+        while Instant::now() < deadline {
+            let contacts = self.key.contacts()?;
+            let paddles = Paddles { left: contacts.dsr, right: contacts.cts };
+            if paddles != self.paddles {
+                self.paddles = paddles;
+                touched(paddles);
+                if until_change {
+                    break;
+                }
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+        Ok(())
+    }
+
+    fn paddles(&self) -> Paddles {
+        self.paddles
+    }
+
+    fn ended(&self) -> bool {
+        false
+    }
+}
+
+/// Reads one answer on paddles wired to a serial port, through the keyer as with the paddle
+/// keys on the keyboard. An answer ends after a pause.
+pub fn read_paddles(key: &mut SerialKey, settings: KeyerSettings, unit_ms: f32, sidetone: Option<&Sidetone>) -> Option<(String, Vec<TimingEvent>, Instant)> {
+    let keyed = paddles::key(SerialPaddles { key, paddles: Paddles::default() }, settings, unit_ms, sidetone);
+    paddles::decode(keyed, unit_ms, "the serial key")
+}
@@ -0,0 +1,133 @@
+use std::{fmt::Write as _, fs, path::Path};
+use serde_json::json;
+use morse_core::grading;
+use morse_stats::{LearningSession, UserStats};
+use crate::locale;
+
+/// Finds a session by its number in the history (1 is the oldest), `last`, or the start
+/// of its timestamp such as `2025-06-14T18:30`.
+pub fn find<'a>(stats: &'a UserStats, id: &str) -> Result<(usize, &'a LearningSession), String> {
+    let history = &stats.session_history;
+    let index = if id == "last" {
+        history.len().checked_sub(1)
+    } else if let Ok(n) = id.parse::<usize>() {
+        n.checked_sub(1).filter(|&i| i < history.len())
+    } else {
+        let matches: Vec<usize> = (0..history.len())
+            .filter(|&i| history[i].timestamp.to_rfc3339().starts_with(id))
+            .collect();
+        if matches.len() > 1 {
+            return Err(format!("'{}' matches {} sessions, give more of the timestamp", id, matches.len()));
+        }
+        matches.first().copied()
+    };
+    index.map(|i| (i + 1, &history[i])).ok_or_else(|| format!("no session '{}'", id))
+}
+
+/// Paste-ready summary of everything that happened in a session.
+pub fn text(number: usize, session: &LearningSession) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "Morse Code Learner session #{}", number);
+    let _ = writeln!(out, "Date:     {}", locale::date_time(&session.timestamp));
+    let _ = writeln!(out, "Level:    {}", session.difficulty);
+    if session.passive {
+        let _ = writeln!(out, "Result:   passive listening for {}m {}s", session.duration / 60, session.duration % 60);
+    } else {
+        let _ = writeln!(
+            out,
+            "Result:   {}% correct in {}m {}s",
+            locale::decimal(session.accuracy * 100.0, 1),
+            session.duration / 60,
+            session.duration % 60
+        );
+    }
+    match &session.settings {
+        Some(s) => {
+            let _ = writeln!(
+                out,
+                "Playback: {} output, {} Hz, dot {} ms, dash {} ms",
+                s.output, s.pitch_hz, s.dot_ms, s.dash_ms
+            );
+        }
+        None => {
+            let _ = writeln!(out, "Playback: not recorded");
+        }
+    }
+    let _ = writeln!(out);
+
+    let expected_width = session.answers.iter().map(|a| a.expected.len()).max().unwrap_or(0).max(8);
+    let _ = writeln!(
+        out,
+        "{:>3}  {:<10} {:<ew$} {:<ew$} {:>6}  Result",
+        "#", "Item", "Expected", "Answer", "Time",
+        ew = expected_width
+    );
+    for (i, a) in session.answers.iter().enumerate() {
+        let mut result = match grading::classify(&a.expected, &a.answer) {
+            _ if a.correct => "ok".to_string(),
+            Some(kind) => kind.name().replace('_', " "),
+            None => "wrong".to_string(),
+        };
+        if a.suspect {
+            result.push_str(" (pasted or too fast)");
+        }
+        let _ = writeln!(
+            out,
+            "{:>3}  {:<10} {:<ew$} {:<ew$} {:>5}s  {}",
+            i + 1, a.item, a.expected, a.answer, locale::decimal(a.response_time, 1), result,
+            ew = expected_width
+        );
+    }
+    if session.answers.is_empty() {
+        let _ = writeln!(out, "(no answers recorded)");
+    }
+    out
+}
+
+fn list(stats: &UserStats) {
+    if stats.session_history.is_empty() {
+        println!("No sessions recorded yet.");
+        return;
+    }
+    for (i, s) in stats.session_history.iter().enumerate() {
+        match &s.external {
+            Some(external) => println!(
+                "{:>4}  {}  imported from {}",
+                i + 1, locale::date_time(&s.timestamp), external.source
+            ),
+            None => println!(
+                "{:>4}  {}  level {}  {}%  {} answers",
+                i + 1, locale::date_time(&s.timestamp), s.difficulty, locale::decimal(s.accuracy * 100.0, 1), s.answers.len()
+            ),
+        }
+    }
+}
+
+/// Prints the session for pasting, or writes it to `out`; a `.json` file gets the raw record.
+pub fn run(id: Option<&str>, out: Option<&Path>) -> Result<(), Box<dyn std::error::Error>> {
+    let stats = UserStats::load()?;
+    let Some(id) = id else {
+        list(&stats);
+        return Ok(());
+    };
+    let (number, session) = find(&stats, id)?;
+
+    match out {
+        Some(path) if path.extension().is_some_and(|e| e == "json") => {
+            let record = json!({
+                "app": "morse_code_learner",
+                "version": env!("CARGO_PKG_VERSION"),
+                "session_number": number,
+                "session": session,
+            });
+            fs::write(path, serde_json::to_string_pretty(&record)?)?;
+            println!("Session #{} written to {}", number, path.display());
+        }
+        Some(path) => {
+            fs::write(path, text(number, session))?;
+            println!("Session #{} written to {}", number, path.display());
+        }
+        None => print!("{}", text(number, session)),
+    }
+    Ok(())
+}
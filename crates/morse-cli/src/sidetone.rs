@@ -0,0 +1,17 @@
+use crate::config::AppConfig;
+
+/// Shows whether typed answers sound a sidetone, or turns it on or off.
+pub fn configure(enabled: Option<bool>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    if let Some(enabled) = enabled {
+        config.keyed_sidetone = enabled;
+        config.save()?;
+    }
+    println!("Sidetone while typing: {}{}",
+        if config.keyed_sidetone { "on" } else { "off" },
+        if config.keyed_sidetone { format!(" ({:.0} Hz)", config.pitch_hz()) } else { String::new() });
+    if enabled == Some(true) && !morse_audio::audio_available() {
+        println!("No audio device found; the sidetone will stay silent.");
+    }
+    Ok(())
+}
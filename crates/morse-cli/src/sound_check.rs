@@ -0,0 +1,69 @@
+use std::{thread, time::Duration};
+use morse_core::morse;
+use crate::{config::{AppConfig, MAX_PITCH_HZ, MIN_PITCH_HZ}, net, speed::DEFAULT_WPM};
+
+const SWEEP_SECS: f32 = 3.0;
+/// Silence between the parts of the check.
+const PAUSE: Duration = Duration::from_millis(700);
+/// Delay above which keyed sidetone and echoed answers start to feel late.
+const NOTICEABLE_LATENCY_MS: u128 = 100;
+const SAMPLE_TEXT: &str = "PARIS 73";
+
+fn heard(prompt: &str) -> bool {
+    let answer = net::answer(&format!("{} [Y/n] ", prompt)).unwrap_or_default();
+    !answer.starts_with('N')
+}
+
+/// Plays a sweep across the pitch range, then sample characters at the configured pitch and
+/// speed, and measures the output delay, so the sound can be set up before a timed session.
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load().unwrap_or_default();
+    let wpm = config.character_wpm.unwrap_or(DEFAULT_WPM);
+    println!("Output device: {}", config.output_device.as_deref().unwrap_or("the system default"));
+    println!("Volume:        {}%{}", config.volume(), if config.muted { " (muted)" } else { "" });
+    println!("Pitch:         {:.0} Hz", config.pitch_hz());
+    match config.effective_wpm.filter(|e| *e < wpm) {
+        Some(effective) => println!("Speed:         {} WPM characters, {} WPM overall", wpm, effective),
+        None => println!("Speed:         {} WPM", wpm),
+    }
+    if !morse_audio::audio_available() {
+        return Err("the audio device can't be opened; pick another with `config device`".into());
+    }
+    if config.muted {
+        println!("Sound is muted, so nothing will be heard. Turn it back on with `config mute off`.");
+    }
+
+    println!("\n1. A tone rising from {:.0} to {:.0} Hz...", MIN_PITCH_HZ, MAX_PITCH_HZ);
+    morse_audio::play_sweep(MIN_PITCH_HZ, MAX_PITCH_HZ, SWEEP_SECS);
+    let sweep = heard("Did you hear it all the way up, at a comfortable level?");
+    thread::sleep(PAUSE);
+
+    println!("\n2. {} at your pitch and speed...", SAMPLE_TEXT);
+    let spacing = config.spacing(None);
+    morse_audio::play_morse_code_at(&morse::encode(SAMPLE_TEXT), spacing, config.pitch_hz());
+    let sample = heard("Were the characters clear, with no clicks or dropouts?");
+    thread::sleep(PAUSE);
+
+    println!("\n3. Measuring the output delay...");
+    let latency = morse_audio::output_latency(config.pitch_hz()).map(|d| d.as_millis());
+    match latency {
+        Some(ms) => println!("About {} ms between a sound being played and it leaving the audio system.", ms),
+        None => println!("The delay couldn't be measured."),
+    }
+
+    println!();
+    if sweep && sample && latency.is_none_or(|ms| ms <= NOTICEABLE_LATENCY_MS) {
+        println!("All good, ready to practice.");
+        return Ok(());
+    }
+    if !sweep {
+        println!("- Nothing or too little heard: check the device with `config device` and the level with `config volume`.");
+    }
+    if !sample {
+        println!("- Unclear characters: a longer keying envelope (`config envelope 8`) softens clicks; a slower speed helps too.");
+    }
+    if latency.is_some_and(|ms| ms > NOTICEABLE_LATENCY_MS) {
+        println!("- The delay is large enough to notice when keying; wired headphones usually have less than Bluetooth ones.");
+    }
+    Ok(())
+}
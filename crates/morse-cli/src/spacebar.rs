@@ -0,0 +1,102 @@
+use std::time::{Duration, Instant};
+use morse_audio::Sidetone;
+use morse_core::{keyer::{self, KeyChange}, timing::TimingEvent};
+use morse_platform::terminal::{KeyAction, KeyReports};
+use crate::mic;
+
+const SPACE: u32 = 32;
+const ENTER: u32 = 13;
+const CTRL_C: u32 = 99;
+/// Silence after the keying that ends an answer, in dots at the sender's speed; a word gap
+/// is 7. Never shorter than `MIN_END`, so a slow hand can pause between characters.
+const END_UNITS: f32 = 12.0;
+const MIN_END: Duration = Duration::from_millis(1500);
+/// How long to wait for the first press or tone before taking the answer as empty.
+pub const GIVE_UP: Duration = Duration::from_secs(20);
+
+/// Silence that ends an answer keyed at `unit_ms`.
+pub fn end_after(unit_ms: f32) -> Duration {
+    Duration::from_secs_f32(END_UNITS * unit_ms / 1000.0).max(MIN_END)
+}
+
+/// Whether the terminal reports keys being let go, which holding them as a key needs.
+pub fn available() -> Result<(), String> {
+    KeyReports::enable().map(drop).map_err(|e| e.to_string())
+}
+
+/// Reads one answer keyed by holding the spacebar, sounding it on `sidetone` while it's
+/// held, and decodes it like keying heard through the microphone. Enter ends the answer
+/// early. `unit_ms` carries the sender's dot length from one answer to the next. Returns the
+/// code, the marks and spaces, and when the keying ended; `None` if the terminal stopped
+/// reporting keys.
+pub fn read_keying(sidetone: Option<&Sidetone>, unit_ms: &mut f32) -> Option<(String, Vec<TimingEvent>, Instant)> {
+    let mut keys = match KeyReports::enable() {
+        Ok(keys) => keys,
+        Err(e) => {
+            eprintln!("Error reading the spacebar: {}", e);
+            return None;
+        }
+    };
+    let end_after = end_after(*unit_ms);
+    let start = Instant::now();
+    let mut changes: Vec<KeyChange> = Vec::new();
+    let mut down = false;
+    let mut last = start;
+
+    loop {
+        let waited = last.elapsed();
+        let limit = if changes.is_empty() { GIVE_UP } else { end_after };
+        if !down && waited >= limit {
+            break;
+        }
+        // A held key can't end the answer, so just wait for it to come up.
+        let timeout = if down { end_after } else { limit - waited };
+        let report = match keys.next(timeout) {
+            Ok(report) => report,
+            Err(e) => {
+                eprintln!("Error reading the spacebar: {}", e);
+                return None;
+            }
+        };
+        let Some((key, at)) = report else {
+            continue;
+        };
+        match (key.code, key.action) {
+            (CTRL_C, _) if key.ctrl => {
+                drop(keys);
+                println!();
+                std::process::exit(130);
+            }
+            (ENTER, KeyAction::Press) if !down => break,
+            (SPACE, KeyAction::Press) | (SPACE, KeyAction::Release) => {
+                down = key.action == KeyAction::Press;
+                if let Some(sidetone) = sidetone {
+                    match down {
+                        true => sidetone.down(),
+                        false => sidetone.up(),
+                    }
+                }
+                changes.push(KeyChange { at_ms: at.duration_since(start).as_secs_f32() * 1000.0, down });
+                last = at;
+            }
+            _ => {}
+        }
+    }
+    drop(keys);
+    Some(decode(&changes, unit_ms, last))
+}
+
+/// Decodes and shows an answer keyed on a straight key, learning the sender's speed into
+/// `unit_ms`. `last` is when the keying ended.
+pub fn decode(changes: &[KeyChange], unit_ms: &mut f32, last: Instant) -> (String, Vec<TimingEvent>, Instant) {
+    let events = keyer::key_straight(changes);
+    if events.is_empty() {
+        println!("(nothing keyed)");
+        return (String::new(), events, last);
+    }
+    let mut decoder = mic::decode(&events, *unit_ms);
+    *unit_ms = decoder.unit_ms();
+    let code = decoder.take_code();
+    println!("{}  (keyed at {:.0} WPM)", code, 1200.0 / *unit_ms);
+    (code, events, last)
+}
@@ -0,0 +1,57 @@
+use serde_derive::{Serialize, Deserialize};
+
+/// Playback speed, and where the ramp starts, when no speed is set.
+pub const DEFAULT_WPM: f32 = 15.0;
+
+/// The policy that raises the playback speed while accuracy keeps up.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct SpeedConfig {
+    /// Older configs kept the character speed here; moved to `character_wpm` on load.
+    #[serde(skip_serializing)]
+    pub wpm: Option<f32>,
+    /// Raise the speed by `increment` after `sessions_required` sessions in a row at
+    /// `min_accuracy` or better, up to `max_wpm`.
+    pub ramp: bool,
+    pub increment: f32,
+    pub max_wpm: f32,
+    pub min_accuracy: f32,
+    pub sessions_required: u8,
+    /// Qualifying sessions in a row so far.
+    pub streak: u8,
+}
+
+impl Default for SpeedConfig {
+    fn default() -> Self {
+        SpeedConfig {
+            wpm: None,
+            ramp: false,
+            increment: 1.0,
+            max_wpm: 30.0,
+            min_accuracy: 0.9,
+            sessions_required: 3,
+            streak: 0,
+        }
+    }
+}
+
+impl SpeedConfig {
+    /// Counts a finished session toward the ramp, returning the speed that should replace
+    /// `current` when it was raised.
+    pub fn record_session(&mut self, current: f32, accuracy: f32) -> Option<f32> {
+        if !self.ramp {
+            return None;
+        }
+        if accuracy < self.min_accuracy {
+            self.streak = 0;
+            return None;
+        }
+        self.streak += 1;
+        if self.streak < self.sessions_required {
+            return None;
+        }
+        self.streak = 0;
+        let raised = (current + self.increment).min(self.max_wpm);
+        (raised > current).then_some(raised)
+    }
+}
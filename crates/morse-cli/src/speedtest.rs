@@ -0,0 +1,92 @@
+use chrono::Local;
+use morse_core::{exam, scoring::{Rule, Scorecard}};
+use morse_stats::{SpeedTestHistory, SpeedTestResult};
+use crate::{cli::Output, config::AppConfig, exam::{copy_while_sending, CHARS_PER_WPM}, layout::{self, Align, Table}, locale, net::Station, scoring};
+
+/// Every test takes this long, so results at the same speed can be compared.
+pub const TEST_MINUTES: f32 = 2.0;
+/// What a test is passed by unless configured otherwise.
+const DEFAULT_RULES: [Rule; 2] = [Rule::MinAccuracy(90.0), Rule::MaxConsecutiveErrors(3)];
+
+fn rule_list(rules: &[Rule]) -> String {
+    rules.iter().map(Rule::to_string).collect::<Vec<_>>().join(",")
+}
+
+/// Sends two minutes of plain language at exactly `wpm` while the copy is typed in, then
+/// prints the result like a certificate and adds it to the speed test history.
+pub fn run(wpm: f32, rules: Option<&str>, output: Output) -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load().unwrap_or_default();
+    let rules = scoring::resolve(rules, config.scoring.speedtest.as_deref(), &DEFAULT_RULES)?;
+    let mut history = SpeedTestHistory::load()?;
+    let station = Station::at_speed(output, wpm, config);
+    let text = exam::qso_text((wpm * CHARS_PER_WPM * TEST_MINUTES) as usize, &mut rand::rng());
+
+    println!("Speed test: {:.0} minutes of plain language at {} WPM.", TEST_MINUTES, wpm);
+    println!("To pass: {}.", scoring::describe(&rules));
+    let Some((copy, minutes)) = copy_while_sending(station, &text) else {
+        return Ok(());
+    };
+    let card = Scorecard::compare(&text, &copy, minutes.unwrap_or(TEST_MINUTES));
+    let verdict = card.judge(&rules);
+    let result = SpeedTestResult {
+        timestamp: Local::now().fixed_offset(),
+        wpm,
+        minutes: card.minutes,
+        sent: card.sent,
+        errors: card.errors,
+        solid: card.solid,
+        rules: rule_list(&rules),
+        passed: verdict.iter().all(|(_, passed)| *passed),
+    };
+
+    println!("\nSent:   {}", text);
+    println!("Copied: {}", copy);
+    println!("\n{}", layout::banner("SPEED TEST RESULT", "SPEED TEST"));
+    println!("Date:               {}", locale::date_time(&result.timestamp));
+    println!("Speed:              {} WPM for {} minutes", wpm, locale::decimal(result.minutes, 1));
+    println!("Characters sent:    {}", result.sent);
+    println!("Characters copied:  {} ({}%)", result.copied(), locale::decimal((1.0 - result.error_rate()) * 100.0, 1));
+    println!("{}", layout::wrap(&format!("Errors:             {} ({}% error rate, {} per minute)",
+        result.errors, locale::decimal(result.error_rate() * 100.0, 1), locale::decimal(card.errors_per_minute(), 1)), 4));
+    println!("Longest solid copy: {} characters", result.solid);
+    for (rule, passed) in &verdict {
+        println!("  {} {}", if *passed { "✓" } else { "✗" }, rule.describe());
+    }
+    println!("Result:             {}", if result.passed { "PASS" } else { "FAIL" });
+    println!("{}", layout::rule('='));
+
+    history.tests.push(result);
+    history.save()?;
+    if let Some(best) = history.best_pass() {
+        println!("Fastest pass so far: {} WPM on {}.", best.wpm, locale::date_time(&best.timestamp));
+    }
+    Ok(())
+}
+
+/// Lists past speed tests, oldest first.
+pub fn history() -> Result<(), Box<dyn std::error::Error>> {
+    let history = SpeedTestHistory::load()?;
+    if history.tests.is_empty() {
+        println!("No speed tests yet. Take one with `speedtest --wpm 15`.");
+        return Ok(());
+    }
+    let mut table = Table::new(&[
+        ("Date", Align::Left), ("WPM", Align::Right), ("Copied", Align::Right),
+        ("Errors", Align::Right), ("Solid", Align::Right), ("Result", Align::Left),
+    ]);
+    for test in &history.tests {
+        table.row(vec![
+            locale::date_time(&test.timestamp),
+            test.wpm.to_string(),
+            format!("{}%", locale::decimal((1.0 - test.error_rate()) * 100.0, 1)),
+            format!("{}%", locale::decimal(test.error_rate() * 100.0, 1)),
+            test.solid.to_string(),
+            if test.passed { "PASS" } else { "FAIL" }.to_string(),
+        ]);
+    }
+    table.print();
+    if let Some(best) = history.best_pass() {
+        println!("\nFastest pass: {} WPM.", best.wpm);
+    }
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use chrono::Local;
+use chrono_tz::Tz;
+use serde_derive::{Serialize, Deserialize};
+use morse_stats::{Streak, UserStats};
+use crate::config::AppConfig;
+
+/// How practice days are counted for the streak.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct StreakConfig {
+    /// IANA time zone such as `Europe/Berlin` the days are counted in; the system's when unset.
+    pub timezone: Option<String>,
+    /// Hour the practice day starts at, so late-night sessions count toward the day before.
+    pub day_starts_at: u32,
+}
+
+impl StreakConfig {
+    pub fn streak(&self, stats: &UserStats) -> Result<Streak, String> {
+        let now = Local::now();
+        match &self.timezone {
+            Some(name) => Ok(stats.streak(&now, &parse_timezone(name)?, self.day_starts_at)),
+            None => Ok(stats.streak(&now, &Local, self.day_starts_at)),
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Days start at {}:00 in {}.",
+            self.day_starts_at,
+            self.timezone.as_deref().unwrap_or("the system time zone")
+        )
+    }
+}
+
+fn parse_timezone(name: &str) -> Result<Tz, String> {
+    name.parse().map_err(|_| format!("unknown time zone '{}', expected a name like Europe/Berlin", name))
+}
+
+/// One-line streak shown after each session.
+pub fn summary(streak: &Streak) -> Option<String> {
+    match streak.current {
+        0 => None,
+        1 => Some("🔥 First day of a new streak.".to_string()),
+        days => Some(format!("🔥 {}-day streak (longest {}).", days, streak.longest)),
+    }
+}
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load().unwrap_or_default();
+    let stats = UserStats::load()?;
+    let streak = config.streak.streak(&stats)?;
+    println!("Current streak: {} day(s)", streak.current);
+    println!("Longest streak: {} day(s)", streak.longest);
+    if streak.current > 0 && !streak.practiced_today {
+        println!("Practice today to keep it going.");
+    }
+    println!("{}", config.streak.describe());
+    Ok(())
+}
+
+/// Shows how practice days are counted, or changes the time zone (`system` for the
+/// system's) or the hour days start at.
+pub fn configure(timezone: Option<String>, day_starts_at: Option<u32>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    if timezone.is_none() && day_starts_at.is_none() {
+        println!("{}", config.streak.describe());
+        return Ok(());
+    }
+    if let Some(name) = timezone {
+        config.streak.timezone = match name.as_str() {
+            "system" => None,
+            _ => Some(parse_timezone(&name)?.name().to_string()),
+        };
+    }
+    if let Some(hour) = day_starts_at {
+        config.streak.day_starts_at = hour;
+    }
+    config.save()?;
+    println!("{}", config.streak.describe());
+    Ok(())
+}
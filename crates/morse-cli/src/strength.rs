@@ -0,0 +1,60 @@
+use std::collections::BTreeMap;
+use chrono::Local;
+use morse_stats::{Strength, UserStats};
+use crate::{config::AppConfig, layout::{Align, Table}, locale};
+
+const BAR_WIDTH: usize = 20;
+
+/// Characters from `chars` from the weakest to the strongest, leaving out ones never practiced.
+pub fn weakest(strengths: &BTreeMap<char, Strength>, chars: &[char]) -> Vec<char> {
+    let mut known: Vec<(char, f32)> = chars.iter()
+        .filter_map(|c| strengths.get(c).map(|s| (*c, s.strength)))
+        .collect();
+    known.sort_by(|a, b| a.1.total_cmp(&b.1));
+    known.into_iter().map(|(c, _)| c).collect()
+}
+
+pub fn bar(strength: f32) -> String {
+    let filled = (strength * BAR_WIDTH as f32).round() as usize;
+    format!("{}{}", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled))
+}
+
+/// Shows how strongly each character learned so far is remembered.
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load().unwrap_or_default();
+    let stats = UserStats::load()?;
+    let now = Local::now();
+    let strengths = stats.strengths(&now);
+    let chars = config.progression().chars_through(config.difficulty_level);
+
+    let mut table = Table::new(&[
+        ("Char", Align::Left), ("Strength", Align::Left), ("Half-life", Align::Right), ("Practiced", Align::Left),
+    ]);
+    for c in &chars {
+        let Some(s) = strengths.get(c) else {
+            table.row(vec![c.to_string(), "not practiced yet".to_string()]);
+            continue;
+        };
+        let days = (now.fixed_offset() - s.last_practiced).num_days();
+        table.row(vec![
+            c.to_string(),
+            format!("{} {:>3.0}%", bar(s.strength), s.strength * 100.0),
+            format!("{}d", locale::decimal(s.half_life_days, 1)),
+            format!("{}{}",
+                locale::date(s.last_practiced.with_timezone(&Local).date_naive()),
+                if days > 0 { format!(" ({} days ago)", days) } else { String::new() }),
+        ]);
+    }
+    table.print();
+
+    let fading: Vec<String> = weakest(&strengths, &chars).into_iter()
+        .filter(|c| strengths[c].is_fading())
+        .map(|c| c.to_string())
+        .collect();
+    if fading.is_empty() {
+        println!("\nNothing is fading, your characters are fresh.");
+    } else {
+        println!("\nFading: {}. Review stages practice these first.", fading.join(" "));
+    }
+    Ok(())
+}
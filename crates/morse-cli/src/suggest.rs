@@ -0,0 +1,107 @@
+use std::io::{self, Write};
+use chrono::{DateTime, Local};
+use morse_core::{scheduler, weakness};
+use morse_platform::terminal;
+use morse_stats::{SpeedTestHistory, UserStats};
+use crate::{cli::PracticeArgs, config::AppConfig, goals, input, strength, template};
+
+/// Days away after which a retention check is suggested.
+pub const RETENTION_GAP_DAYS: i64 = 3;
+/// How many recent character answers weak characters are judged by.
+const RECENT_CHAR_ANSWERS: usize = 200;
+/// Weak characters in a focus session.
+const FOCUS_CHARS: usize = 3;
+
+pub struct Suggestion {
+    pub title: String,
+    pub reasons: Vec<String>,
+    pub options: PracticeArgs,
+}
+
+/// Today's session based on recent stats: a retention check after a break or when characters
+/// are fading, work toward the goal furthest behind for its priority, and a focus on
+/// often-missed characters, or `None` when a regular session is the best choice.
+pub fn suggest(stats: &UserStats, tests: &SpeedTestHistory, config: &AppConfig, now: DateTime<Local>) -> Option<Suggestion> {
+    let last = stats.session_history.last()?;
+    let mut titles = Vec::new();
+    let mut reasons = Vec::new();
+    let mut options = PracticeArgs::default();
+
+    let days = (now.date_naive() - last.timestamp.date_naive()).num_days();
+    let strengths = stats.strengths(&now);
+    let fading: Vec<String> = strength::weakest(&strengths, &config.progression().chars_through(config.difficulty_level))
+        .into_iter()
+        .filter(|c| strengths[c].is_fading() && config.banned.allows_char(*c))
+        .map(|c| c.to_string())
+        .collect();
+    if days >= RETENTION_GAP_DAYS || !fading.is_empty() {
+        titles.push("Retention check".to_string());
+        if days >= RETENTION_GAP_DAYS {
+            reasons.push(format!("{} days since your last session", days));
+        }
+        if !fading.is_empty() {
+            reasons.push(format!("fading: {}", fading.join(" ")));
+        }
+        options.pipeline = Some("review:2,chars:3".to_string());
+    }
+
+    if let Some((goal, progress)) = goals::next(config, stats, tests, &now) {
+        titles.push(goal.describe());
+        reasons.push(format!("goal {:.0}% done, {}", progress.fraction() * 100.0, progress.detail));
+        let toward = goals::session(&goal, &progress, stats, &now);
+        options.copy = toward.copy;
+        options.echo = toward.echo;
+        options.wpm = toward.wpm;
+        options.focus = toward.focus;
+    }
+
+    let answers: Vec<(&str, &str)> = stats.session_history.iter().rev()
+        .flat_map(|s| s.answers.iter().rev())
+        .filter(|a| a.item.chars().count() == 1 && scheduler::decode_code(&a.item).is_none())
+        .take(RECENT_CHAR_ANSWERS)
+        .map(|a| (a.item.as_str(), a.answer.as_str()))
+        .collect();
+    let letter_stats = weakness::letter_stats(answers);
+    let weak: Vec<char> = weakness::weak_letters(&letter_stats).into_iter()
+        .filter(|c| config.banned.allows_char(*c))
+        .take(FOCUS_CHARS)
+        .collect();
+    if !weak.is_empty() && options.focus.is_none() {
+        let chars: Vec<String> = weak.iter().map(|c| c.to_string()).collect();
+        titles.push(format!("{} focus", chars.join("/")));
+        reasons.push(format!("recently missed {}", weak.iter()
+            .map(|c| format!("{} {:.0}% of the time", c, letter_stats[c].error_rate() * 100.0))
+            .collect::<Vec<_>>()
+            .join(", ")));
+        options.focus = Some(chars.concat());
+    }
+
+    if titles.is_empty() {
+        return None;
+    }
+    Some(Suggestion {
+        title: titles.join(" + "),
+        reasons,
+        options,
+    })
+}
+
+/// Offers today's suggestion on an interactive terminal, returning the options to practice
+/// with if it's accepted with Enter or `y`.
+pub fn offer() -> Option<PracticeArgs> {
+    if !terminal::is_interactive() {
+        return None;
+    }
+    let config = AppConfig::load().ok()?;
+    let stats = UserStats::load().ok()?;
+    let tests = SpeedTestHistory::load().ok()?;
+    let suggestion = suggest(&stats, &tests, &config, Local::now())?;
+
+    println!("Suggested for today: {} ({})", suggestion.title, suggestion.reasons.join("; "));
+    println!("  practice {}", template::describe(&suggestion.options));
+    print!("Press Enter to accept or any other key for a regular session: ");
+    io::stdout().flush().ok()?;
+
+    let key = input::read_key()?;
+    matches!(key, '\n' | 'y' | 'Y').then_some(suggestion.options)
+}
@@ -0,0 +1,134 @@
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+use serde_derive::{Serialize, Deserialize};
+use serde_json::Value;
+use morse_stats::{DeviceSession, UserStats};
+use crate::config::AppConfig;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Connection to a `morse_sync_server`, configured in the `[sync]` table of `morse_config.toml`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SyncConfig {
+    #[serde(default)]
+    pub server: Option<String>,
+    /// Name this machine's sessions are filed under, defaults to the host name
+    #[serde(default)]
+    pub device: Option<String>,
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+impl SyncConfig {
+    fn device(&self) -> String {
+        self.device.clone()
+            .or_else(|| std::env::var("HOSTNAME").ok())
+            .or_else(|| std::env::var("COMPUTERNAME").ok())
+            .filter(|d| !d.trim().is_empty())
+            .unwrap_or_else(|| "this-device".to_string())
+    }
+}
+
+struct Server {
+    host: String,
+    base_path: String,
+    token: Option<String>,
+}
+
+impl Server {
+    fn new(url: &str, token: Option<String>) -> Result<Self, Box<dyn std::error::Error>> {
+        let rest = url.strip_prefix("http://")
+            .ok_or("only plain http:// server URLs are supported")?;
+        let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let host = if host.contains(':') { host.to_string() } else { format!("{}:80", host) };
+        Ok(Server {
+            host,
+            base_path: format!("/{}", path.trim_end_matches('/')).trim_end_matches('/').to_string(),
+            token,
+        })
+    }
+
+    fn request(&self, method: &str, path: &str, body: &[u8]) -> Result<Value, Box<dyn std::error::Error>> {
+        let mut stream = TcpStream::connect(&self.host)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+
+        let mut head = format!(
+            "{} {}{} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+            method, self.base_path, path, self.host, body.len()
+        );
+        if let Some(token) = &self.token {
+            head.push_str(&format!("Authorization: Bearer {}\r\n", token));
+        }
+        head.push_str("\r\n");
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(body)?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response)?;
+        let split = response.windows(4).position(|w| w == b"\r\n\r\n")
+            .ok_or("malformed response from server")?;
+        let status_line = String::from_utf8_lossy(&response[..split]);
+        let status: u16 = status_line.split_whitespace().nth(1).and_then(|s| s.parse().ok())
+            .ok_or("malformed response from server")?;
+        let body: Value = serde_json::from_slice(&response[split + 4..]).unwrap_or(Value::Null);
+        if status != 200 {
+            let message = body["error"].as_str().unwrap_or("request failed");
+            return Err(format!("server returned {}: {}", status, message).into());
+        }
+        Ok(body)
+    }
+}
+
+fn connect(server: Option<String>, config: &SyncConfig) -> Result<Server, Box<dyn std::error::Error>> {
+    let url = server.or_else(|| config.server.clone())
+        .ok_or("no sync server configured; pass --server or set server in the [sync] table of morse_config.toml")?;
+    Server::new(&url, config.token.clone())
+}
+
+fn query_value(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Uploads every local session; the server ignores ones it already has.
+pub fn push(server: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load().unwrap_or_default();
+    let server = connect(server, &config.sync)?;
+    let stats = UserStats::load()?;
+    let device = config.sync.device();
+
+    let body = serde_json::to_vec(&stats.session_history)?;
+    let reply = server.request("POST", &format!("/api/sessions?device={}", query_value(&device)), &body)?;
+    println!(
+        "Uploaded {} new of {} sessions as '{}'.",
+        reply["added"].as_u64().unwrap_or(0),
+        stats.session_history.len(),
+        device
+    );
+    Ok(())
+}
+
+/// Merges sessions uploaded by other devices into the local history.
+pub fn pull(server: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load().unwrap_or_default();
+    let server = connect(server, &config.sync)?;
+    let device = config.sync.device();
+
+    let reply = server.request("GET", &format!("/api/sessions?exclude={}", query_value(&device)), &[])?;
+    let remote: Vec<DeviceSession> = serde_json::from_value(reply)?;
+    let mut stats = UserStats::load()?;
+    let added = stats.merge_sessions(remote.into_iter().map(|s| s.session));
+    if added > 0 {
+        stats.save()?;
+    }
+    println!("Added {} sessions from other devices.", added);
+    Ok(())
+}
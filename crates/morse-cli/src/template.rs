@@ -0,0 +1,81 @@
+use crate::{cli::{self, PracticeArgs}, config::AppConfig};
+
+/// Applies `--template` and `--save-template`: a template replaces the flags, and the flags
+/// are stored when saving one.
+pub fn resolve(mut options: PracticeArgs) -> Result<PracticeArgs, Box<dyn std::error::Error>> {
+    if let Some(name) = &options.template {
+        let config = AppConfig::load()?;
+        return config.templates.get(name)
+            .cloned()
+            .ok_or_else(|| format!("no template named '{}' (see `config templates`)", name).into());
+    }
+
+    if let Some(name) = options.save_template.take() {
+        let mut config = AppConfig::load()?;
+        let replaced = config.templates.insert(name.clone(), options.clone()).is_some();
+        config.save()?;
+        println!("{} template '{}'.", if replaced { "Updated" } else { "Saved" }, name);
+    }
+    Ok(options)
+}
+
+/// The flags a template stands for, as they would be typed.
+pub fn describe(options: &PracticeArgs) -> String {
+    let mut flags = Vec::new();
+    if options.pseudo_words {
+        flags.push("--pseudo-words".to_string());
+    }
+    if let Some(cover) = &options.cover {
+        flags.push(format!("--cover {} --min-count {}", cover, options.min_count));
+    }
+    if options.logbook {
+        flags.push("--logbook".to_string());
+    }
+    if let Some(drill) = options.drill {
+        flags.push(format!("--drill {}", cli::value_name(&drill)));
+    }
+    if let Some(subset) = options.subset {
+        flags.push(format!("--subset {}", cli::value_name(&subset)));
+    }
+    if let Some(focus) = &options.focus {
+        flags.push(format!("--focus {}", focus));
+    }
+    if options.copy {
+        flags.push("--copy".to_string());
+    }
+    if options.echo {
+        flags.push("--echo".to_string());
+    }
+    if let Some(wpm) = options.wpm {
+        flags.push(format!("--wpm {}", wpm));
+    }
+    if let Some(pipeline) = &options.pipeline {
+        flags.push(format!("--pipeline {}", pipeline));
+    }
+    if let Some(items) = options.items {
+        flags.push(format!("--items {}", items));
+    }
+    flags.push(format!("--output {}", cli::value_name(&options.output)));
+    flags.join(" ")
+}
+
+pub fn list() -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load()?;
+    if config.templates.is_empty() {
+        println!("No templates yet. Save one with `practice <flags> --save-template <name>`.");
+    }
+    for (name, options) in &config.templates {
+        println!("{:<20} {}", name, describe(options));
+    }
+    Ok(())
+}
+
+pub fn remove(name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    if config.templates.remove(name).is_none() {
+        return Err(format!("no template named '{}'", name).into());
+    }
+    config.save()?;
+    println!("Removed template '{}'.", name);
+    Ok(())
+}
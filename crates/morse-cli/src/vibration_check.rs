@@ -0,0 +1,66 @@
+use std::{thread, time::Duration};
+use rand::seq::IndexedRandom;
+use morse_audio::vibration;
+use morse_core::morse;
+use crate::{config::AppConfig, net};
+
+/// Dot lengths tried, from about 6 to 30 WPM.
+const UNITS_MS: [f32; 8] = [200.0, 150.0, 120.0, 100.0, 80.0, 60.0, 50.0, 40.0];
+/// Characters felt at each dot length.
+const ROUND: usize = 3;
+/// Characters of a round that must be copied for its dot length to count as readable.
+const PASS: usize = 2;
+/// Characters felt when the learner's level has too few of its own.
+const FALLBACK_CHARS: &str = "ETIMANSO";
+/// Time to take a hand off the keyboard before each character.
+const PAUSE: Duration = Duration::from_millis(700);
+
+/// Vibrates characters at ever shorter dot lengths until they can no longer be told apart,
+/// and keeps the shortest that could as the speed vibration keys at.
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = AppConfig::load()?;
+    let Some(port) = config.vibration_port.clone() else {
+        return Err("no vibration device set; pick one with `config vibration`".into());
+    };
+    let mut chars = config.progression().chars_through(config.difficulty_level);
+    if chars.len() < ROUND {
+        chars = FALLBACK_CHARS.chars().collect();
+    }
+    println!("Vibration device: {}", port);
+    println!("Each character is felt once; type what it was, or press Enter if you couldn't tell.");
+
+    let mut rng = rand::rng();
+    let mut readable = None;
+    for unit_ms in UNITS_MS {
+        println!("\n{:.0} ms a dot, about {:.0} WPM:", unit_ms, 1200.0 / unit_ms);
+        let mut copied = 0;
+        for _ in 0..ROUND {
+            let c = *chars.choose(&mut rng).unwrap_or(&'E');
+            thread::sleep(PAUSE);
+            vibration::vibrate_code(&port, &morse::encode(&c.to_string()), unit_ms)
+                .map_err(|e| format!("can't vibrate on {}: {}", port, e))?;
+            let Some(answer) = net::answer("What did you feel: ") else {
+                return Ok(());
+            };
+            if answer == c.to_string() {
+                copied += 1;
+            } else {
+                println!("It was {} ({})", c, morse::encode(&c.to_string()));
+            }
+        }
+        if copied < PASS {
+            break;
+        }
+        readable = Some(unit_ms);
+    }
+
+    let Some(unit_ms) = readable else {
+        println!("\nEven the slowest speed was hard to read. Check that the device is held firmly, or set a slower speed by hand with `vibration_unit_ms` in the config.");
+        return Ok(());
+    };
+    config.vibration_unit_ms = Some(unit_ms);
+    config.save()?;
+    println!("\nVibration now keys at {:.0} ms a dot, about {:.0} WPM.", unit_ms, 1200.0 / unit_ms);
+    println!("Copy by touch with `practice --copy --output vibration`.");
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use std::{sync::Mutex, time::{Duration, Instant}};
+use morse_core::{keyer::{KeyerMode, KeyerSettings}, morse, timing::{Spacing, TimingEvent}};
+use morse_platform::winkeyer::{PaddleMode, Reply, WinKeyer};
+use crate::spacebar;
+
+/// Extra time allowed on top of how long an item takes at the set speed before sending is
+/// given up on.
+const SEND_MARGIN: Duration = Duration::from_secs(2);
+
+/// Opens the WinKeyer on `port` for the host, at the character speed of `spacing` and
+/// with the paddles set up like the emulated keyer.
+pub fn open(port: &str, spacing: Spacing, settings: KeyerSettings) -> Result<WinKeyer, String> {
+    let mut keyer = WinKeyer::open(port).map_err(|e| e.to_string())?;
+    let mode = match settings.mode {
+        KeyerMode::IambicA => PaddleMode::IambicA,
+        KeyerMode::IambicB => PaddleMode::IambicB,
+        KeyerMode::Bug => PaddleMode::Bug,
+    };
+    keyer.set_mode(mode, settings.reverse).map_err(|e| e.to_string())?;
+    keyer.set_speed(1200.0 / spacing.unit_ms).map_err(|e| e.to_string())?;
+    Ok(keyer)
+}
+
+/// Sends `morse_code` on the keyer's sidetone and key line, returning once it's sent.
+pub fn play(keyer: &Mutex<WinKeyer>, morse_code: &str, spacing: Spacing) {
+    let mut keyer = keyer.lock().unwrap();
+    let events = morse_core::timing::timings_with_spacing(morse_code, spacing);
+    let length: f32 = events.iter().map(|e| match e {
+        TimingEvent::Mark(ms) | TimingEvent::Space(ms) => *ms,
+    }).sum();
+    let limit = Duration::from_secs_f32(length / 1000.0) + SEND_MARGIN;
+    if let Err(e) = keyer.send(&morse::decode(morse_code), limit) {
+        eprintln!("Error sending on the WinKeyer: {}", e);
+    }
+}
+
+/// Reads one answer keyed on the WinKeyer's paddles, as the characters it echoes back. The
+/// answer ends after the same pause as on the spacebar. Returns the code, no timings, as
+/// the keyer times the elements itself, and when the last character came.
+pub fn read_answer(keyer: &Mutex<WinKeyer>, unit_ms: f32) -> Option<(String, Vec<TimingEvent>, Instant)> {
+    let mut keyer = keyer.lock().unwrap();
+    let end_after = spacebar::end_after(unit_ms);
+    let mut text = String::new();
+    let mut last = Instant::now();
+    loop {
+        let limit = if text.is_empty() { spacebar::GIVE_UP } else { end_after };
+        let Some(timeout) = limit.checked_sub(last.elapsed()) else {
+            break;
+        };
+        match keyer.next(timeout) {
+            Ok(Some(Reply::Echo(c))) if !c.is_whitespace() => {
+                text.push(c.to_ascii_uppercase());
+                last = Instant::now();
+            }
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Error reading the WinKeyer: {}", e);
+                return None;
+            }
+        }
+    }
+    if text.is_empty() {
+        println!("(nothing keyed)");
+        return Some((String::new(), Vec::new(), last));
+    }
+    let code = morse::encode_word(&text);
+    println!("{}  ({})", code, text);
+    Some((code, Vec::new(), last))
+}
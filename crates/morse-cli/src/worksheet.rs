@@ -0,0 +1,58 @@
+use std::{fs, path::Path};
+use morse_core::{material, morse};
+use crate::{
+    config::AppConfig,
+    pdf::{Document, Font, Page, PAGE_WIDTH},
+};
+
+const MARGIN: f32 = 56.0;
+const FIRST_ROW: f32 = 150.0;
+const ROW_HEIGHT: f32 = 32.0;
+const MAX_ITEMS: usize = 20;
+
+pub struct WorksheetOptions {
+    pub level: Option<u8>,
+    pub groups: usize,
+    pub words: usize,
+}
+
+fn header(page: &mut Page, title: &str, chars: &[char]) {
+    page.text(MARGIN, 70.0, Font::HelveticaBold, 18.0, title);
+    page.text(MARGIN, 92.0, Font::Helvetica, 10.0, &format!("Characters: {}", chars.iter().collect::<String>()));
+}
+
+pub fn run(options: WorksheetOptions, out: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load().unwrap_or_default();
+    let level = options.level.unwrap_or(config.difficulty_level).clamp(1, 8);
+    let progression = config.progression();
+    let chars = progression.chars_through(level);
+    
+    let mut rng = rand::rng();
+    let mut items = material::groups_and_words(&chars, &progression.common_words, options.groups, options.words, &mut rng);
+    items.truncate(MAX_ITEMS);
+
+    let mut sheet = Page::default();
+    header(&mut sheet, &format!("Morse Code Worksheet - Level {}", level), &chars);
+    sheet.text(MARGIN, 112.0, Font::Helvetica, 10.0, "Write the text for each line of code. Name: ____________  Date: __________");
+    for (i, item) in items.iter().enumerate() {
+        let y = FIRST_ROW + i as f32 * ROW_HEIGHT;
+        sheet.text(MARGIN, y, Font::Helvetica, 10.0, &format!("{}.", i + 1));
+        sheet.text(MARGIN + 24.0, y, Font::Courier, 11.0, &morse::encode_word(item));
+        sheet.line(MARGIN + 24.0, y + 16.0, PAGE_WIDTH - MARGIN, y + 16.0);
+    }
+
+    let mut key = Page::default();
+    header(&mut key, &format!("Answer Key - Level {}", level), &chars);
+    for (i, item) in items.iter().enumerate() {
+        let y = FIRST_ROW + i as f32 * ROW_HEIGHT;
+        key.text(MARGIN, y, Font::Helvetica, 10.0, &format!("{}.", i + 1));
+        key.text(MARGIN + 24.0, y, Font::Courier, 12.0, item);
+    }
+
+    let mut document = Document::default();
+    document.add_page(sheet);
+    document.add_page(key);
+    fs::write(out, document.to_bytes())?;
+    println!("Worksheet with {} items written to {}", items.len(), out.display());
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use chrono::{NaiveDate, NaiveTime};
+use morse_cli::import::{self, ImportSource, ParsedResult};
+
+#[test]
+fn morserunner_hst_results_are_read_a_run_per_line() {
+    let results = import::parse(ImportSource::MorseRunner, include_str!("data/HstResults.txt"));
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0], ParsedResult {
+        date: NaiveDate::from_ymd_opt(2025, 3, 1),
+        time: NaiveTime::from_hms_opt(13, 28, 0),
+        score: Some(12345),
+        wpm: Some(30.0),
+        ..ParsedResult::default()
+    });
+    // A run without the operator's name still has its fields counted from the right.
+    assert_eq!((results[2].score, results[2].wpm), (Some(11020), Some(28.0)));
+    assert!(import::parse(ImportSource::MorseRunner, "DL1ABC ALEX 30 12345\nnot a result").is_empty());
+}
+
+#[test]
+fn rufzxp_reports_are_read_an_attempt_at_a_time() {
+    let results = import::parse(ImportSource::RufzXp, include_str!("data/rufzxp.txt"));
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0], ParsedResult {
+        date: NaiveDate::from_ymd_opt(2025, 3, 1),
+        time: NaiveTime::from_hms_opt(13, 28, 0),
+        score: Some(188),
+        wpm: Some(33.0),
+        correct: Some((2, 4)),
+        seconds: None,
+    });
+    assert_eq!(results[1].date, NaiveDate::from_ymd_opt(2025, 3, 2));
+    assert_eq!(results[1].correct, Some((2, 2)));
+}
+
+#[test]
+fn result_files_are_told_apart_by_name_or_banner() {
+    let detect = |path: &str, text: &str| ImportSource::detect(path.as_ref(), text);
+    assert_eq!(detect("HstResults.txt", ""), Some(ImportSource::MorseRunner));
+    assert_eq!(detect("attempt.txt", "RufzXP 1.0  DL1ABC"), Some(ImportSource::RufzXp));
+    assert_eq!(detect("scores.txt", "12345"), None);
+}
@@ -0,0 +1,26 @@
+use morse_cli::{config::AppConfig, probation::{self, Probation, ProbationOutcome, PROBATION_SESSIONS}};
+
+fn promoted() -> AppConfig {
+    AppConfig {
+        difficulty_level: 3,
+        probation: Some(Probation::new(2, vec!['K', 'M'], 0.9)),
+        ..AppConfig::default()
+    }
+}
+
+#[test]
+fn a_session_without_answers_leaves_probation_alone() {
+    let mut config = promoted();
+    assert_eq!(probation::settle(&mut config, 0.0, 0), None);
+    assert_eq!(config.difficulty_level, 3);
+    assert_eq!(config.probation.map(|p| p.sessions_left), Some(PROBATION_SESSIONS));
+}
+
+#[test]
+fn collapsed_accuracy_takes_the_promotion_back() {
+    let mut config = promoted();
+    assert_eq!(probation::settle(&mut config, 0.5, 10), Some(ProbationOutcome::Reverted { from: 3 }));
+    assert_eq!(config.difficulty_level, 2);
+    assert_eq!(config.known_chars, vec!['K', 'M']);
+    assert!(config.probation.is_none());
+}
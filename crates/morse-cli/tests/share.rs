@@ -0,0 +1,35 @@
+use chrono::DateTime;
+use morse_cli::share;
+use morse_stats::{AnswerRecord, LearningSession};
+
+#[test]
+fn shared_results_give_accuracy_as_a_percentage() {
+    let session = LearningSession {
+        timestamp: DateTime::parse_from_rfc3339("2025-06-14T18:30:00+02:00").unwrap(),
+        duration: 125,
+        chars_practiced: vec!['K'],
+        words_practiced: Vec::new(),
+        accuracy: 0.9,
+        difficulty: 1,
+        answers: vec![AnswerRecord {
+            item: "K".into(),
+            expected: "-.-".into(),
+            answer: "-.-".into(),
+            correct: true,
+            response_time: 1.2,
+            ..AnswerRecord::default()
+        }],
+        settings: None,
+        external: None,
+        head_copy: Vec::new(),
+        passive: false,
+        sending: None,
+        ending: None,
+        outside_progression: false,
+    };
+    let text = share::text(1, &session);
+    let result = text.lines().find(|l| l.starts_with("Result:")).unwrap();
+    // The decimal point follows the locale, so only the whole percent is checked.
+    assert!(result.starts_with("Result:   90"), "{}", result);
+    assert!(result.contains("% correct in 2m 5s"), "{}", result);
+}
@@ -0,0 +1,74 @@
+use std::collections::BTreeSet;
+use crate::{morse, progression::ProgressionSystem};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CodeAnalysis {
+    pub dots: usize,
+    pub dashes: usize,
+    pub letters: usize,
+    pub words: usize,
+    /// Nominal length in dot units, including element, letter and word gaps.
+    pub units: usize,
+    pub chars_used: BTreeSet<char>,
+    pub unknown_codes: Vec<String>,
+}
+
+/// PARIS is 50 units long including its trailing word gap.
+pub const UNITS_PER_WORD: f32 = 50.0;
+
+impl CodeAnalysis {
+    pub fn decoded(&self) -> bool {
+        self.unknown_codes.is_empty()
+    }
+
+    /// Standard words contained in the code, for computing effective WPM.
+    pub fn standard_words(&self) -> f32 {
+        self.units as f32 / UNITS_PER_WORD
+    }
+
+    /// Lowest curriculum level at which every character used here has been introduced.
+    pub fn required_level(&self, progression: &ProgressionSystem) -> Option<u8> {
+        let mut required = 0;
+        for c in &self.chars_used {
+            let level = progression.levels.iter()
+                .find(|l| l.chars_to_learn.contains(c))?;
+            required = required.max(level.level);
+        }
+        Some(required).filter(|l| *l > 0)
+    }
+}
+
+/// Analyzes space-separated codes with words separated by `/`.
+pub fn analyze_code(code: &str) -> CodeAnalysis {
+    let mut analysis = CodeAnalysis::default();
+    let words: Vec<&str> = code.split('/').map(str::trim).filter(|w| !w.is_empty()).collect();
+
+    for (w, word) in words.iter().enumerate() {
+        analysis.words += 1;
+        if w > 0 {
+            analysis.units += 7;
+        }
+        for (l, letter) in word.split_whitespace().enumerate() {
+            analysis.letters += 1;
+            if l > 0 {
+                analysis.units += 3;
+            }
+            let dots = letter.matches('.').count();
+            let dashes = letter.matches('-').count();
+            analysis.dots += dots;
+            analysis.dashes += dashes;
+            analysis.units += dots + 3 * dashes + (dots + dashes).saturating_sub(1);
+
+            match morse::morse_to_char(letter) {
+                Some(c) => {
+                    analysis.chars_used.insert(c);
+                }
+                None => analysis.unknown_codes.push(letter.to_string()),
+            }
+        }
+    }
+    if analysis.words > 0 {
+        analysis.units += 7;
+    }
+    analysis
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use rand::{seq::SliceRandom, Rng};
+use crate::morse;
+
+/// Longest group made of leftover characters when no word can cover them.
+const MAX_GROUP_LENGTH: usize = 5;
+
+/// Composes a short text in which every character of `targets` appears at least
+/// `min_count` times. Words are picked greedily from `corpus` by how much of the remaining
+/// requirement they cover; when `allowed` is given, only words spelled entirely with those
+/// characters (plus the targets) are used. Characters no word can supply are sent as groups.
+pub fn coverage_text<R: Rng + ?Sized>(
+    targets: &[char],
+    min_count: usize,
+    corpus: &[String],
+    allowed: Option<&[char]>,
+    rng: &mut R,
+) -> Vec<String> {
+    let mut needed: HashMap<char, usize> = targets.iter()
+        .map(|c| c.to_ascii_uppercase())
+        .filter(|c| morse::char_to_morse(*c).is_some())
+        .map(|c| (c, min_count))
+        .collect();
+
+    let mut candidates: Vec<&String> = corpus.iter()
+        .filter(|w| match allowed {
+            Some(allowed) => w.chars().all(|c| allowed.contains(&c) || needed.contains_key(&c)),
+            None => w.chars().all(|c| morse::char_to_morse(c).is_some()),
+        })
+        .collect();
+    candidates.shuffle(rng);
+
+    let mut words = Vec::new();
+    while needed.values().any(|n| *n > 0) {
+        let score = |word: &str| -> usize {
+            needed.iter()
+                .map(|(c, n)| word.chars().filter(|w| w == c).count().min(*n))
+                .sum()
+        };
+        let best = candidates.iter()
+            .enumerate()
+            .map(|(i, w)| (score(w), w.len(), i))
+            .filter(|(s, _, _)| *s > 0)
+            .max_by(|(a, la, _), (b, lb, _)| a.cmp(b).then(lb.cmp(la)));
+
+        // Each word is used once so the text stays varied.
+        let word = match best {
+            Some((_, _, i)) => candidates.swap_remove(i).clone(),
+            None => leftover_group(&needed),
+        };
+        for c in word.chars() {
+            if let Some(n) = needed.get_mut(&c) {
+                *n = n.saturating_sub(1);
+            }
+        }
+        words.push(word);
+    }
+    words.shuffle(rng);
+    words
+}
+
+fn leftover_group(needed: &HashMap<char, usize>) -> String {
+    let mut chars: Vec<char> = needed.iter()
+        .filter(|(_, n)| **n > 0)
+        .map(|(c, _)| *c)
+        .collect();
+    chars.sort_unstable();
+    chars.into_iter().take(MAX_GROUP_LENGTH).collect()
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+use crate::{morse, scheduler};
+
+/// Characters from most to least common in English text, digits last.
+const FREQUENCY_ORDER: &str = "ETAOINSHRDLCUMWFGYPBVKJXQZ1234567890";
+
+const LENGTH_WEIGHT: f32 = 0.1;
+const CONFUSABLE_WEIGHT: f32 = 0.25;
+
+/// Difficulty of a single character: longer codes, dashes and rare characters cost more.
+pub fn char_difficulty(c: char) -> f32 {
+    let c = c.to_ascii_uppercase();
+    let Some(code) = morse::char_to_morse(c) else {
+        return 0.0;
+    };
+    let elements = code.len() as f32;
+    let dashes = code.matches('-').count() as f32;
+    let rarity = FREQUENCY_ORDER.find(c)
+        .map(|i| i as f32 / FREQUENCY_ORDER.len() as f32)
+        .unwrap_or(1.0);
+    elements * 0.5 + dashes * 0.25 + rarity * 2.0
+}
+
+/// Counts adjacent letters whose codes run together into another valid character,
+/// e.g. `E` + `T` (`.` `-`) against `A` (`.-`), which punishes sloppy letter spacing.
+pub fn confusable_pairs(word: &str) -> usize {
+    let codes: Vec<&str> = word.chars().filter_map(morse::char_to_morse).collect();
+    codes.windows(2)
+        .filter(|pair| morse::morse_to_char(&format!("{}{}", pair[0], pair[1])).is_some())
+        .count()
+}
+
+fn edit_distance(a: &[u8], b: &[u8]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, x) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, y) in b.iter().enumerate() {
+            let cost = usize::from(x != y);
+            current.push((previous[j] + cost).min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Whether two codes are easily mixed up: one element apart, mirrored (`.-` and `-.`) or
+/// with dots and dashes swapped (`-.-` and `.-.`).
+pub fn similar_codes(a: &str, b: &str) -> bool {
+    let reversed: String = a.chars().rev().collect();
+    let inverted: String = a.chars()
+        .map(|e| match e {
+            '.' => '-',
+            '-' => '.',
+            other => other,
+        })
+        .collect();
+    edit_distance(a.as_bytes(), b.as_bytes()) <= 1 || reversed == b || inverted == b
+}
+
+/// Whether practicing `other` exercises what went wrong in `missed`: for single characters
+/// their codes are similar, for words they share a letter or contain letters with similar codes.
+pub fn similar_items(missed: &str, other: &str) -> bool {
+    let codes = |item: &str| -> Vec<&'static str> {
+        match scheduler::decode_code(item).and_then(morse::morse_to_char) {
+            Some(c) => morse::char_to_morse(c).into_iter().collect(),
+            None => item.chars().filter_map(morse::char_to_morse).collect(),
+        }
+    };
+    let (missed_codes, other_codes) = (codes(missed), codes(other));
+    missed_codes.iter().any(|m| other_codes.iter().any(|o| m == o || similar_codes(m, o)))
+}
+
+pub fn word_difficulty(word: &str) -> f32 {
+    let chars: Vec<char> = word.chars().filter(|c| morse::char_to_morse(*c).is_some()).collect();
+    if chars.is_empty() {
+        return 0.0;
+    }
+    let mean = chars.iter().map(|c| char_difficulty(*c)).sum::<f32>() / chars.len() as f32;
+    mean + LENGTH_WEIGHT * (chars.len() - 1) as f32 + CONFUSABLE_WEIGHT * confusable_pairs(word) as f32
+}
+
+/// Mean word difficulty of a text.
+pub fn text_difficulty(text: &str) -> f32 {
+    let scores: Vec<f32> = text.split_whitespace().map(word_difficulty).collect();
+    if scores.is_empty() {
+        return 0.0;
+    }
+    scores.iter().sum::<f32>() / scores.len() as f32
+}
+
+/// Sorts words from easiest to hardest, keeping the original order for ties.
+pub fn sort_by_difficulty(words: &mut [String]) {
+    words.sort_by(|a, b| word_difficulty(a).total_cmp(&word_difficulty(b)));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DifficultyLabel {
+    Easy,
+    Medium,
+    Hard,
+    VeryHard,
+}
+
+impl DifficultyLabel {
+    pub fn for_score(score: f32) -> Self {
+        if score < 2.0 {
+            DifficultyLabel::Easy
+        } else if score < 3.0 {
+            DifficultyLabel::Medium
+        } else if score < 4.0 {
+            DifficultyLabel::Hard
+        } else {
+            DifficultyLabel::VeryHard
+        }
+    }
+}
+
+impl fmt::Display for DifficultyLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DifficultyLabel::Easy => "easy",
+            DifficultyLabel::Medium => "medium",
+            DifficultyLabel::Hard => "hard",
+            DifficultyLabel::VeryHard => "very hard",
+        })
+    }
+}
@@ -0,0 +1,105 @@
+use rand::{seq::IndexedRandom, Rng};
+use crate::{material, scoring::Rule};
+
+/// A receiving test modeled on a historical license exam.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExamPreset {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub wpm: f32,
+    pub minutes: f32,
+    /// What a copy must meet to pass.
+    pub rules: &'static [Rule],
+}
+
+pub const PRESETS: [ExamPreset; 5] = [
+    ExamPreset {
+        name: "us-novice",
+        description: "US Novice/Technician Plus element 1 (until 2007): 5 WPM for 5 minutes, one minute of solid copy",
+        wpm: 5.0,
+        minutes: 5.0,
+        rules: &[Rule::SolidCopy(25)],
+    },
+    ExamPreset {
+        name: "us-general",
+        description: "US General element 1B (until 2000): 13 WPM for 5 minutes, one minute of solid copy",
+        wpm: 13.0,
+        minutes: 5.0,
+        rules: &[Rule::SolidCopy(65)],
+    },
+    ExamPreset {
+        name: "us-extra",
+        description: "US Amateur Extra element 1C (until 2000): 20 WPM for 5 minutes, one minute of solid copy",
+        wpm: 20.0,
+        minutes: 5.0,
+        rules: &[Rule::SolidCopy(100)],
+    },
+    ExamPreset {
+        name: "uk-5",
+        description: "UK Novice Morse test (until 2003): 5 WPM plain language, at most 4 errors",
+        wpm: 5.0,
+        minutes: 3.0,
+        rules: &[Rule::MaxErrors(4)],
+    },
+    ExamPreset {
+        name: "uk-12",
+        description: "UK Class A Morse test (until 2003): 12 WPM plain language for 3 minutes, at most 4 errors",
+        wpm: 12.0,
+        minutes: 3.0,
+        rules: &[Rule::MaxErrors(4)],
+    },
+];
+
+pub fn preset(name: &str) -> Option<ExamPreset> {
+    PRESETS.iter().find(|p| p.name.eq_ignore_ascii_case(name)).copied()
+}
+
+const NAMES: [&str; 10] = ["BOB", "JIM", "ANN", "TOM", "SUE", "ED", "PAT", "JOE", "LIZ", "AL"];
+const CITIES: [&str; 10] = ["DENVER", "BOSTON", "AUSTIN", "TOLEDO", "RENO", "SALEM", "OMAHA", "TULSA", "AKRON", "BANGOR"];
+const RIGS: [&str; 6] = ["IC7300", "K3", "FT991", "TS590", "KX2", "FT710"];
+const ANTENNAS: [&str; 5] = ["DIPOLE", "VERTICAL", "YAGI", "LOOP", "END FED WIRE"];
+const WEATHER: [&str; 6] = ["SUNNY", "CLOUDY", "RAINY", "COLD", "WARM", "WINDY"];
+
+/// Plain-language text of about `chars` characters in the style of the old exams: the
+/// exchanges of a ragchew QSO, with numbers and the common punctuation.
+pub fn qso_text<R: Rng + ?Sized>(chars: usize, rng: &mut R) -> String {
+    let calls = material::callsigns(2, rng);
+    let (me, you) = (&calls[0], &calls[1]);
+    let mut sentences = vec![format!("{} DE {}", you, me)];
+    let closing = format!("BACK TO YOU, {} DE {} K.", you, me);
+    let mut topics = Vec::new();
+    let mut text_len = sentences[0].len() + closing.len() + 1;
+
+    while text_len < chars {
+        if topics.is_empty() {
+            topics = (0..TOPICS).collect();
+        }
+        let topic = topics.remove(rng.random_range(0..topics.len()));
+        let sentence = topic_sentence(topic, me, rng);
+        text_len += sentence.len() + 1;
+        sentences.push(sentence);
+    }
+    sentences.push(closing);
+    sentences.join(" ")
+}
+
+/// One sentence of a ragchew QSO on a random topic, sent by a random callsign.
+pub fn qso_sentence<R: Rng + ?Sized>(rng: &mut R) -> String {
+    let me = material::callsigns(1, rng).remove(0);
+    let topic = rng.random_range(0..TOPICS);
+    topic_sentence(topic, &me, rng)
+}
+
+const TOPICS: usize = 6;
+
+fn topic_sentence<R: Rng + ?Sized>(topic: usize, me: &str, rng: &mut R) -> String {
+    match topic {
+        0 => format!("THANKS FOR THE CALL. YOUR RST IS {}{}9.", rng.random_range(3..=5), rng.random_range(5..=9)),
+        1 => format!("MY NAME IS {}, QTH IS {}.", NAMES.choose(rng).unwrap_or(&"BOB"), CITIES.choose(rng).unwrap_or(&"DENVER")),
+        2 => format!("RIG HERE IS A {} AT {} WATTS INTO A {}.",
+            RIGS.choose(rng).unwrap_or(&"K3"), [5, 10, 50, 100].choose(rng).unwrap_or(&100), ANTENNAS.choose(rng).unwrap_or(&"DIPOLE")),
+        3 => format!("WX IS {} AND {} DEGREES.", WEATHER.choose(rng).unwrap_or(&"SUNNY"), rng.random_range(20..=90)),
+        4 => format!("I HAVE BEEN A HAM FOR {} YEARS, AGE IS {}.", rng.random_range(2..=50), rng.random_range(18..=80)),
+        _ => format!("HOW DO YOU COPY? I AM PORTABLE, SIGNING {}/P.", me),
+    }
+}
@@ -0,0 +1,132 @@
+/// How an answer differs from the expected code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Nothing was entered.
+    NoAnswer,
+    /// The answer contains characters other than dots, dashes and spaces.
+    InvalidSymbols,
+    /// Same number of elements, but at least one dot/dash is swapped.
+    WrongElements,
+    /// Fewer elements than expected.
+    MissingElements,
+    /// More elements than expected.
+    ExtraElements,
+    /// The elements are right but the letter spacing is not.
+    Spacing,
+}
+
+impl ErrorKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorKind::NoAnswer => "no_answer",
+            ErrorKind::InvalidSymbols => "invalid_symbols",
+            ErrorKind::WrongElements => "wrong_elements",
+            ErrorKind::MissingElements => "missing_elements",
+            ErrorKind::ExtraElements => "extra_elements",
+            ErrorKind::Spacing => "spacing",
+        }
+    }
+}
+
+/// Typing faster than this many keys per second, counted from when the item was shown,
+/// means the answer wasn't keyed by hand.
+pub const MAX_KEYS_PER_SEC: f32 = 12.0;
+
+/// Whether a multi-character answer came in faster than anyone could type it.
+pub fn implausibly_fast(answer: &str, response_time: f32) -> bool {
+    let keys = answer.trim().chars().count();
+    keys >= 2 && response_time < keys as f32 / MAX_KEYS_PER_SEC
+}
+
+/// Classifies an answer against the expected code, returning `None` when it is correct.
+pub fn classify(expected: &str, answer: &str) -> Option<ErrorKind> {
+    let answer = answer.trim();
+    if answer == expected {
+        return None;
+    }
+    if answer.is_empty() {
+        return Some(ErrorKind::NoAnswer);
+    }
+    if answer.chars().any(|c| !matches!(c, '.' | '-' | ' ' | '/')) {
+        return Some(ErrorKind::InvalidSymbols);
+    }
+
+    let elements = |s: &str| s.chars().filter(|c| matches!(c, '.' | '-')).collect::<String>();
+    let (expected_elements, answer_elements) = (elements(expected), elements(answer));
+    match answer_elements.len().cmp(&expected_elements.len()) {
+        std::cmp::Ordering::Less => Some(ErrorKind::MissingElements),
+        std::cmp::Ordering::Greater => Some(ErrorKind::ExtraElements),
+        std::cmp::Ordering::Equal if answer_elements == expected_elements => Some(ErrorKind::Spacing),
+        std::cmp::Ordering::Equal => Some(ErrorKind::WrongElements),
+    }
+}
+
+/// How many words of `expected` were copied in order in `answer` (their longest common
+/// subsequence), ignoring case and spacing.
+pub fn copied_words(expected: &str, answer: &str) -> usize {
+    word_diff(expected, answer).iter()
+        .filter(|w| matches!(w, WordDiff::Copied(_)))
+        .count()
+}
+
+/// One word in the comparison of a copy with the text that was sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordDiff {
+    Copied(String),
+    /// Sent, but not in the copy.
+    Missed(String),
+    /// Written down as another word.
+    Miscopied { sent: String, copied: String },
+    /// In the copy, but never sent.
+    Extra(String),
+}
+
+/// Compares `answer` with `expected` word by word, in the order of the text, ignoring
+/// case and spacing. The copied words are their longest common subsequence; between two
+/// of them, missed and extra words are paired up as miscopied ones.
+pub fn word_diff(expected: &str, answer: &str) -> Vec<WordDiff> {
+    let expected: Vec<String> = expected.split_whitespace().map(|w| w.to_uppercase()).collect();
+    let answer: Vec<String> = answer.split_whitespace().map(|w| w.to_uppercase()).collect();
+    // common[i][j]: length of the longest common subsequence of expected[i..] and answer[j..].
+    let mut common = vec![vec![0usize; answer.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..answer.len()).rev() {
+            common[i][j] = if expected[i] == answer[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut missed, mut extra) = (Vec::new(), Vec::new());
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < answer.len() {
+        if i < expected.len() && j < answer.len() && expected[i] == answer[j] {
+            pair_up(&mut diff, &mut missed, &mut extra);
+            diff.push(WordDiff::Copied(expected[i].clone()));
+            i += 1;
+            j += 1;
+        } else if j == answer.len() || (i < expected.len() && common[i + 1][j] >= common[i][j + 1]) {
+            missed.push(expected[i].clone());
+            i += 1;
+        } else {
+            extra.push(answer[j].clone());
+            j += 1;
+        }
+    }
+    pair_up(&mut diff, &mut missed, &mut extra);
+    diff
+}
+
+fn pair_up(diff: &mut Vec<WordDiff>, missed: &mut Vec<String>, extra: &mut Vec<String>) {
+    let mut copied = extra.drain(..);
+    for sent in missed.drain(..) {
+        diff.push(match copied.next() {
+            Some(copied) => WordDiff::Miscopied { sent, copied },
+            None => WordDiff::Missed(sent),
+        });
+    }
+    diff.extend(copied.map(WordDiff::Extra));
+}
@@ -0,0 +1,212 @@
+use crate::timing::TimingEvent;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyerMode {
+    /// Squeezing alternates dits and dahs; letting go stops after the current element.
+    IambicA,
+    /// Like A, but letting go of a squeeze sends one more, opposite element (Curtis B).
+    #[default]
+    IambicB,
+    /// Semi-automatic "bug": the dit paddle sends a stream of dits, the dah paddle keys
+    /// the tone for as long as it is held, like a straight key.
+    Bug,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KeyerSettings {
+    pub mode: KeyerMode,
+    /// Swap the paddles: the right one sends dits (left-handed operation).
+    pub reverse: bool,
+}
+
+/// Contact state of the two paddle levers as seen by the operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Paddles {
+    pub left: bool,
+    pub right: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Element {
+    Dit,
+    Dah,
+    /// Key down until the dah paddle is let go (bug mode).
+    Manual,
+}
+
+/// Decides the elements an electronic keyer sends. Call [`Keyer::touch`] whenever the
+/// paddles change while an element is being sent, and [`Keyer::next`] each time an element
+/// and its following gap are over.
+#[derive(Debug, Clone)]
+pub struct Keyer {
+    settings: KeyerSettings,
+    last: Option<Element>,
+    /// The paddle opposite to the last element was pressed while it was being sent.
+    opposite_pressed: bool,
+}
+
+impl Keyer {
+    pub fn new(settings: KeyerSettings) -> Self {
+        Keyer {
+            settings,
+            last: None,
+            opposite_pressed: false,
+        }
+    }
+
+    /// (dit, dah) contacts after paddle reversal.
+    fn contacts(&self, paddles: Paddles) -> (bool, bool) {
+        if self.settings.reverse {
+            (paddles.right, paddles.left)
+        } else {
+            (paddles.left, paddles.right)
+        }
+    }
+
+    pub fn touch(&mut self, paddles: Paddles) {
+        let (dit, dah) = self.contacts(paddles);
+        match self.last {
+            Some(Element::Dit) if dah => self.opposite_pressed = true,
+            Some(Element::Dah) if dit => self.opposite_pressed = true,
+            _ => {}
+        }
+    }
+
+    /// The element to send now, or `None` to leave the key up.
+    pub fn next(&mut self, paddles: Paddles) -> Option<Element> {
+        let (dit, dah) = self.contacts(paddles);
+        let opposite = match self.last {
+            Some(Element::Dit) => Element::Dah,
+            _ => Element::Dit,
+        };
+        let element = match self.settings.mode {
+            KeyerMode::Bug if dah => Some(Element::Manual),
+            KeyerMode::Bug => dit.then_some(Element::Dit),
+            KeyerMode::IambicB if self.opposite_pressed => Some(opposite),
+            KeyerMode::IambicA | KeyerMode::IambicB => match (dit, dah) {
+                (true, true) => Some(opposite),
+                (true, false) => Some(Element::Dit),
+                (false, true) => Some(Element::Dah),
+                (false, false) => None,
+            },
+        };
+        self.last = element;
+        // A squeeze already held counts as pressed, so letting go of it still triggers B.
+        self.opposite_pressed = match element {
+            Some(Element::Dit) => dah,
+            Some(Element::Dah) => dit,
+            _ => false,
+        };
+        element
+    }
+}
+
+/// A change of the paddle contacts `at_ms` milliseconds into a recording.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PaddleEvent {
+    pub at_ms: f32,
+    pub paddles: Paddles,
+}
+
+fn push_space(events: &mut Vec<TimingEvent>, ms: f32) {
+    match events.last_mut() {
+        Some(TimingEvent::Space(gap)) => *gap += ms,
+        Some(_) => events.push(TimingEvent::Space(ms)),
+        // Silence before the first element isn't part of the keying.
+        None => {}
+    }
+}
+
+/// Runs a recording of paddle changes (sorted by time) through a keyer at the given dot
+/// length and returns the key timings it sends.
+pub fn key_paddles(changes: &[PaddleEvent], settings: KeyerSettings, unit_ms: f32) -> Vec<TimingEvent> {
+    let state_at = |t: f32| changes.iter()
+        .take_while(|c| c.at_ms <= t)
+        .last()
+        .map(|c| c.paddles)
+        .unwrap_or_default();
+    let mut keyer = Keyer::new(settings);
+    let mut events = Vec::new();
+    let mut t = changes.first().map(|c| c.at_ms).unwrap_or(0.0);
+    let end_of_recording = changes.last().map(|c| c.at_ms).unwrap_or(0.0);
+
+    loop {
+        let paddles = state_at(t);
+        // Paddles still held when the recording stops would key forever.
+        if t > end_of_recording && (paddles.left || paddles.right) {
+            break;
+        }
+        let element = keyer.next(paddles);
+        let length = match element {
+            Some(Element::Dit) => unit_ms,
+            Some(Element::Dah) => 3.0 * unit_ms,
+            Some(Element::Manual) => {
+                let dah = |p: Paddles| if settings.reverse { p.left } else { p.right };
+                let release = changes.iter()
+                    .find(|c| c.at_ms > t && !dah(c.paddles))
+                    .map(|c| c.at_ms);
+                match release {
+                    Some(release) => release - t,
+                    None => break,
+                }
+            }
+            None => {
+                // Idle until the paddles change again.
+                match changes.iter().find(|c| c.at_ms > t) {
+                    Some(change) => {
+                        push_space(&mut events, change.at_ms - t);
+                        t = change.at_ms;
+                        continue;
+                    }
+                    None => break,
+                }
+            }
+        };
+
+        events.push(TimingEvent::Mark(length));
+        let end = t + length + unit_ms;
+        for change in changes.iter().filter(|c| c.at_ms > t && c.at_ms < end) {
+            keyer.touch(change.paddles);
+        }
+        push_space(&mut events, unit_ms);
+        t = end;
+    }
+
+    if let Some(TimingEvent::Space(_)) = events.last() {
+        events.pop();
+    }
+    events
+}
+
+/// A straight key going down or coming up `at_ms` milliseconds into a recording.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyChange {
+    pub at_ms: f32,
+    pub down: bool,
+}
+
+/// The key timings of a straight key: each press is a mark as long as it was held, and the
+/// time between presses a space. Repeated downs or ups, as from key bounce, are ignored, and
+/// a press that hasn't been let go yet is left out.
+pub fn key_straight(changes: &[KeyChange]) -> Vec<TimingEvent> {
+    let mut events = Vec::new();
+    let mut pressed_at: Option<f32> = None;
+    let mut released_at: Option<f32> = None;
+    for change in changes {
+        match (change.down, pressed_at) {
+            (true, None) => {
+                if let Some(released_at) = released_at {
+                    push_space(&mut events, change.at_ms - released_at);
+                }
+                pressed_at = Some(change.at_ms);
+            }
+            (false, Some(at)) => {
+                events.push(TimingEvent::Mark(change.at_ms - at));
+                pressed_at = None;
+                released_at = Some(change.at_ms);
+            }
+            _ => {}
+        }
+    }
+    events
+}
@@ -0,0 +1,201 @@
+use crate::timing::TimingEvent;
+
+/// What counts as clean sending on one kind of key.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyingNorms {
+    /// How far an element or gap may be off, as a fraction of its ideal length, and still
+    /// count as clean.
+    pub tolerance: f32,
+    /// Character speeds the key is usually sent at.
+    pub min_wpm: f32,
+    pub max_wpm: f32,
+    /// Share of the score that comes from the gaps rather than the elements.
+    pub spacing_weight: f32,
+}
+
+/// Hand-timed elements vary a lot on a straight key, so the tolerance is wide, the speeds
+/// are low (SKCC style) and the score rests mostly on even spacing.
+pub const STRAIGHT_KEY: KeyingNorms = KeyingNorms {
+    tolerance: 0.5,
+    min_wpm: 5.0,
+    max_wpm: 22.0,
+    spacing_weight: 0.7,
+};
+
+/// A keyer times the elements itself, so they are held to a tighter standard.
+pub const PADDLE: KeyingNorms = KeyingNorms {
+    tolerance: 0.25,
+    min_wpm: 12.0,
+    max_wpm: 40.0,
+    spacing_weight: 0.5,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyingScore {
+    /// Fraction of dots and dashes within the tolerance.
+    pub clean_elements: f32,
+    /// Fraction of element, letter and word gaps within the tolerance.
+    pub clean_gaps: f32,
+    /// Standard deviation of the letter gaps as a fraction of their mean; `None` with
+    /// fewer than two letter gaps.
+    pub letter_gap_spread: Option<f32>,
+    /// 0 to 100, weighted by [`KeyingNorms::spacing_weight`].
+    pub score: f32,
+}
+
+fn fraction(hits: usize, total: usize) -> f32 {
+    if total == 0 { 1.0 } else { hits as f32 / total as f32 }
+}
+
+fn mean(values: &[f32]) -> Option<f32> {
+    (!values.is_empty()).then(|| values.iter().sum::<f32>() / values.len() as f32)
+}
+
+/// Standard deviation as a fraction of the mean; `None` with fewer than two values.
+fn spread(values: &[f32]) -> Option<f32> {
+    if values.len() < 2 {
+        return None;
+    }
+    let mean = mean(values)?;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / values.len() as f32;
+    Some(variance.sqrt() / mean)
+}
+
+/// How keying is timed, whatever was sent. Gaps are measured in the sender's own dots.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SendingQuality {
+    /// Average dash over average dot; 3 in ideal timing.
+    pub dash_dot_ratio: Option<f32>,
+    /// Average gap between the elements of a character; ideally 1 dot.
+    pub element_gap: Option<f32>,
+    /// Standard deviation of those gaps as a fraction of their average.
+    pub element_gap_spread: Option<f32>,
+    /// Average gap between characters; ideally 3 dots.
+    pub letter_gap: Option<f32>,
+    pub letter_gap_spread: Option<f32>,
+}
+
+/// Element and gap lengths of keyed answers in dots, gathered to judge the sending rather
+/// than what was sent.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SendingTally {
+    dots: Vec<f32>,
+    dashes: Vec<f32>,
+    element_gaps: Vec<f32>,
+    letter_gaps: Vec<f32>,
+}
+
+impl SendingTally {
+    /// Adds the keying of one answer, sorted into elements and gaps by the same thresholds
+    /// as decoding at `unit_ms`. Word gaps and the silence around the answer are left out.
+    pub fn add(&mut self, events: &[TimingEvent], unit_ms: f32) {
+        let start = events.iter().position(|e| matches!(e, TimingEvent::Mark(_)));
+        let end = events.iter().rposition(|e| matches!(e, TimingEvent::Mark(_)));
+        let (Some(start), Some(end)) = (start, end) else {
+            return;
+        };
+        for event in &events[start..=end] {
+            match *event {
+                TimingEvent::Mark(ms) if ms < 2.0 * unit_ms => self.dots.push(ms / unit_ms),
+                TimingEvent::Mark(ms) => self.dashes.push(ms / unit_ms),
+                TimingEvent::Space(ms) if ms < 2.0 * unit_ms => self.element_gaps.push(ms / unit_ms),
+                TimingEvent::Space(ms) if ms < 5.0 * unit_ms => self.letter_gaps.push(ms / unit_ms),
+                TimingEvent::Space(_) => {}
+            }
+        }
+    }
+
+    /// `None` until something was keyed.
+    pub fn quality(&self) -> Option<SendingQuality> {
+        if self.dots.is_empty() && self.dashes.is_empty() {
+            return None;
+        }
+        let dot = mean(&self.dots).unwrap_or(1.0);
+        Some(SendingQuality {
+            dash_dot_ratio: mean(&self.dots).and(mean(&self.dashes)).map(|dash| dash / dot),
+            element_gap: mean(&self.element_gaps).map(|gap| gap / dot),
+            element_gap_spread: spread(&self.element_gaps),
+            letter_gap: mean(&self.letter_gaps).map(|gap| gap / dot),
+            letter_gap_spread: spread(&self.letter_gaps),
+        })
+    }
+}
+
+/// When each element of `events` starts, in ms from the start of the first.
+pub fn onsets(events: &[TimingEvent]) -> Vec<f32> {
+    let mut at = 0.0;
+    let mut onsets = Vec::new();
+    for event in events {
+        match event {
+            TimingEvent::Mark(ms) => {
+                onsets.push(at);
+                at += ms;
+            }
+            TimingEvent::Space(ms) => at += ms,
+        }
+    }
+    onsets
+}
+
+/// How closely the rhythm of keyed elements follows the ideal, 0 to 100: the share of the
+/// intervals from one element's start to the next within the tolerance, once the keying
+/// is scaled to the ideal's overall length. Only when each element started is needed, so
+/// typed answers can be scored too. `None` when the number of elements differs or there
+/// are fewer than two.
+pub fn rhythm(onsets_ms: &[f32], ideal: &[TimingEvent], norms: &KeyingNorms) -> Option<f32> {
+    let expected = onsets(ideal);
+    if onsets_ms.len() != expected.len() || expected.len() < 2 {
+        return None;
+    }
+    let intervals = |onsets: &[f32]| onsets.windows(2).map(|w| w[1] - w[0]).collect::<Vec<f32>>();
+    let (keyed, expected) = (intervals(onsets_ms), intervals(&expected));
+    let scale = expected.iter().sum::<f32>() / keyed.iter().sum::<f32>();
+    if !scale.is_finite() {
+        return None;
+    }
+    let clean = keyed.iter().zip(&expected)
+        .filter(|(k, e)| (*k * scale / *e - 1.0).abs() <= norms.tolerance)
+        .count();
+    Some(100.0 * fraction(clean, expected.len()))
+}
+
+/// Compares keyed timings with the ideal ones for the same text, event by event.
+/// `None` when they don't line up, i.e. a different number of elements was keyed.
+pub fn score(keyed: &[TimingEvent], ideal: &[TimingEvent], unit_ms: f32, norms: &KeyingNorms) -> Option<KeyingScore> {
+    let keyed = match keyed.last() {
+        Some(TimingEvent::Space(_)) => &keyed[..keyed.len() - 1],
+        _ => keyed,
+    };
+    if keyed.len() != ideal.len() {
+        return None;
+    }
+
+    let (mut elements, mut clean_elements, mut gaps, mut clean_gaps) = (0, 0, 0, 0);
+    let mut letter_gaps = Vec::new();
+    for (actual, expected) in keyed.iter().zip(ideal) {
+        let clean = |actual: f32, expected: f32| (actual / expected - 1.0).abs() <= norms.tolerance;
+        match (actual, expected) {
+            (TimingEvent::Mark(a), TimingEvent::Mark(e)) => {
+                elements += 1;
+                clean_elements += usize::from(clean(*a, *e));
+            }
+            (TimingEvent::Space(a), TimingEvent::Space(e)) => {
+                gaps += 1;
+                clean_gaps += usize::from(clean(*a, *e));
+                if *e > 2.0 * unit_ms && *e < 5.0 * unit_ms {
+                    letter_gaps.push(*a);
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    let letter_gap_spread = spread(&letter_gaps);
+    let (clean_elements, clean_gaps) = (fraction(clean_elements, elements), fraction(clean_gaps, gaps));
+    Some(KeyingScore {
+        clean_elements,
+        clean_gaps,
+        letter_gap_spread,
+        score: 100.0 * ((1.0 - norms.spacing_weight) * clean_elements + norms.spacing_weight * clean_gaps),
+    })
+}
@@ -0,0 +1,19 @@
+pub mod analysis;
+pub mod coverage;
+pub mod difficulty;
+pub mod exam;
+pub mod grading;
+pub mod keyer;
+pub mod keying;
+pub mod material;
+pub mod memorization;
+pub mod morse;
+pub mod progression;
+pub mod pseudowords;
+pub mod radiogram;
+pub mod rehab;
+pub mod scheduler;
+pub mod scoring;
+pub mod timing;
+pub mod verbal;
+pub mod weakness;
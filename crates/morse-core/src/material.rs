@@ -0,0 +1,94 @@
+use rand::{seq::{IndexedRandom, SliceRandom}, Rng};
+
+pub const GROUP_LENGTH: usize = 5;
+
+/// Random fixed-length code groups drawn from `chars`, the classic copy-practice format.
+pub fn code_groups<R: Rng + ?Sized>(chars: &[char], count: usize, length: usize, rng: &mut R) -> Vec<String> {
+    if chars.is_empty() {
+        return Vec::new();
+    }
+    (0..count)
+        .map(|_| (0..length).filter_map(|_| chars.choose(rng)).collect())
+        .collect()
+}
+
+/// Up to `count` random words from `corpus` spelled only with `chars`.
+pub fn words_within<R: Rng + ?Sized>(chars: &[char], corpus: &[String], count: usize, rng: &mut R) -> Vec<String> {
+    let mut words: Vec<String> = corpus.iter()
+        .filter(|w| w.chars().all(|c| chars.contains(&c)))
+        .cloned()
+        .collect();
+    words.shuffle(rng);
+    words.truncate(count);
+    words
+}
+
+/// `groups` code groups followed by up to `words` words; words the corpus can't supply
+/// are made up with extra groups so the drill keeps its length.
+pub fn groups_and_words<R: Rng + ?Sized>(chars: &[char], corpus: &[String], groups: usize, words: usize, rng: &mut R) -> Vec<String> {
+    let found = words_within(chars, corpus, words, rng);
+    let groups = groups + words - found.len();
+    let mut items = code_groups(chars, groups, GROUP_LENGTH, rng);
+    items.extend(found);
+    items
+}
+
+/// Amateur bands as (lowest, highest) frequency in kHz, from HF up to 70 cm.
+const BANDS_KHZ: [(u32, u32); 10] = [
+    (1810, 2000), (3500, 3800), (7000, 7200), (10100, 10150), (14000, 14350),
+    (18068, 18168), (21000, 21450), (28000, 29700), (144000, 146000), (430000, 440000),
+];
+
+/// Random Maidenhead locators as sent on air, e.g. `JO62` or, about half the time,
+/// the six-character `JO62QM`.
+pub fn grid_squares<R: Rng + ?Sized>(count: usize, rng: &mut R) -> Vec<String> {
+    let letter = |rng: &mut R, last: u8| (b'A' + rng.random_range(0..=last - b'A')) as char;
+    (0..count)
+        .map(|_| {
+            let mut grid = String::new();
+            grid.push(letter(rng, b'R'));
+            grid.push(letter(rng, b'R'));
+            grid.push_str(&format!("{:02}", rng.random_range(0..100)));
+            if rng.random_bool(0.5) {
+                grid.push(letter(rng, b'X'));
+                grid.push(letter(rng, b'X'));
+            }
+            grid
+        })
+        .collect()
+}
+
+/// Random frequencies in MHz within the amateur bands, e.g. `14.025` or `144.174`,
+/// with every fourth item a split or repeater offset in kHz such as `+1.5` or `-600`.
+pub fn frequencies<R: Rng + ?Sized>(count: usize, rng: &mut R) -> Vec<String> {
+    (0..count)
+        .map(|i| {
+            if i % 4 == 3 {
+                let sign = if rng.random_bool(0.5) { '+' } else { '-' };
+                return match rng.random_range(0..3) {
+                    0 => format!("{}{}", sign, rng.random_range(1..=5)),
+                    1 => format!("{}{}.5", sign, rng.random_range(0..5)),
+                    _ => format!("{}600", sign),
+                };
+            }
+            let (low, high) = *BANDS_KHZ.choose(rng).unwrap_or(&BANDS_KHZ[4]);
+            let khz = rng.random_range(low..high);
+            format!("{}.{:03}", khz / 1000, khz % 1000)
+        })
+        .collect()
+}
+
+/// Random callsigns of the usual shape: a one- or two-letter prefix, a digit and a
+/// one- to three-letter suffix, e.g. `K1ABC` or `DL3X`.
+pub fn callsigns<R: Rng + ?Sized>(count: usize, rng: &mut R) -> Vec<String> {
+    let letters = |rng: &mut R, n: usize| -> String {
+        (0..n).map(|_| (b'A' + rng.random_range(0..26u8)) as char).collect()
+    };
+    (0..count)
+        .map(|_| {
+            let prefix_len = rng.random_range(1..=2);
+            let suffix_len = rng.random_range(1..=3);
+            format!("{}{}{}", letters(rng, prefix_len), rng.random_range(0..10), letters(rng, suffix_len))
+        })
+        .collect()
+}
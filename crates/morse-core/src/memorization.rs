@@ -0,0 +1,35 @@
+use std::collections::{BTreeSet, HashMap};
+
+/// Answers per word looked at; a word needs this many before it can count as memorized.
+pub const RECENT_ANSWERS: usize = 5;
+/// Coefficient of variation of response times below which answers are considered rote.
+pub const MAX_VARIATION: f32 = 0.15;
+/// Rote answers that are also no slower than this factor of the word's best time have
+/// stopped improving.
+pub const PLATEAU_FACTOR: f32 = 1.2;
+
+/// Words whose recent answers are all correct, nearly identical in time and no longer getting
+/// faster: signs the word is recalled as a whole rather than sent letter by letter.
+/// `answers` are `(word, response time, correct)` in chronological order.
+pub fn memorized_words<'a>(answers: impl IntoIterator<Item = (&'a str, f32, bool)>) -> BTreeSet<String> {
+    let mut by_word: HashMap<&str, Vec<(f32, bool)>> = HashMap::new();
+    for (word, time, correct) in answers {
+        by_word.entry(word).or_default().push((time, correct));
+    }
+
+    by_word.into_iter()
+        .filter(|(_, answers)| answers.len() >= RECENT_ANSWERS)
+        .filter(|(_, answers)| {
+            let best = answers.iter().map(|(t, _)| *t).fold(f32::INFINITY, f32::min);
+            let recent = &answers[answers.len() - RECENT_ANSWERS..];
+            let times: Vec<f32> = recent.iter().map(|(t, _)| *t).collect();
+            let mean = times.iter().sum::<f32>() / times.len() as f32;
+            let variance = times.iter().map(|t| (t - mean).powi(2)).sum::<f32>() / times.len() as f32;
+            recent.iter().all(|(_, correct)| *correct)
+                && mean > 0.0
+                && variance.sqrt() / mean < MAX_VARIATION
+                && mean <= best * PLATEAU_FACTOR
+        })
+        .map(|(word, _)| word.to_string())
+        .collect()
+}
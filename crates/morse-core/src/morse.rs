@@ -0,0 +1,62 @@
+pub const MORSE_MAPPING: [(char, &str); 36] = [
+    ('A', ".-"), ('B', "-..."), ('C', "-.-."), ('D', "-.."), ('E', "."), ('F', "..-."),
+    ('G', "--."), ('H', "...."), ('I', ".."), ('J', ".---"), ('K', "-.-"), ('L', ".-.."),
+    ('M', "--"), ('N', "-."), ('O', "---"), ('P', ".--."), ('Q', "--.-"), ('R', ".-."),
+    ('S', "..."), ('T', "-"), ('U', "..-"), ('V', "...-"), ('W', ".--"), ('X', "-..-"),
+    ('Y', "-.--"), ('Z', "--.."), ('1', ".----"), ('2', "..---"), ('3', "...--"),
+    ('4', "....-"), ('5', "....."), ('6', "-...."), ('7', "--..."), ('8', "---.."),
+    ('9', "----."), ('0', "-----"),
+];
+
+/// ITU punctuation, kept apart from letters and digits so drills can pick either.
+pub const PUNCTUATION_MAPPING: [(char, &str); 14] = [
+    ('.', ".-.-.-"), (',', "--..--"), ('?', "..--.."), ('\'', ".----."), ('/', "-..-."),
+    ('(', "-.--."), (')', "-.--.-"), (':', "---..."), (';', "-.-.-."), ('=', "-...-"),
+    ('+', ".-.-."), ('-', "-....-"), ('"', ".-..-."), ('@', ".--.-."),
+];
+
+/// Separator placed between encoded words, e.g. `... --- ... / ... --- ...`.
+pub const WORD_SEPARATOR: &str = " / ";
+
+pub fn char_to_morse(c: char) -> Option<&'static str> {
+    MORSE_MAPPING.iter()
+        .chain(&PUNCTUATION_MAPPING)
+        .find(|(ch, _)| *ch == c.to_ascii_uppercase())
+        .map(|(_, code)| *code)
+}
+
+pub fn morse_to_char(code: &str) -> Option<char> {
+    MORSE_MAPPING.iter()
+        .chain(&PUNCTUATION_MAPPING)
+        .find(|(_, c)| *c == code)
+        .map(|(ch, _)| *ch)
+}
+
+/// Encodes a single word with one space between characters. Unknown characters are skipped.
+pub fn encode_word(word: &str) -> String {
+    word.chars()
+        .filter_map(char_to_morse)
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+pub fn encode(text: &str) -> String {
+    text.split_whitespace()
+        .map(encode_word)
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<String>>()
+        .join(WORD_SEPARATOR)
+}
+
+/// Decodes space-separated codes, with words separated by `/`. Unknown codes become `?`.
+pub fn decode(code: &str) -> String {
+    code.split('/')
+        .map(|word| {
+            word.split_whitespace()
+                .map(|c| morse_to_char(c).unwrap_or('?'))
+                .collect::<String>()
+        })
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
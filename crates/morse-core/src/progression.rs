@@ -0,0 +1,198 @@
+use std::{collections::{HashMap, HashSet}, fs};
+
+#[derive(Debug)]
+pub struct ProgressionSystem {
+    pub levels: Vec<ProgressionLevel>,
+    pub common_words: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct ProgressionLevel {
+    pub level: u8,
+    pub chars_to_learn: Vec<char>,
+    /// Response time in seconds every character learned before this level must meet.
+    pub speed_requirement: f32,
+    pub accuracy_requirement: f32,
+    /// Factor by which the characters introduced at this level may exceed `speed_requirement`.
+    pub new_char_slack: f32,
+}
+
+/// One character's latest response time against its target.
+#[derive(Debug, Clone, Copy)]
+pub struct CharSpeed {
+    pub ch: char,
+    pub time: Option<f32>,
+    pub target: f32,
+}
+
+impl CharSpeed {
+    pub fn passed(&self) -> bool {
+        self.time.is_some_and(|t| t <= self.target)
+    }
+}
+
+impl ProgressionSystem {
+    pub fn new() -> Self {
+        Self::with_words(load_common_words())
+    }
+
+    pub fn with_words(common_words: Vec<String>) -> Self {
+        let levels = vec![
+            ProgressionLevel {
+                level: 1,
+                chars_to_learn: vec!['E', 'T'],
+                speed_requirement: 5.0,
+                accuracy_requirement: 0.8,
+                new_char_slack: 1.0,
+            },
+            ProgressionLevel {
+                level: 2,
+                chars_to_learn: vec!['A', 'I', 'M', 'N'],
+                speed_requirement: 4.0,
+                accuracy_requirement: 0.85,
+                new_char_slack: 1.5,
+            },
+            ProgressionLevel {
+                level: 3,
+                chars_to_learn: vec!['D', 'G', 'K', 'O'],
+                speed_requirement: 3.5,
+                accuracy_requirement: 0.9,
+                new_char_slack: 1.5,
+            },
+            ProgressionLevel {
+                level: 4,
+                chars_to_learn: vec!['R', 'S', 'U', 'W'],
+                speed_requirement: 3.5,
+                accuracy_requirement: 0.9,
+                new_char_slack: 1.5,
+            },
+            ProgressionLevel {
+                level: 5,
+                chars_to_learn: vec!['B', 'C', 'F', 'H', 'J', 'L'],
+                speed_requirement: 3.0,
+                accuracy_requirement: 0.95,
+                new_char_slack: 1.5,
+            },
+            ProgressionLevel {
+                level: 6,
+                chars_to_learn: vec!['P', 'Q', 'V', 'X', 'Y', 'Z'],
+                speed_requirement: 3.0,
+                accuracy_requirement: 0.95,
+                new_char_slack: 1.5,
+            },
+            ProgressionLevel {
+                level: 7,
+                chars_to_learn: vec!['0', '1', '2', '3', '4'],
+                speed_requirement: 2.5,
+                accuracy_requirement: 0.95,
+                new_char_slack: 1.5,
+            },
+            ProgressionLevel {
+                level: 8,
+                chars_to_learn: vec!['5', '6', '7', '8', '9'],
+                speed_requirement: 2.5,
+                accuracy_requirement: 0.95,
+                new_char_slack: 1.5,
+            },
+        ];
+        
+        ProgressionSystem {
+            levels,
+            common_words,
+        }
+    }
+
+    pub fn level(&self, level: u8) -> Option<&ProgressionLevel> {
+        self.levels.iter().find(|l| l.level == level)
+    }
+
+    /// Response time target for `c` when promoting out of `level`. Characters introduced at
+    /// `level` get its slack, older ones must meet the plain requirement.
+    pub fn speed_target(&self, level: u8, c: char) -> Option<f32> {
+        let current = self.level(level)?;
+        if current.chars_to_learn.contains(&c) {
+            Some(current.speed_requirement * current.new_char_slack)
+        } else {
+            Some(current.speed_requirement)
+        }
+    }
+
+    /// Checks every character up to `level` against its own target.
+    pub fn speed_check(&self, level: u8, response_times: &HashMap<char, f32>) -> Vec<CharSpeed> {
+        self.chars_through(level).into_iter()
+            .filter_map(|ch| {
+                self.speed_target(level, ch).map(|target| CharSpeed {
+                    ch,
+                    time: response_times.get(&ch).copied(),
+                    target,
+                })
+            })
+            .collect()
+    }
+
+    /// Drops `chars` from every level and removes the words that are listed in `words` or
+    /// contain a dropped character. Words are compared in uppercase.
+    pub fn exclude(&mut self, chars: &[char], words: &[String]) {
+        for level in &mut self.levels {
+            level.chars_to_learn.retain(|c| !chars.contains(c));
+        }
+        self.common_words.retain(|w| !words.contains(w) && !w.chars().any(|c| chars.contains(&c)));
+    }
+
+    /// `chars` the way a list of known characters should be: upper-case, each once, only
+    /// those the curriculum teaches and in the order it teaches them.
+    pub fn curriculum_order(&self, chars: &[char]) -> Vec<char> {
+        let wanted: HashSet<char> = chars.iter().map(|c| c.to_ascii_uppercase()).collect();
+        self.chars_through(u8::MAX).into_iter().filter(|c| wanted.contains(c)).collect()
+    }
+
+    /// Every character introduced up to and including `level`.
+    pub fn chars_through(&self, level: u8) -> Vec<char> {
+        self.levels.iter()
+            .filter(|l| l.level <= level)
+            .flat_map(|l| l.chars_to_learn.iter().copied())
+            .collect()
+    }
+}
+
+impl Default for ProgressionSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn load_common_words() -> Vec<String> {
+    match fs::read_to_string("common_words.txt") {
+        Ok(contents) => {
+            contents.lines()
+                .map(|s| s.trim().to_uppercase())
+                .filter(|s| !s.is_empty())
+                .collect()
+        }
+        Err(_) => {
+            println!("Warning: common_words.txt not found. Using default words.");
+            vec![
+                "THE".to_string(),
+                "BE".to_string(),
+                "TO".to_string(),
+                "OF".to_string(),
+                "AND".to_string(),
+                "A".to_string(),
+                "IN".to_string(),
+                "THAT".to_string(),
+                "HAVE".to_string(),
+                "I".to_string(),
+                "IT".to_string(),
+                "FOR".to_string(),
+                "NOT".to_string(),
+                "ON".to_string(),
+                "WITH".to_string(),
+                "HE".to_string(),
+                "AS".to_string(),
+                "YOU".to_string(),
+                "DO".to_string(),
+                "AT".to_string(),
+            ]
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::{HashMap, HashSet};
+use rand::{seq::IndexedRandom, Rng};
+
+const VOWELS: &str = "AEIOUY";
+const MIN_LENGTH: usize = 3;
+const MAX_LENGTH: usize = 5;
+const ATTEMPTS: usize = 20;
+/// How often the fallback alternates vowels and consonants rather than picking freely.
+const ALTERNATION_BIAS: f64 = 0.75;
+
+fn is_vowel(c: char) -> bool {
+    VOWELS.contains(c)
+}
+
+/// Generates pronounceable fake words from a restricted alphabet. Letter transitions are
+/// learned from a word list (a bigram Markov chain); where the list has nothing to offer
+/// the generator alternates vowels and consonants instead.
+#[derive(Debug, Clone)]
+pub struct PseudoWordGenerator {
+    letters: Vec<char>,
+    starts: Vec<char>,
+    transitions: HashMap<char, Vec<char>>,
+    real_words: HashSet<String>,
+}
+
+impl PseudoWordGenerator {
+    pub fn new(known_chars: &[char], corpus: &[String]) -> Self {
+        let letters: Vec<char> = known_chars.iter()
+            .map(|c| c.to_ascii_uppercase())
+            .filter(|c| c.is_ascii_alphabetic())
+            .collect::<HashSet<char>>()
+            .into_iter()
+            .collect();
+        let known: HashSet<char> = letters.iter().copied().collect();
+
+        let mut starts = Vec::new();
+        let mut transitions: HashMap<char, Vec<char>> = HashMap::new();
+        for word in corpus {
+            let chars: Vec<char> = word.chars().map(|c| c.to_ascii_uppercase()).collect();
+            if let Some(first) = chars.first().filter(|c| known.contains(c)) {
+                starts.push(*first);
+            }
+            for pair in chars.windows(2) {
+                if known.contains(&pair[0]) && known.contains(&pair[1]) {
+                    transitions.entry(pair[0]).or_default().push(pair[1]);
+                }
+            }
+        }
+
+        let mut letters = letters;
+        letters.sort_unstable();
+        PseudoWordGenerator {
+            letters,
+            starts,
+            transitions,
+            real_words: corpus.iter().map(|w| w.to_uppercase()).collect(),
+        }
+    }
+
+    /// Whether there are enough letters to build words at all.
+    pub fn is_usable(&self) -> bool {
+        self.letters.len() >= 2
+    }
+
+    fn next_letter<R: Rng + ?Sized>(&self, prev: char, rng: &mut R) -> char {
+        if let Some(next) = self.transitions.get(&prev).and_then(|t| t.choose(rng)) {
+            return *next;
+        }
+        let alternating: Vec<char> = self.letters.iter()
+            .copied()
+            .filter(|c| is_vowel(*c) != is_vowel(prev))
+            .collect();
+        let candidates = if rng.random_bool(ALTERNATION_BIAS) && !alternating.is_empty() {
+            &alternating
+        } else {
+            &self.letters
+        };
+        *candidates.choose(rng).unwrap_or(&prev)
+    }
+
+    fn build<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let length = rng.random_range(MIN_LENGTH..=MAX_LENGTH);
+        let mut current = *self.starts.choose(rng)
+            .or_else(|| self.letters.choose(rng))
+            .unwrap_or(&'E');
+        let mut word = String::from(current);
+        while word.len() < length {
+            current = self.next_letter(current, rng);
+            word.push(current);
+        }
+        word
+    }
+
+    /// Generates one word, avoiding real words from the corpus where possible.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let mut word = self.build(rng);
+        for _ in 0..ATTEMPTS {
+            if !self.real_words.contains(&word) {
+                break;
+            }
+            word = self.build(rng);
+        }
+        word
+    }
+}
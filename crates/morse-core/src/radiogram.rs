@@ -0,0 +1,101 @@
+use rand::{seq::IndexedRandom, Rng};
+use crate::material;
+
+const PRECEDENCES: [&str; 4] = ["R", "R", "W", "P"];
+/// Handling instructions; most messages carry none.
+const HANDLING: [&str; 4] = ["HXA50", "HXC", "HXE", "HXG"];
+const MONTHS: [&str; 12] = ["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+const PLACES: [&str; 12] = [
+    "NEWINGTON CT", "DAYTON OH", "DENVER CO", "AUSTIN TX", "BOISE ID", "TAMPA FL",
+    "FRESNO CA", "MADISON WI", "RALEIGH NC", "TUCSON AZ", "ALBANY NY", "SALEM OR",
+];
+const ZIP_PREFIXES: [&str; 12] = ["061", "454", "802", "787", "837", "336", "937", "537", "276", "857", "122", "973"];
+const FIRST_NAMES: [&str; 12] = ["JOHN", "MARY", "DAVE", "ANNA", "BOB", "LINDA", "TOM", "SUE", "JIM", "KATE", "BILL", "JANE"];
+const LAST_NAMES: [&str; 12] = ["SMITH", "JONES", "BROWN", "MILLER", "DAVIS", "WILSON", "MOORE", "TAYLOR", "CLARK", "LEWIS", "HALL", "YOUNG"];
+const STREETS: [&str; 10] = ["MAIN ST", "OAK AVE", "ELM ST", "PARK RD", "HILL ST", "LAKE DR", "PINE ST", "MAPLE AVE", "CEDAR LN", "RIVER RD"];
+/// ARRL numbered radiogram texts, sent as `ARL` and the number spelled out.
+const ARL_TEXTS: [&str; 6] = [
+    "ARL FIFTY", "ARL FORTY SIX", "ARL SIXTY SEVEN", "ARL FORTY SEVEN", "ARL FIFTY ONE", "ARL SIXTY",
+];
+
+/// One field of a radiogram form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: &'static str,
+    pub value: String,
+}
+
+impl Field {
+    fn new(name: &'static str, value: impl Into<String>) -> Self {
+        Field { name, value: value.into() }
+    }
+}
+
+/// An NTS radiogram: the preamble, the addressee, the text and the signature.
+#[derive(Debug, Clone)]
+pub struct Radiogram {
+    pub preamble: Vec<Field>,
+    pub address: Vec<Field>,
+    pub text: Field,
+    pub signature: Field,
+}
+
+impl Radiogram {
+    /// A random message whose text draws on `words`, with `X` for each full stop.
+    pub fn random<R: Rng + ?Sized>(words: &[String], rng: &mut R) -> Self {
+        let text = if words.len() < 5 || rng.random_bool(0.25) {
+            ARL_TEXTS.choose(rng).unwrap_or(&ARL_TEXTS[0]).to_string()
+        } else {
+            let length = rng.random_range(5..=10);
+            let mut text: Vec<String> = words.choose_multiple(rng, length).cloned().collect();
+            if text.len() > 5 {
+                let stop = rng.random_range(2..text.len() - 2);
+                text.insert(stop, "X".to_string());
+            }
+            text.join(" ")
+        };
+        let check = text.split_whitespace().count();
+        let check = if text.starts_with("ARL") { format!("ARL {}", check) } else { check.to_string() };
+
+        let mut preamble = vec![
+            Field::new("Number", rng.random_range(1..=999).to_string()),
+            Field::new("Precedence", *PRECEDENCES.choose(rng).unwrap_or(&"R")),
+        ];
+        if rng.random_bool(0.2) {
+            preamble.push(Field::new("Handling", *HANDLING.choose(rng).unwrap_or(&"HXG")));
+        }
+        preamble.extend([
+            Field::new("Station of origin", material::callsigns(1, rng).remove(0)),
+            Field::new("Check", check),
+            Field::new("Place of origin", *PLACES.choose(rng).unwrap_or(&PLACES[0])),
+            Field::new("Time filed", format!("{:02}{:02}Z", rng.random_range(0..24), rng.random_range(0..60))),
+            Field::new("Date", format!("{} {}", MONTHS.choose(rng).unwrap_or(&"JAN"), rng.random_range(1..=28))),
+        ]);
+
+        let destination = rng.random_range(0..PLACES.len());
+        let address = vec![
+            Field::new("Name", format!("{} {}", FIRST_NAMES.choose(rng).unwrap_or(&"JOHN"), LAST_NAMES.choose(rng).unwrap_or(&"SMITH"))),
+            Field::new("Street", format!("{} {}", rng.random_range(1..=999), STREETS.choose(rng).unwrap_or(&"MAIN ST"))),
+            Field::new("City", format!("{} {}{:02}", PLACES[destination], ZIP_PREFIXES[destination], rng.random_range(0..100))),
+            Field::new("Phone", format!("{} {} {:04}", rng.random_range(200..1000), rng.random_range(200..1000), rng.random_range(0..10000))),
+        ];
+
+        Radiogram {
+            preamble,
+            address,
+            text: Field::new("Text", text),
+            signature: Field::new("Signature", *FIRST_NAMES.choose(rng).unwrap_or(&"JOHN")),
+        }
+    }
+
+    /// The message's parts as sent on CW, each with the fields copied from it.
+    pub fn parts(&self) -> Vec<(String, Vec<&Field>)> {
+        let values = |fields: &[Field]| fields.iter().map(|f| f.value.as_str()).collect::<Vec<_>>().join(" ");
+        vec![
+            (format!("NR {}", values(&self.preamble)), self.preamble.iter().collect()),
+            (values(&self.address), self.address.iter().collect()),
+            (format!("BT {} BT", self.text.value), vec![&self.text]),
+            (format!("{} AR", self.signature.value), vec![&self.signature]),
+        ]
+    }
+}
@@ -0,0 +1,32 @@
+use std::collections::{BTreeSet, HashMap};
+
+/// Hints on one item that send it to rehab.
+pub const HINTS_TO_REHAB: usize = 3;
+/// Correct answers in a row without a hint that clear an item's hints, bringing it back
+/// to the normal queues.
+pub const CLEAN_TO_GRADUATE: usize = 3;
+
+/// Items that needed a hint too often and haven't been recalled cleanly since.
+/// `answers` are `(item, correct, hinted)` in chronological order.
+pub fn in_rehab<'a>(answers: impl IntoIterator<Item = (&'a str, bool, bool)>) -> BTreeSet<String> {
+    // Per item: hints since its hints were last cleared, and clean answers in a row.
+    let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+    for (item, correct, hinted) in answers {
+        let (hints, clean) = counts.entry(item).or_default();
+        if hinted {
+            *hints += 1;
+            *clean = 0;
+        } else if correct {
+            *clean += 1;
+            if *clean >= CLEAN_TO_GRADUATE {
+                *hints = 0;
+            }
+        } else {
+            *clean = 0;
+        }
+    }
+    counts.into_iter()
+        .filter(|(_, (hints, _))| *hints >= HINTS_TO_REHAB)
+        .map(|(item, _)| item.to_string())
+        .collect()
+}
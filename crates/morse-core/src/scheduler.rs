@@ -0,0 +1,200 @@
+use std::collections::{BTreeSet, VecDeque};
+use rand::{seq::SliceRandom, Rng};
+use crate::{difficulty, morse, progression::ProgressionLevel, pseudowords::PseudoWordGenerator};
+
+pub const CHAR_REPETITIONS: usize = 5;
+pub const WORDS_PER_SESSION: usize = 10;
+/// Alphabets smaller than this get sessions sized to the configured duration.
+pub const SMALL_ALPHABET: usize = 8;
+/// Rough pace of character practice, used to size those sessions.
+pub const ITEMS_PER_MINUTE: usize = 8;
+/// Items similar to a missed one that are moved to the front of the queue.
+pub const PULL_FORWARD: usize = 2;
+
+/// Marks a decode exercise, queued as the marker followed by the code and answered with the
+/// character. Plain codes would be mistaken for punctuation like `.` and `-`.
+pub const DECODE_MARKER: char = '~';
+
+pub fn decode_item(code: &str) -> String {
+    format!("{}{}", DECODE_MARKER, code)
+}
+
+/// The code of a decode exercise, or `None` for an ordinary item.
+pub fn decode_code(item: &str) -> Option<&str> {
+    item.strip_prefix(DECODE_MARKER)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Scheduler {
+    queue: VecDeque<String>,
+}
+
+impl Scheduler {
+    pub fn for_chars<R: Rng + ?Sized>(
+        known_chars: &[char],
+        level: Option<&ProgressionLevel>,
+        rng: &mut R,
+    ) -> Self {
+        let chars = Self::practice_chars(known_chars, level, rng);
+        
+        let mut queue = VecDeque::new();
+        for _ in 0..CHAR_REPETITIONS {
+            for c in &chars {
+                queue.push_back(c.to_string());
+            }
+        }
+        Scheduler { queue }
+    }
+
+    /// Like `for_chars`, but a small alphabet is repeated until the session fills about
+    /// `minutes`, in a fresh order each round, with every other round asking to decode.
+    pub fn for_session<R: Rng + ?Sized>(
+        known_chars: &[char],
+        level: Option<&ProgressionLevel>,
+        minutes: u32,
+        rng: &mut R,
+    ) -> Self {
+        let chars = Self::practice_chars(known_chars, level, rng);
+        if chars.is_empty() || chars.len() >= SMALL_ALPHABET {
+            return Self::for_chars(known_chars, level, rng);
+        }
+
+        let rounds = (minutes as usize * ITEMS_PER_MINUTE).div_ceil(chars.len()).max(CHAR_REPETITIONS);
+        let mut queue = VecDeque::new();
+        let mut previous = None;
+        for round in 0..rounds {
+            let mut order = chars.clone();
+            order.shuffle(rng);
+            if order.len() > 1 && order.first() == previous.as_ref() {
+                order.swap(0, 1);
+            }
+            previous = order.last().copied();
+            for c in order {
+                match morse::char_to_morse(c) {
+                    Some(code) if round % 2 == 1 => queue.push_back(decode_item(code)),
+                    _ => queue.push_back(c.to_string()),
+                }
+            }
+        }
+        Scheduler { queue }
+    }
+
+    fn practice_chars<R: Rng + ?Sized>(known_chars: &[char], level: Option<&ProgressionLevel>, rng: &mut R) -> Vec<char> {
+        let mut chars = known_chars.to_vec();
+        chars.shuffle(rng);
+        
+        if let Some(level) = level {
+            for c in &level.chars_to_learn {
+                if !chars.contains(c) {
+                    chars.push(*c);
+                }
+            }
+        }
+        chars
+    }
+
+    /// Picks random words and orders them from easiest to hardest so the session ramps up.
+    pub fn for_words<R: Rng + ?Sized>(words: &[String], rng: &mut R) -> Self {
+        let mut selected_words = words.to_vec();
+        selected_words.shuffle(rng);
+        selected_words.truncate(WORDS_PER_SESSION);
+        difficulty::sort_by_difficulty(&mut selected_words);
+        
+        Scheduler {
+            queue: selected_words.into_iter().collect(),
+        }
+    }
+
+    /// Generates a session of distinct pseudo-words, easiest first.
+    pub fn for_pseudo_words<R: Rng + ?Sized>(generator: &PseudoWordGenerator, rng: &mut R) -> Self {
+        let mut words: Vec<String> = Vec::new();
+        for _ in 0..WORDS_PER_SESSION * 5 {
+            if words.len() == WORDS_PER_SESSION {
+                break;
+            }
+            let word = generator.generate(rng);
+            if !words.contains(&word) {
+                words.push(word);
+            }
+        }
+        difficulty::sort_by_difficulty(&mut words);
+        
+        Scheduler {
+            queue: words.into_iter().collect(),
+        }
+    }
+
+    pub fn from_items(items: Vec<String>) -> Self {
+        Scheduler {
+            queue: items.into_iter().collect(),
+        }
+    }
+
+    /// Spreads `items` through the queue, one after every `every` existing items.
+    pub fn mix_in(&mut self, items: Vec<String>, every: usize) {
+        let every = every.max(1);
+        for (i, item) in items.into_iter().enumerate() {
+            let position = ((i + 1) * every + i).min(self.queue.len());
+            self.queue.insert(position, item);
+        }
+    }
+
+    pub fn next_item(&self) -> Option<&str> {
+        self.queue.front().map(|s| s.as_str())
+    }
+
+    /// Drops the current item when it was answered correctly. A missed item moves to the back
+    /// and up to `PULL_FORWARD` similar items are brought forward to practice the difference.
+    pub fn record(&mut self, correct: bool) {
+        if let Some(item) = self.queue.pop_front() {
+            if !correct {
+                self.bring_forward_similar(&item);
+                self.queue.push_back(item);
+            }
+        }
+    }
+
+    fn bring_forward_similar(&mut self, missed: &str) {
+        let mut taken = Vec::new();
+        let mut i = 0;
+        while i < self.queue.len() && taken.len() < PULL_FORWARD {
+            let item = &self.queue[i];
+            if item != missed && !taken.contains(item) && difficulty::similar_items(missed, item) {
+                taken.extend(self.queue.remove(i));
+            } else {
+                i += 1;
+            }
+        }
+        for item in taken.into_iter().rev() {
+            self.queue.push_front(item);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &String> {
+        self.queue.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Drops every item in `items`, unless that would leave nothing to practice.
+    pub fn remove_all(&mut self, items: &BTreeSet<String>) {
+        if self.queue.iter().any(|item| !items.contains(item)) {
+            self.queue.retain(|item| !items.contains(item));
+        }
+    }
+
+    /// Keeps only the first `len` items.
+    pub fn truncate(&mut self, len: usize) {
+        self.queue.truncate(len);
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+}
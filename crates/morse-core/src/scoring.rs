@@ -0,0 +1,172 @@
+use std::fmt;
+
+/// One condition a copy must meet, written as `name:value` in rule lists.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rule {
+    /// `errors:N`, at most N character errors in all.
+    MaxErrors(usize),
+    /// `errors-per-minute:N`, at most N character errors per minute of sending.
+    MaxErrorsPerMinute(f32),
+    /// `consecutive:N`, never more than N character errors in a row.
+    MaxConsecutiveErrors(usize),
+    /// `solid:N`, at least N characters in a row copied without an error.
+    SolidCopy(usize),
+    /// `accuracy:N`, at least N percent of the characters copied right.
+    MinAccuracy(f32),
+}
+
+impl Rule {
+    pub fn parse(token: &str) -> Result<Self, String> {
+        let (name, value) = token.trim().split_once(':')
+            .ok_or_else(|| format!("'{}' should look like name:value", token.trim()))?;
+        let count = || value.trim().parse::<usize>().map_err(|_| format!("'{}' needs a whole number", name));
+        let number = || value.trim().parse::<f32>().ok()
+            .filter(|n| *n >= 0.0)
+            .ok_or_else(|| format!("'{}' needs a number", name));
+        match name.trim() {
+            "errors" => Ok(Rule::MaxErrors(count()?)),
+            "errors-per-minute" => Ok(Rule::MaxErrorsPerMinute(number()?)),
+            "consecutive" => Ok(Rule::MaxConsecutiveErrors(count()?)),
+            "solid" => Ok(Rule::SolidCopy(count()?)),
+            "accuracy" => Ok(Rule::MinAccuracy(number()?)),
+            other => Err(format!("unknown rule '{}' (errors, errors-per-minute, consecutive, solid, accuracy)", other)),
+        }
+    }
+
+    pub fn passed(self, card: &Scorecard) -> bool {
+        match self {
+            Rule::MaxErrors(errors) => card.errors <= errors,
+            Rule::MaxErrorsPerMinute(rate) => card.errors_per_minute() <= rate,
+            Rule::MaxConsecutiveErrors(errors) => card.consecutive_errors <= errors,
+            Rule::SolidCopy(chars) => card.solid >= chars,
+            Rule::MinAccuracy(percent) => card.accuracy() * 100.0 >= percent,
+        }
+    }
+
+    /// The rule in words, e.g. "at most 4 errors".
+    pub fn describe(self) -> String {
+        match self {
+            Rule::MaxErrors(errors) => format!("at most {} errors", errors),
+            Rule::MaxErrorsPerMinute(rate) => format!("at most {} errors per minute", rate),
+            Rule::MaxConsecutiveErrors(errors) => format!("no more than {} errors in a row", errors),
+            Rule::SolidCopy(chars) => format!("{} characters in a row without an error", chars),
+            Rule::MinAccuracy(percent) => format!("at least {}% copied", percent),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::MaxErrors(n) => write!(f, "errors:{}", n),
+            Rule::MaxErrorsPerMinute(n) => write!(f, "errors-per-minute:{}", n),
+            Rule::MaxConsecutiveErrors(n) => write!(f, "consecutive:{}", n),
+            Rule::SolidCopy(n) => write!(f, "solid:{}", n),
+            Rule::MinAccuracy(n) => write!(f, "accuracy:{}", n),
+        }
+    }
+}
+
+/// Parses a comma-separated rule list such as `errors-per-minute:1,consecutive:3`.
+pub fn parse_rules(spec: &str) -> Result<Vec<Rule>, String> {
+    let rules: Vec<Rule> = spec.split(',')
+        .filter(|token| !token.trim().is_empty())
+        .map(Rule::parse)
+        .collect::<Result<_, _>>()?;
+    if rules.is_empty() {
+        return Err("no rules given".to_string());
+    }
+    Ok(rules)
+}
+
+/// A copy compared character by character with what was sent, spaces ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Scorecard {
+    pub sent: usize,
+    /// Characters left out, added or copied wrong.
+    pub errors: usize,
+    /// The longest run of characters copied without an error.
+    pub solid: usize,
+    /// The longest run of errors.
+    pub consecutive_errors: usize,
+    /// How long the sending took.
+    pub minutes: f32,
+}
+
+fn squeeze(text: &str) -> Vec<char> {
+    text.chars().filter(|c| !c.is_whitespace()).map(|c| c.to_ascii_uppercase()).collect()
+}
+
+impl Scorecard {
+    /// Aligns `copy` with `sent` (fewest edits) and walks the alignment for its runs.
+    pub fn compare(sent: &str, copy: &str, minutes: f32) -> Self {
+        let (sent, copy) = (squeeze(sent), squeeze(copy));
+        let (n, m) = (sent.len(), copy.len());
+        let mut distance = vec![vec![0usize; m + 1]; n + 1];
+        distance[0] = (0..=m).collect();
+        for (i, row) in distance.iter_mut().enumerate() {
+            row[0] = i;
+        }
+        for i in 1..=n {
+            for j in 1..=m {
+                let substitution = distance[i - 1][j - 1] + usize::from(sent[i - 1] != copy[j - 1]);
+                distance[i][j] = substitution.min(distance[i - 1][j] + 1).min(distance[i][j - 1] + 1);
+            }
+        }
+
+        let mut card = Scorecard { sent: n, errors: distance[n][m], minutes, ..Scorecard::default() };
+        let (mut solid, mut wrong) = (0, 0);
+        let (mut i, mut j) = (n, m);
+        while i > 0 || j > 0 {
+            let matched = i > 0 && j > 0 && sent[i - 1] == copy[j - 1] && distance[i][j] == distance[i - 1][j - 1];
+            if matched {
+                solid += 1;
+                wrong = 0;
+                (i, j) = (i - 1, j - 1);
+            } else {
+                wrong += 1;
+                solid = 0;
+                if i > 0 && j > 0 && distance[i][j] == distance[i - 1][j - 1] + 1 {
+                    (i, j) = (i - 1, j - 1);
+                } else if i > 0 && distance[i][j] == distance[i - 1][j] + 1 {
+                    i -= 1;
+                } else {
+                    j -= 1;
+                }
+            }
+            card.solid = card.solid.max(solid);
+            card.consecutive_errors = card.consecutive_errors.max(wrong);
+        }
+        card
+    }
+
+    /// Adds the results of another item sent in the same session.
+    pub fn add(&mut self, other: &Scorecard) {
+        self.sent += other.sent;
+        self.errors += other.errors;
+        self.solid = self.solid.max(other.solid);
+        self.consecutive_errors = self.consecutive_errors.max(other.consecutive_errors);
+        self.minutes += other.minutes;
+    }
+
+    /// Share of the sent characters copied right, from 0 to 1.
+    pub fn accuracy(&self) -> f32 {
+        if self.sent == 0 {
+            return 1.0;
+        }
+        1.0 - (self.errors as f32 / self.sent as f32).min(1.0)
+    }
+
+    pub fn errors_per_minute(&self) -> f32 {
+        if self.minutes > 0.0 {
+            self.errors as f32 / self.minutes
+        } else {
+            self.errors as f32
+        }
+    }
+
+    /// Each rule with whether this copy meets it.
+    pub fn judge(&self, rules: &[Rule]) -> Vec<(Rule, bool)> {
+        rules.iter().map(|rule| (*rule, rule.passed(self))).collect()
+    }
+}
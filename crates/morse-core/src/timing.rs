@@ -0,0 +1,281 @@
+use rand::Rng;
+use crate::morse;
+
+/// Length of one dot at the PARIS standard: `1200 / wpm` milliseconds.
+pub fn unit_ms_for_wpm(wpm: f32) -> f32 {
+    1200.0 / wpm
+}
+
+/// Nominal lengths in units: dot 1, dash 3, element gap 1, letter gap 3, word gap 7.
+/// Durations are classified against thresholds between those lengths, so every element
+/// still decodes correctly while it is off by less than this many units.
+pub const MAX_JITTER_UNITS: f32 = 1.0;
+
+const DASH_THRESHOLD: f32 = 2.0;
+const LETTER_GAP_THRESHOLD: f32 = 2.0;
+const WORD_GAP_THRESHOLD: f32 = 5.0;
+/// How far each mark moves an adaptive decoder's dot length toward its own.
+const ADAPT_RATE: f32 = 0.2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimingEvent {
+    /// Key down (tone on) for the given number of milliseconds.
+    Mark(f32),
+    /// Key up (silence) for the given number of milliseconds.
+    Space(f32),
+}
+
+/// Element and gap lengths for sending: elements at the character speed, gaps between
+/// characters and words possibly stretched so the overall (effective) speed is lower.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spacing {
+    pub unit_ms: f32,
+    pub letter_gap_ms: f32,
+    pub word_gap_ms: f32,
+}
+
+impl Spacing {
+    /// Standard PARIS proportions: letter gap 3 units, word gap 7.
+    pub fn standard(unit_ms: f32) -> Self {
+        Spacing {
+            unit_ms,
+            letter_gap_ms: 3.0 * unit_ms,
+            word_gap_ms: 7.0 * unit_ms,
+        }
+    }
+
+    /// Farnsworth timing: characters sent at `character_wpm` with the letter and word gaps
+    /// lengthened so PARIS takes as long as at `effective_wpm`. The 19 gap units of PARIS
+    /// (3 per letter gap, 7 for the word gap) share the extra time.
+    pub fn farnsworth(character_wpm: f32, effective_wpm: f32) -> Self {
+        let unit_ms = unit_ms_for_wpm(character_wpm);
+        if effective_wpm >= character_wpm {
+            return Spacing::standard(unit_ms);
+        }
+        // 31 of the 50 PARIS units are elements and element gaps; the rest fills the gaps.
+        let gap_unit_ms = (60_000.0 / effective_wpm - 31.0 * unit_ms) / 19.0;
+        Spacing {
+            unit_ms,
+            letter_gap_ms: 3.0 * gap_unit_ms,
+            word_gap_ms: 7.0 * gap_unit_ms,
+        }
+    }
+
+    /// How long one PARIS word takes with its word gap, the pace of the overall speed.
+    pub fn word_ms(&self) -> f32 {
+        31.0 * self.unit_ms + 4.0 * self.letter_gap_ms + self.word_gap_ms
+    }
+}
+
+/// Ideal key timings for a code string produced by [`morse::encode`].
+pub fn timings_for_code(code: &str, unit_ms: f32) -> Vec<TimingEvent> {
+    timings_with_spacing(code, Spacing::standard(unit_ms))
+}
+
+/// Key timings for a code string with the given gap lengths.
+pub fn timings_with_spacing(code: &str, spacing: Spacing) -> Vec<TimingEvent> {
+    let unit_ms = spacing.unit_ms;
+    let mut events = Vec::new();
+    for (w, word) in code.split('/').map(str::trim).filter(|w| !w.is_empty()).enumerate() {
+        if w > 0 {
+            events.push(TimingEvent::Space(spacing.word_gap_ms));
+        }
+        for (c, letter) in word.split_whitespace().enumerate() {
+            if c > 0 {
+                events.push(TimingEvent::Space(spacing.letter_gap_ms));
+            }
+            for (e, element) in letter.chars().enumerate() {
+                if e > 0 {
+                    events.push(TimingEvent::Space(unit_ms));
+                }
+                match element {
+                    '.' => events.push(TimingEvent::Mark(unit_ms)),
+                    '-' => events.push(TimingEvent::Mark(3.0 * unit_ms)),
+                    _ => {}
+                }
+            }
+        }
+    }
+    events
+}
+
+pub fn timings_for_text(text: &str, unit_ms: f32) -> Vec<TimingEvent> {
+    timings_for_code(&morse::encode(text), unit_ms)
+}
+
+/// Largest fraction a human fist may stretch or shorten an element or gap by. Beyond it a
+/// shortened dash would get too close to a dot, or a shortened word gap to a letter gap.
+pub const MAX_FIST_JITTER: f32 = 0.25;
+
+/// How irregular a human sender is, as the fraction each length may be off by either way.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fist {
+    /// Dots and dashes.
+    pub element: f32,
+    /// Gaps between elements, characters and words.
+    pub spacing: f32,
+}
+
+/// `events` as a human might key them: every mark and space is stretched or shortened by a
+/// random fraction within the fist's bounds.
+pub fn humanize<R: Rng + ?Sized>(events: &[TimingEvent], fist: Fist, rng: &mut R) -> Vec<TimingEvent> {
+    let element = fist.element.clamp(0.0, MAX_FIST_JITTER);
+    let spacing = fist.spacing.clamp(0.0, MAX_FIST_JITTER);
+    events.iter()
+        .map(|event| match *event {
+            TimingEvent::Mark(ms) => TimingEvent::Mark(ms * (1.0 + rng.random_range(-element..=element))),
+            TimingEvent::Space(ms) => TimingEvent::Space(ms * (1.0 + rng.random_range(-spacing..=spacing))),
+        })
+        .collect()
+}
+
+/// Parses a timing trace of durations in milliseconds: positive values are key-down,
+/// negative values key-up, e.g. `60 -60 180 -180 60`.
+pub fn parse_timing_trace(trace: &str) -> Result<Vec<TimingEvent>, String> {
+    trace.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty())
+        .map(|t| {
+            let ms: f32 = t.parse().ok()
+                .filter(|ms: &f32| ms.is_finite())
+                .ok_or_else(|| format!("invalid duration '{}'", t))?;
+            if ms > 0.0 {
+                Ok(TimingEvent::Mark(ms))
+            } else if ms < 0.0 {
+                Ok(TimingEvent::Space(-ms))
+            } else {
+                Err("durations must not be zero".to_string())
+            }
+        })
+        .collect()
+}
+
+/// Estimates the dot length from the key-down durations. Marks are split at the midpoint
+/// between the shortest and longest one; if they are all similar they are taken as dots.
+pub fn estimate_unit_ms(events: &[TimingEvent]) -> Option<f32> {
+    let marks: Vec<f32> = events.iter()
+        .filter_map(|e| match e {
+            TimingEvent::Mark(ms) => Some(*ms),
+            TimingEvent::Space(_) => None,
+        })
+        .collect();
+    let shortest = marks.iter().copied().reduce(f32::min)?;
+    let longest = marks.iter().copied().reduce(f32::max)?;
+
+    if longest < shortest * DASH_THRESHOLD {
+        return Some(marks.iter().sum::<f32>() / marks.len() as f32);
+    }
+    let split = (shortest + longest) / 2.0;
+    let dots: Vec<f32> = marks.iter().copied().filter(|ms| *ms < split).collect();
+    Some(dots.iter().sum::<f32>() / dots.len() as f32)
+}
+
+/// Streaming decoder turning key-down/key-up durations into text.
+#[derive(Debug, Clone)]
+pub struct TimingDecoder {
+    unit_ms: f32,
+    adaptive: bool,
+    elements: String,
+    text: String,
+    code: String,
+}
+
+impl TimingDecoder {
+    pub fn new(unit_ms: f32) -> Self {
+        TimingDecoder {
+            unit_ms,
+            adaptive: false,
+            elements: String::new(),
+            text: String::new(),
+            code: String::new(),
+        }
+    }
+
+    /// Follows the sender's speed: every mark moves the dot length a little toward its
+    /// own, so `unit_ms` only needs to be a first guess.
+    pub fn adaptive(mut self) -> Self {
+        self.adaptive = true;
+        self
+    }
+
+    pub fn from_wpm(wpm: f32) -> Self {
+        Self::new(unit_ms_for_wpm(wpm))
+    }
+
+    pub fn unit_ms(&self) -> f32 {
+        self.unit_ms
+    }
+
+    pub fn feed(&mut self, event: TimingEvent) {
+        match event {
+            TimingEvent::Mark(ms) => {
+                let dot = ms / self.unit_ms < DASH_THRESHOLD;
+                self.elements.push(if dot { '.' } else { '-' });
+                if self.adaptive {
+                    let unit_ms = if dot { ms } else { ms / 3.0 };
+                    self.unit_ms += ADAPT_RATE * (unit_ms - self.unit_ms);
+                }
+            }
+            TimingEvent::Space(ms) => {
+                let units = ms / self.unit_ms;
+                if units >= LETTER_GAP_THRESHOLD {
+                    self.finish_letter();
+                }
+                if units >= WORD_GAP_THRESHOLD && !self.text.is_empty() && !self.text.ends_with(' ') {
+                    self.text.push(' ');
+                }
+                if units >= WORD_GAP_THRESHOLD && !self.code.is_empty() && !self.code.ends_with(' ') {
+                    self.code.push_str(morse::WORD_SEPARATOR);
+                }
+            }
+        }
+    }
+
+    /// Flushes the letter currently being keyed, e.g. once input has stopped.
+    pub fn finish(&mut self) {
+        self.finish_letter();
+    }
+
+    /// Returns everything decoded so far and clears the output buffer.
+    pub fn take_text(&mut self) -> String {
+        std::mem::take(&mut self.text)
+    }
+
+    /// Returns the code decoded so far, e.g. `.- -...`, and clears it.
+    pub fn take_code(&mut self) -> String {
+        std::mem::take(&mut self.code)
+    }
+
+    fn finish_letter(&mut self) {
+        if self.elements.is_empty() {
+            return;
+        }
+        self.text.push(morse::morse_to_char(&self.elements).unwrap_or('?'));
+        if !self.code.is_empty() && !self.code.ends_with(' ') {
+            self.code.push(' ');
+        }
+        self.code.push_str(&self.elements);
+        self.elements.clear();
+    }
+}
+
+pub fn decode_timings(events: &[TimingEvent], unit_ms: f32) -> String {
+    let mut decoder = TimingDecoder::new(unit_ms);
+    for event in events {
+        decoder.feed(*event);
+    }
+    decoder.finish();
+    decoder.take_text()
+}
+
+/// Splits a trace at its word gaps, dropping the gaps themselves.
+pub fn split_words(events: &[TimingEvent], unit_ms: f32) -> Vec<Vec<TimingEvent>> {
+    let mut words = vec![Vec::new()];
+    for event in events {
+        match event {
+            TimingEvent::Space(ms) if ms / unit_ms >= WORD_GAP_THRESHOLD => words.push(Vec::new()),
+            _ => words.last_mut().unwrap().push(*event),
+        }
+    }
+    words.retain(|w| w.iter().any(|e| matches!(e, TimingEvent::Mark(_))));
+    words
+}
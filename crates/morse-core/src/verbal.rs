@@ -0,0 +1,89 @@
+/// How a code is spoken, the way it is taught by ear: `-.-.` is "dah-di-dah-dit".
+/// Letters are separated by spaces and words by ` / `.
+pub fn verbalize(code: &str) -> String {
+    code.split('/')
+        .map(|word| {
+            word.split_whitespace()
+                .map(|letter| {
+                    let elements: Vec<char> = letter.chars().collect();
+                    elements.iter().enumerate()
+                        .map(|(i, e)| match (e, i + 1 == elements.len()) {
+                            ('.', true) => "dit",
+                            ('.', false) => "di",
+                            _ => "dah",
+                        })
+                        .collect::<Vec<_>>()
+                        .join("-")
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+/// The code of a spoken form like "dah-di-dah-dit", letters separated by spaces. The
+/// syllables may be run together, and "da" or "dah", "di" or "dit" are all fine.
+/// `None` when anything else is in it.
+pub fn parse(spoken: &str) -> Option<String> {
+    spoken.to_ascii_lowercase()
+        .split_whitespace()
+        .map(|letter| {
+            let mut code = String::new();
+            let mut rest = letter;
+            loop {
+                rest = rest.trim_start_matches('-');
+                if rest.is_empty() {
+                    return Some(code);
+                }
+                if let Some(after) = rest.strip_prefix("dah").or_else(|| rest.strip_prefix("da")) {
+                    code.push('-');
+                    rest = after;
+                } else if let Some(after) = rest.strip_prefix("dit").or_else(|| rest.strip_prefix("di")) {
+                    code.push('.');
+                    rest = after;
+                } else {
+                    return None;
+                }
+            }
+        })
+        .collect::<Option<Vec<String>>>()
+        .map(|letters| letters.join(" "))
+}
+
+/// Words to remember letters by, spoken with the stress on the dahs: a capitalized
+/// syllable is a dah, a lowercase one a dit.
+const MNEMONICS: [(char, &str); 26] = [
+    ('A', "a-PART"),
+    ('B', "BOIS-ter-ous-ly"),
+    ('C', "CA-ro-LI-na"),
+    ('D', "DAN-ger-ous"),
+    ('E', "eh"),
+    ('F', "fe-ro-CIOUS-ly"),
+    ('G', "GOOD-NESS-me"),
+    ('H', "ha-ha-ha-ha"),
+    ('I', "is-it"),
+    ('J', "a-MAZ-ING-GRACE"),
+    ('K', "KAN-ga-ROO"),
+    ('L', "el-EV-a-tor"),
+    ('M', "MOO-MOO"),
+    ('N', "NA-vy"),
+    ('O', "ONE-TWO-THREE"),
+    ('P', "a-POL-O-gize"),
+    ('Q', "GOD-SAVE-the-QUEEN"),
+    ('R', "ro-TA-tion"),
+    ('S', "sis-ter-ly"),
+    ('T', "TALL"),
+    ('U', "un-i-FORM"),
+    ('V', "vic-to-ry-VEE"),
+    ('W', "a-WHOLE-WEEK"),
+    ('X', "X-marks-the-SPOT"),
+    ('Y', "YEL-low-YO-YO"),
+    ('Z', "ZE-BRA-cross-ing"),
+];
+
+/// A word to remember a letter by, e.g. "CA-ro-LI-na" for C. Only letters have one.
+pub fn mnemonic(c: char) -> Option<&'static str> {
+    let c = c.to_ascii_uppercase();
+    MNEMONICS.iter().find(|(letter, _)| *letter == c).map(|(_, word)| *word)
+}
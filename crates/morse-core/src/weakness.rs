@@ -0,0 +1,53 @@
+use std::collections::BTreeMap;
+use crate::morse;
+
+/// Letters need this many attempts inside words before their error rate is trusted.
+pub const MIN_ATTEMPTS: u32 = 3;
+pub const WEAK_ERROR_RATE: f32 = 0.25;
+
+/// Errors and attempts for each letter sent as part of a word.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LetterStats {
+    pub errors: u32,
+    pub attempts: u32,
+}
+
+impl LetterStats {
+    pub fn error_rate(&self) -> f32 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.errors as f32 / self.attempts as f32
+        }
+    }
+}
+
+/// Attributes word answers to their letters: a letter counts as wrong when the code in its
+/// position of the answer differs from its own code.
+pub fn letter_stats<'a>(answers: impl IntoIterator<Item = (&'a str, &'a str)>) -> BTreeMap<char, LetterStats> {
+    let mut stats: BTreeMap<char, LetterStats> = BTreeMap::new();
+    for (word, answer) in answers {
+        let mut sent = answer.split_whitespace();
+        for c in word.chars() {
+            let Some(expected) = morse::char_to_morse(c) else {
+                continue;
+            };
+            let entry = stats.entry(c).or_default();
+            entry.attempts += 1;
+            if sent.next() != Some(expected) {
+                entry.errors += 1;
+            }
+        }
+    }
+    stats
+}
+
+/// Letters with enough attempts and an error rate of at least `WEAK_ERROR_RATE`, worst first.
+pub fn weak_letters(stats: &BTreeMap<char, LetterStats>) -> Vec<char> {
+    let mut weak: Vec<(char, f32)> = stats.iter()
+        .filter(|(_, s)| s.attempts >= MIN_ATTEMPTS && s.error_rate() >= WEAK_ERROR_RATE)
+        .map(|(c, s)| (*c, s.error_rate()))
+        .collect();
+    weak.sort_by(|a, b| b.1.total_cmp(&a.1));
+    weak.into_iter().map(|(c, _)| c).collect()
+}
@@ -0,0 +1,52 @@
+use morse_core::{
+    keyer::{self, KeyChange, KeyerMode, KeyerSettings, PaddleEvent, Paddles},
+    timing::{self, TimingEvent::{Mark, Space}},
+};
+
+const UNIT_MS: f32 = 60.0;
+
+fn change(at_ms: f32, left: bool, right: bool) -> PaddleEvent {
+    PaddleEvent { at_ms, paddles: Paddles { left, right } }
+}
+
+fn keyed(changes: &[PaddleEvent], mode: KeyerMode, reverse: bool) -> String {
+    let events = keyer::key_paddles(changes, KeyerSettings { mode, reverse }, UNIT_MS);
+    timing::decode_timings(&events, UNIT_MS)
+}
+
+#[test]
+fn squeeze_released_during_dah_sends_extra_dit_only_in_mode_b() {
+    // Squeezed from the start and let go during the dah: .- in mode A, .-. in mode B.
+    let changes = [change(0.0, true, true), change(250.0, false, false)];
+    assert_eq!(keyed(&changes, KeyerMode::IambicA, false), "A");
+    assert_eq!(keyed(&changes, KeyerMode::IambicB, false), "R");
+}
+
+#[test]
+fn reverse_swaps_the_paddles() {
+    let changes = [change(0.0, false, true), change(100.0, false, false)];
+    assert_eq!(keyed(&changes, KeyerMode::IambicB, false), "T");
+    assert_eq!(keyed(&changes, KeyerMode::IambicB, true), "E");
+}
+
+#[test]
+fn bug_dah_lasts_as_long_as_the_paddle_is_held() {
+    let changes = [change(0.0, false, true), change(200.0, false, false)];
+    let events = keyer::key_paddles(&changes, KeyerSettings { mode: KeyerMode::Bug, reverse: false }, UNIT_MS);
+    assert_eq!(events, vec![timing::TimingEvent::Mark(200.0)]);
+}
+
+#[test]
+fn straight_key_holds_become_marks_and_the_time_between_them_spaces() {
+    let press = |at_ms: f32, held_ms: f32| [
+        KeyChange { at_ms, down: true },
+        KeyChange { at_ms: at_ms + held_ms, down: false },
+    ];
+    // A dit, a bounced dah and a dit at a 60 ms dot, then a letter gap before a dah: R T.
+    let mut changes: Vec<KeyChange> = [press(0.0, 55.0), press(120.0, 190.0), press(370.0, 65.0), press(620.0, 175.0)]
+        .concat();
+    changes.insert(3, KeyChange { at_ms: 125.0, down: true });
+    let events = keyer::key_straight(&changes);
+    assert_eq!(events[..3], [Mark(55.0), Space(65.0), Mark(190.0)]);
+    assert_eq!(timing::decode_timings(&events, UNIT_MS), "RT");
+}
@@ -0,0 +1,144 @@
+use morse_core::{
+    morse::{self, MORSE_MAPPING, PUNCTUATION_MAPPING},
+    timing::{self, Fist, Spacing, TimingEvent, MAX_FIST_JITTER, MAX_JITTER_UNITS},
+    verbal,
+};
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+fn supported_char() -> impl Strategy<Value = char> {
+    proptest::sample::select(MORSE_MAPPING.iter().chain(&PUNCTUATION_MAPPING).map(|(c, _)| *c).collect::<Vec<char>>())
+}
+
+fn word() -> impl Strategy<Value = String> {
+    proptest::collection::vec(supported_char(), 1..8).prop_map(|chars| chars.into_iter().collect())
+}
+
+fn text() -> impl Strategy<Value = String> {
+    proptest::collection::vec(word(), 1..6).prop_map(|words| words.join(" "))
+}
+
+fn jitter(event: TimingEvent, unit_ms: f32, offset: f32) -> TimingEvent {
+    match event {
+        TimingEvent::Mark(ms) => TimingEvent::Mark(ms + offset * unit_ms),
+        TimingEvent::Space(ms) => TimingEvent::Space(ms + offset * unit_ms),
+    }
+}
+
+#[test]
+fn every_mapping_entry_round_trips() {
+    for (c, code) in MORSE_MAPPING.into_iter().chain(PUNCTUATION_MAPPING) {
+        assert_eq!(morse::char_to_morse(c), Some(code));
+        assert_eq!(morse::morse_to_char(code), Some(c));
+    }
+}
+
+#[test]
+fn timing_traces_reject_durations_that_are_not_finite() {
+    assert_eq!(timing::parse_timing_trace("60 -60 180"), Ok(vec![
+        TimingEvent::Mark(60.0), TimingEvent::Space(60.0), TimingEvent::Mark(180.0),
+    ]));
+    for trace in ["60 NaN", "60 -inf", "1e40", "60 -0"] {
+        assert!(timing::parse_timing_trace(trace).is_err(), "{}", trace);
+    }
+}
+
+proptest! {
+    #[test]
+    fn encode_decode_round_trip(text in text()) {
+        prop_assert_eq!(morse::decode(&morse::encode(&text)), text);
+    }
+
+    #[test]
+    fn lowercase_input_decodes_to_uppercase(text in text()) {
+        prop_assert_eq!(morse::decode(&morse::encode(&text.to_lowercase())), text);
+    }
+
+    #[test]
+    fn exact_timings_round_trip(text in text(), wpm in 5.0f32..60.0) {
+        let unit_ms = timing::unit_ms_for_wpm(wpm);
+        let events = timing::timings_for_text(&text, unit_ms);
+        prop_assert_eq!(timing::decode_timings(&events, unit_ms), text);
+    }
+
+    #[test]
+    fn farnsworth_paris_takes_one_minute_per_effective_word(
+        character_wpm in 10.0f32..40.0,
+        ratio in 0.3f32..1.0,
+    ) {
+        let effective_wpm = character_wpm * ratio;
+        let spacing = Spacing::farnsworth(character_wpm, effective_wpm);
+        let events = timing::timings_with_spacing(&morse::encode("PARIS"), spacing);
+        let word_ms: f32 = events.iter()
+            .map(|e| match e {
+                TimingEvent::Mark(ms) | TimingEvent::Space(ms) => *ms,
+            })
+            .sum::<f32>() + spacing.word_gap_ms;
+        prop_assert!((word_ms - 60_000.0 / effective_wpm).abs() < 1.0);
+        prop_assert!((spacing.word_ms() - word_ms).abs() < 1.0);
+    }
+
+    #[test]
+    fn decoder_tolerates_jitter_within_tolerance(
+        (text, offsets) in text().prop_flat_map(|text| {
+            let len = timing::timings_for_text(&text, 1.0).len();
+            let bound = MAX_JITTER_UNITS * 0.99;
+            (Just(text), proptest::collection::vec(-bound..bound, len))
+        }),
+        wpm in 5.0f32..60.0,
+    ) {
+        let unit_ms = timing::unit_ms_for_wpm(wpm);
+        let events: Vec<TimingEvent> = timing::timings_for_text(&text, unit_ms)
+            .into_iter()
+            .zip(offsets)
+            .map(|(event, offset)| jitter(event, unit_ms, offset))
+            .collect();
+        prop_assert_eq!(timing::decode_timings(&events, unit_ms), text);
+    }
+
+    #[test]
+    fn humanized_timings_still_decode(
+        text in text(),
+        wpm in 5.0f32..60.0,
+        element in 0.0f32..=MAX_FIST_JITTER,
+        spacing in 0.0f32..=MAX_FIST_JITTER,
+        seed in any::<u64>(),
+    ) {
+        let unit_ms = timing::unit_ms_for_wpm(wpm);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let events = timing::humanize(&timing::timings_for_text(&text, unit_ms), Fist { element, spacing }, &mut rng);
+        prop_assert_eq!(timing::decode_timings(&events, unit_ms), text);
+    }
+
+    #[test]
+    fn verbalization_round_trips(word in word()) {
+        let code = morse::encode_word(&word);
+        prop_assert_eq!(verbal::parse(&verbal::verbalize(&code)), Some(code));
+    }
+
+    #[test]
+    fn mnemonics_stress_the_dahs(letter in proptest::char::range('A', 'Z')) {
+        let stressed: String = verbal::mnemonic(letter).unwrap()
+            .split('-')
+            .map(|syllable| if syllable.chars().any(|c| c.is_uppercase()) { '-' } else { '.' })
+            .collect();
+        prop_assert_eq!(Some(stressed.as_str()), morse::char_to_morse(letter));
+    }
+
+    #[test]
+    fn adaptive_decoder_settles_on_the_senders_speed(
+        text in text(),
+        wpm in 5.0f32..60.0,
+        guess in 0.7f32..1.4,
+    ) {
+        let unit_ms = timing::unit_ms_for_wpm(wpm);
+        let mut decoder = timing::TimingDecoder::new(unit_ms * guess).adaptive();
+        for event in timing::timings_for_text(&text, unit_ms) {
+            decoder.feed(event);
+        }
+        decoder.finish();
+        prop_assert_eq!(decoder.take_text(), text.clone());
+        prop_assert_eq!(decoder.take_code(), morse::encode(&text));
+        prop_assert!((decoder.unit_ms() / unit_ms - 1.0).abs() < (guess - 1.0).abs() + 1e-3);
+    }
+}
@@ -0,0 +1,77 @@
+use morse_core::{grading::{self, WordDiff}, keying, scoring::{self, Rule, Scorecard}, timing::{self, TimingEvent::{Mark, Space}}};
+
+#[test]
+fn solid_copy_is_the_longest_run_without_errors() {
+    // One wrong letter splits "CQCQDEK1ABC" into "CQCQDE" and "1ABC".
+    let card = Scorecard::compare("CQ CQ DE K1ABC", "cq cq de x1abc", 1.0);
+    assert_eq!(card.sent, 11);
+    assert_eq!(card.errors, 1);
+    assert_eq!(card.solid, 6);
+    assert!(Rule::MaxErrors(1).passed(&card));
+    assert!(!Rule::SolidCopy(7).passed(&card));
+}
+
+#[test]
+fn missing_and_extra_characters_count_as_errors() {
+    let card = Scorecard::compare("NAME IS BOB", "NAM IS BOBB", 1.0);
+    assert_eq!(card.errors, 2);
+    assert_eq!(card.consecutive_errors, 1);
+}
+
+#[test]
+fn a_dropped_word_is_a_run_of_errors() {
+    let card = Scorecard::compare("RIG IS A K3 AT 100 WATTS", "RIG IS A AT 100 WATTS", 0.5);
+    assert_eq!(card.errors, 2);
+    assert_eq!(card.consecutive_errors, 2);
+    assert_eq!(card.errors_per_minute(), 4.0);
+    let rules = scoring::parse_rules("errors-per-minute:5, consecutive:1").unwrap();
+    assert_eq!(card.judge(&rules), vec![(rules[0], true), (rules[1], false)]);
+}
+
+#[test]
+fn rule_lists_round_trip() {
+    let spec = "errors:4,errors-per-minute:1.5,consecutive:3,solid:65,accuracy:90";
+    let rules = scoring::parse_rules(spec).unwrap();
+    let written: Vec<String> = rules.iter().map(Rule::to_string).collect();
+    assert_eq!(written.join(","), spec);
+    assert!(scoring::parse_rules("speed:20").is_err());
+}
+
+#[test]
+fn rhythm_ignores_tempo_but_not_uneven_spacing() {
+    let ideal = timing::timings_for_code("-.-.", 60.0);
+    // The same pattern keyed at half the speed is still in rhythm.
+    let slow: Vec<f32> = keying::onsets(&ideal).iter().map(|at| at * 2.0).collect();
+    assert_eq!(keying::rhythm(&slow, &ideal, &keying::PADDLE), Some(100.0));
+    // Evenly spaced elements cut the dit short; swapping the long and short steps misses all.
+    assert_eq!(keying::rhythm(&[0.0, 200.0, 400.0, 600.0], &ideal, &keying::PADDLE).map(f32::round), Some(67.0));
+    assert_eq!(keying::rhythm(&[0.0, 100.0, 400.0, 500.0], &ideal, &keying::PADDLE), Some(0.0));
+    assert_eq!(keying::rhythm(&[0.0, 200.0], &ideal, &keying::PADDLE), None);
+}
+
+#[test]
+fn sending_quality_measures_weight_and_spacing_in_the_senders_dots() {
+    let mut tally = keying::SendingTally::default();
+    assert_eq!(tally.quality(), None);
+    // "AN" with short dashes, long gaps inside characters and cramped ones between them.
+    tally.add(&[Space(400.0), Mark(100.0), Space(150.0), Mark(250.0), Space(250.0), Mark(250.0), Space(150.0), Mark(100.0), Space(1500.0)], 100.0);
+    let quality = tally.quality().unwrap();
+    assert_eq!(quality.dash_dot_ratio, Some(2.5));
+    assert_eq!(quality.element_gap, Some(1.5));
+    assert_eq!(quality.element_gap_spread, Some(0.0));
+    assert_eq!(quality.letter_gap, Some(2.5));
+    assert_eq!(quality.letter_gap_spread, None);
+}
+
+#[test]
+fn word_diff_pairs_missed_and_extra_words_between_copied_ones() {
+    assert_eq!(grading::word_diff("the quick brown fox jumps", "THE QUIK FOX JUMPS OVER"), vec![
+        WordDiff::Copied("THE".into()),
+        WordDiff::Miscopied { sent: "QUICK".into(), copied: "QUIK".into() },
+        WordDiff::Missed("BROWN".into()),
+        WordDiff::Copied("FOX".into()),
+        WordDiff::Copied("JUMPS".into()),
+        WordDiff::Extra("OVER".into()),
+    ]);
+    assert_eq!(grading::copied_words("the quick brown fox jumps", "THE QUIK FOX JUMPS OVER"), 3);
+}
@@ -0,0 +1,35 @@
+#ifndef MORSE_CODE_LEARNER_H
+#define MORSE_CODE_LEARNER_H
+
+/* Built with `cargo build --release -p morse-ffi`. */
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct TimingDecoder TimingDecoder;
+typedef struct Scheduler Scheduler;
+
+/* Returned strings must be released with morse_string_free. */
+char *morse_encode(const char *text);
+char *morse_decode(const char *code);
+void morse_string_free(char *s);
+
+TimingDecoder *morse_decoder_new(float wpm);
+void morse_feed_timing_event(TimingDecoder *decoder, bool key_down, float duration_ms);
+char *morse_decoder_take_text(TimingDecoder *decoder, bool flush);
+void morse_decoder_free(TimingDecoder *decoder);
+
+Scheduler *morse_scheduler_new(uint8_t level, const char *known_chars);
+char *morse_next_item(const Scheduler *scheduler);
+void morse_scheduler_record(Scheduler *scheduler, bool correct);
+void morse_scheduler_free(Scheduler *scheduler);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
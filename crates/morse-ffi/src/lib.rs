@@ -0,0 +1,151 @@
+//! C ABI for embedding the encoder, timing decoder and scheduler in other programs.
+//! Strings returned by these functions are owned by the caller and must be released
+//! with `morse_string_free`; handles are released with their matching `_free` function.
+
+use std::{
+    ffi::{c_char, CStr, CString},
+    ptr,
+};
+use morse_core::{
+    morse,
+    progression::ProgressionSystem,
+    scheduler::Scheduler,
+    timing::{TimingDecoder, TimingEvent},
+};
+
+unsafe fn str_arg<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok()
+}
+
+fn into_c_string(s: String) -> *mut c_char {
+    CString::new(s).map(CString::into_raw).unwrap_or(ptr::null_mut())
+}
+
+/// Encodes `text` into dots and dashes, returning NULL on invalid input.
+///
+/// # Safety
+/// `text` must be NULL or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn morse_encode(text: *const c_char) -> *mut c_char {
+    match str_arg(text) {
+        Some(text) => into_c_string(morse::encode(text)),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Decodes space-separated codes (words separated by `/`), returning NULL on invalid input.
+///
+/// # Safety
+/// `code` must be NULL or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn morse_decode(code: *const c_char) -> *mut c_char {
+    match str_arg(code) {
+        Some(code) => into_c_string(morse::decode(code)),
+        None => ptr::null_mut(),
+    }
+}
+
+/// # Safety
+/// `s` must be NULL or a string returned by this library that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn morse_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn morse_decoder_new(wpm: f32) -> *mut TimingDecoder {
+    Box::into_raw(Box::new(TimingDecoder::from_wpm(wpm)))
+}
+
+/// Feeds one key event: `key_down` is non-zero for a tone of `duration_ms`, zero for silence.
+///
+/// # Safety
+/// `decoder` must be a live handle from `morse_decoder_new`.
+#[no_mangle]
+pub unsafe extern "C" fn morse_feed_timing_event(
+    decoder: *mut TimingDecoder,
+    key_down: bool,
+    duration_ms: f32,
+) {
+    let Some(decoder) = decoder.as_mut() else {
+        return;
+    };
+    decoder.feed(if key_down {
+        TimingEvent::Mark(duration_ms)
+    } else {
+        TimingEvent::Space(duration_ms)
+    });
+}
+
+/// Returns the text decoded since the last call. Pass `flush` to close the current letter.
+///
+/// # Safety
+/// `decoder` must be a live handle from `morse_decoder_new`.
+#[no_mangle]
+pub unsafe extern "C" fn morse_decoder_take_text(decoder: *mut TimingDecoder, flush: bool) -> *mut c_char {
+    let Some(decoder) = decoder.as_mut() else {
+        return ptr::null_mut();
+    };
+    if flush {
+        decoder.finish();
+    }
+    into_c_string(decoder.take_text())
+}
+
+/// # Safety
+/// `decoder` must be NULL or a handle from `morse_decoder_new` that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn morse_decoder_free(decoder: *mut TimingDecoder) {
+    if !decoder.is_null() {
+        drop(Box::from_raw(decoder));
+    }
+}
+
+/// Creates the practice queue for `level`; `known_chars` may be NULL.
+///
+/// # Safety
+/// `known_chars` must be NULL or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn morse_scheduler_new(level: u8, known_chars: *const c_char) -> *mut Scheduler {
+    let known_chars: Vec<char> = str_arg(known_chars)
+        .map(|s| s.chars().map(|c| c.to_ascii_uppercase()).collect())
+        .unwrap_or_default();
+    let progression = ProgressionSystem::with_words(Vec::new());
+    let scheduler = Scheduler::for_chars(&known_chars, progression.level(level), &mut rand::rng());
+    Box::into_raw(Box::new(scheduler))
+}
+
+/// Returns the item to practice next, or NULL once the queue is empty.
+///
+/// # Safety
+/// `scheduler` must be a live handle from `morse_scheduler_new`.
+#[no_mangle]
+pub unsafe extern "C" fn morse_next_item(scheduler: *const Scheduler) -> *mut c_char {
+    match scheduler.as_ref().and_then(Scheduler::next_item) {
+        Some(item) => into_c_string(item.to_string()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// # Safety
+/// `scheduler` must be a live handle from `morse_scheduler_new`.
+#[no_mangle]
+pub unsafe extern "C" fn morse_scheduler_record(scheduler: *mut Scheduler, correct: bool) {
+    if let Some(scheduler) = scheduler.as_mut() {
+        scheduler.record(correct);
+    }
+}
+
+/// # Safety
+/// `scheduler` must be NULL or a handle from `morse_scheduler_new` that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn morse_scheduler_free(scheduler: *mut Scheduler) {
+    if !scheduler.is_null() {
+        drop(Box::from_raw(scheduler));
+    }
+}
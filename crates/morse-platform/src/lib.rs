@@ -0,0 +1,45 @@
+//! Operating-system specific pieces shared by the other crates, so that features built on
+//! them work the same on Linux, macOS and Windows.
+
+pub mod notify;
+pub mod paths;
+pub mod serial;
+pub mod speech;
+pub mod terminal;
+pub mod winkeyer;
+
+use std::process::Command;
+
+/// The operating system the program was built for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Os {
+    Linux,
+    MacOs,
+    Windows,
+    Other,
+}
+
+impl Os {
+    pub fn current() -> Self {
+        if cfg!(target_os = "linux") {
+            Os::Linux
+        } else if cfg!(target_os = "macos") {
+            Os::MacOs
+        } else if cfg!(windows) {
+            Os::Windows
+        } else {
+            Os::Other
+        }
+    }
+}
+
+/// Runs `command` through the system shell: `cmd /C` on Windows, `sh -c` elsewhere.
+pub fn shell_command(command: &str) -> Command {
+    let (shell, flag) = match Os::current() {
+        Os::Windows => ("cmd", "/C"),
+        _ => ("sh", "-c"),
+    };
+    let mut cmd = Command::new(shell);
+    cmd.args([flag, command]);
+    cmd
+}
@@ -0,0 +1,57 @@
+use std::{io, process::{Command, Stdio}, thread};
+use crate::Os;
+
+/// The command showing a desktop notification on `os`: `notify-send` on Linux,
+/// AppleScript on macOS and a PowerShell balloon tip on Windows.
+pub fn notification_command(os: Os, title: &str, body: &str) -> Option<Command> {
+    match os {
+        Os::Linux => {
+            let mut command = Command::new("notify-send");
+            command.args(["--app-name", "Morse Code Learner", title, body]);
+            Some(command)
+        }
+        Os::MacOs => {
+            let script = format!(
+                "display notification {} with title {}",
+                applescript_string(body),
+                applescript_string(title)
+            );
+            let mut command = Command::new("osascript");
+            command.args(["-e", &script]);
+            Some(command)
+        }
+        Os::Windows => {
+            let script = format!(
+                "Add-Type -AssemblyName System.Windows.Forms; \
+                 $n = New-Object System.Windows.Forms.NotifyIcon; \
+                 $n.Icon = [System.Drawing.SystemIcons]::Information; \
+                 $n.Visible = $true; \
+                 $n.ShowBalloonTip(5000, {}, {}, 'Info'); \
+                 Start-Sleep -Seconds 5; $n.Dispose()",
+                powershell_string(title),
+                powershell_string(body)
+            );
+            let mut command = Command::new("powershell");
+            command.args(["-NoProfile", "-Command", &script]);
+            Some(command)
+        }
+        Os::Other => None,
+    }
+}
+
+/// Shows a desktop notification without waiting for it to be dismissed.
+pub fn notify(title: &str, body: &str) -> io::Result<()> {
+    let mut command = notification_command(Os::current(), title, body)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "notifications are not supported on this platform"))?;
+    let mut child = command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).spawn()?;
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+pub(crate) fn powershell_string(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
@@ -0,0 +1,24 @@
+use std::path::{Path, PathBuf};
+use directories::ProjectDirs;
+
+/// Per-user data directory, e.g. `~/.local/share/morse_code_learner` on Linux,
+/// `~/Library/Application Support/morse_code_learner` on macOS and
+/// `%APPDATA%\morse_code_learner\data` on Windows.
+pub fn data_dir() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "morse_code_learner").map(|dirs| dirs.data_dir().to_path_buf())
+}
+
+/// Where the data file `name` lives. Files already in the working directory, where older
+/// versions kept them, are used in place; everything else goes to the data directory.
+pub fn data_file(name: &str) -> PathBuf {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    resolve(name, &cwd, data_dir().as_deref())
+}
+
+pub fn resolve(name: &str, cwd: &Path, data_dir: Option<&Path>) -> PathBuf {
+    let legacy = cwd.join(name);
+    match data_dir {
+        Some(dir) if !legacy.exists() => dir.join(name),
+        _ => legacy,
+    }
+}
@@ -0,0 +1,72 @@
+use std::{io::{self, Write}, time::Duration};
+use serialport::SerialPort;
+
+/// Speed the port is opened at; only the status lines are used, so it doesn't matter.
+const BAUD_RATE: u32 = 9600;
+const TIMEOUT: Duration = Duration::from_millis(10);
+
+/// Which contacts of a key are closed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Contacts {
+    /// Closed between RTS and CTS: a straight key, or the dah paddle.
+    pub cts: bool,
+    /// Closed between DTR and DSR: the dit paddle.
+    pub dsr: bool,
+}
+
+/// A key wired to the modem status lines of a serial port, like a USB-serial adapter.
+/// RTS and DTR are held up, so a closed contact shows on CTS or DSR.
+pub struct SerialKey {
+    port: Box<dyn SerialPort>,
+}
+
+impl SerialKey {
+    pub fn open(path: &str) -> io::Result<Self> {
+        let mut port = serialport::new(path, BAUD_RATE).timeout(TIMEOUT).open()?;
+        port.write_request_to_send(true)?;
+        port.write_data_terminal_ready(true)?;
+        Ok(SerialKey { port })
+    }
+
+    pub fn contacts(&mut self) -> io::Result<Contacts> {
+        Ok(Contacts {
+            cts: self.port.read_clear_to_send()?,
+            dsr: self.port.read_data_set_ready()?,
+        })
+    }
+}
+
+/// A device switched on and off through a serial port: a vibration motor or buzzer driven
+/// from RTS and DTR, or a microcontroller reading a `1` byte for on and `0` for off.
+pub struct SerialSwitch {
+    port: Box<dyn SerialPort>,
+}
+
+impl SerialSwitch {
+    /// Opens `path` with the device switched off.
+    pub fn open(path: &str) -> io::Result<Self> {
+        let port = serialport::new(path, BAUD_RATE).timeout(TIMEOUT).open()?;
+        let mut switch = SerialSwitch { port };
+        switch.set(false)?;
+        Ok(switch)
+    }
+
+    pub fn set(&mut self, on: bool) -> io::Result<()> {
+        self.port.write_request_to_send(on)?;
+        self.port.write_data_terminal_ready(on)?;
+        self.port.write_all(if on { b"1" } else { b"0" })
+    }
+}
+
+impl Drop for SerialSwitch {
+    fn drop(&mut self) {
+        let _ = self.set(false);
+    }
+}
+
+/// Names of the serial ports on the system, e.g. `/dev/ttyUSB0` or `COM3`.
+pub fn ports() -> Vec<String> {
+    serialport::available_ports()
+        .map(|ports| ports.into_iter().map(|p| p.port_name).collect())
+        .unwrap_or_default()
+}
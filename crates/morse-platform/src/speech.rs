@@ -0,0 +1,41 @@
+use std::{io, process::{Command, Stdio}};
+use crate::{notify::powershell_string, Os};
+
+/// The command saying `text` aloud on `os`: espeak-ng on Linux, `say` on macOS and the
+/// .NET speech synthesizer through PowerShell on Windows.
+pub fn speech_command(os: Os, text: &str) -> Option<Command> {
+    match os {
+        Os::Linux => {
+            let mut command = Command::new("espeak-ng");
+            command.arg(text);
+            Some(command)
+        }
+        Os::MacOs => {
+            let mut command = Command::new("say");
+            command.arg(text);
+            Some(command)
+        }
+        Os::Windows => {
+            let script = format!(
+                "Add-Type -AssemblyName System.Speech; \
+                 (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak({})",
+                powershell_string(text)
+            );
+            let mut command = Command::new("powershell");
+            command.args(["-NoProfile", "-Command", &script]);
+            Some(command)
+        }
+        Os::Other => None,
+    }
+}
+
+/// Says `text` aloud, returning once it has been said.
+pub fn speak(text: &str) -> io::Result<()> {
+    let mut command = speech_command(Os::current(), text)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "speech is not supported on this platform"))?;
+    let status = command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("{:?} exited with {}", command.get_program(), status)));
+    }
+    Ok(())
+}
@@ -0,0 +1,405 @@
+use std::{
+    io::{self, BufRead, IsTerminal, Write},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
+    KeyboardEnhancementFlags, MouseEvent, MouseEventKind, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+};
+use crate::Os;
+
+/// Whether both stdin and stdout are attached to a terminal rather than pipes or files.
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+const PASTE_START: &str = "\x1b[200~";
+const PASTE_END: &str = "\x1b[201~";
+
+/// Asks the terminal to mark pasted text for as long as the guard lives, so pastes can be
+/// told apart from typing. Terminals without support simply ignore the request.
+pub struct BracketedPaste(());
+
+impl BracketedPaste {
+    pub fn enable() -> Option<Self> {
+        if !is_interactive() {
+            return None;
+        }
+        print!("\x1b[?2004h");
+        io::stdout().flush().ok()?;
+        Some(BracketedPaste(()))
+    }
+}
+
+impl Drop for BracketedPaste {
+    fn drop(&mut self) {
+        print!("\x1b[?2004l");
+        let _ = io::stdout().flush();
+    }
+}
+
+/// Failed reads in a row, not counting interruptions, before input is taken as gone.
+const READ_ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Runs `read` until it succeeds, riding out interrupted reads and brief failures such as
+/// those a terminal resize can cause. `None` once it has failed `READ_ATTEMPTS` times in
+/// a row.
+pub fn retrying<T>(mut read: impl FnMut() -> io::Result<T>) -> Option<T> {
+    let mut failures = 0;
+    loop {
+        match read() {
+            Ok(value) => return Some(value),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => {
+                failures += 1;
+                if failures == READ_ATTEMPTS {
+                    eprintln!("Error reading input: {}", e);
+                    return None;
+                }
+                thread::sleep(RETRY_DELAY);
+            }
+        }
+    }
+}
+
+/// Reads a line from stdin; see [`read_line_from`].
+pub fn read_line() -> Option<String> {
+    read_line_from(&mut io::stdin().lock())
+}
+
+/// Reads a line, retrying failed reads and keeping what was read before them. `None` at
+/// the end of input, or once reading keeps failing.
+pub fn read_line_from(reader: &mut impl BufRead) -> Option<String> {
+    let mut line = String::new();
+    let read = retrying(|| reader.read_line(&mut line))?;
+    (read > 0 || !line.is_empty()).then_some(line)
+}
+
+/// Removes bracketed-paste markers from a line of input, reporting whether any were present.
+pub fn strip_paste_markers(input: &str) -> (String, bool) {
+    let pasted = input.contains(PASTE_START);
+    (input.replace(PASTE_START, "").replace(PASTE_END, ""), pasted)
+}
+
+/// Unbuffered, unechoed terminal input for as long as the guard lives, so single key
+/// presses can be read as they happen. Ctrl+C and Ctrl+Z arrive as key presses instead of
+/// signals, so the caller can restore the terminal before quitting, and printed lines need
+/// a carriage return of their own until it's dropped.
+pub struct RawMode(());
+
+impl RawMode {
+    pub fn enable() -> io::Result<Self> {
+        if !io::stdin().is_terminal() {
+            return Err(io::Error::other("stdin is not a terminal"));
+        }
+        crossterm::terminal::enable_raw_mode()?;
+        Ok(RawMode(()))
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        if let Err(e) = crossterm::terminal::disable_raw_mode() {
+            eprintln!("Error restoring terminal: {}", e);
+        }
+    }
+}
+
+/// The terminal's width in columns: asked of the terminal when output goes to one, else taken
+/// from `COLUMNS`. `None` when output goes to a pipe or file.
+pub fn width() -> Option<usize> {
+    if !io::stdout().is_terminal() {
+        return None;
+    }
+    let asked = match Os::current() {
+        Os::Windows => None,
+        _ if io::stdin().is_terminal() => stty(&["size"]).ok().and_then(|size| columns_from_size(&size)),
+        _ => None,
+    };
+    asked.or_else(|| std::env::var("COLUMNS").ok()?.trim().parse().ok()).filter(|&w| w > 0)
+}
+
+/// The columns in the output of `stty size`, which gives rows then columns.
+pub fn columns_from_size(size: &str) -> Option<usize> {
+    let mut parts = size.split_whitespace();
+    let _rows: usize = parts.next()?.parse().ok()?;
+    parts.next()?.parse().ok().filter(|&w| w > 0)
+}
+
+fn stty(args: &[&str]) -> io::Result<String> {
+    // stty works on the terminal on its stdin, which `output` would otherwise close.
+    let output = Command::new("stty").args(args).stdin(Stdio::inherit()).output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// A key typed in raw mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Backspace,
+    /// Ctrl+C.
+    Interrupt,
+    /// Ctrl+D.
+    EndOfInput,
+    /// Text pasted in one go, from a terminal that marks pastes.
+    Paste(String),
+}
+
+/// The key typed in `event`. `None` for keys that type nothing, such as the arrows, Escape
+/// and Alt or Ctrl with a letter, and for key releases.
+pub fn typed_key(event: &Event) -> Option<Key> {
+    let key = match event {
+        Event::Key(key) if key.kind != KeyEventKind::Release => key,
+        Event::Paste(text) => return Some(Key::Paste(text.clone())),
+        _ => return None,
+    };
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Char('c') if ctrl => Some(Key::Interrupt),
+        KeyCode::Char('d') if ctrl => Some(Key::EndOfInput),
+        KeyCode::Char(_) if ctrl || key.modifiers.contains(KeyModifiers::ALT) => None,
+        KeyCode::Char(c) => Some(Key::Char(c)),
+        KeyCode::Enter => Some(Key::Enter),
+        KeyCode::Backspace => Some(Key::Backspace),
+        _ => None,
+    }
+}
+
+/// A key typed in raw mode, and when it arrived.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keystroke {
+    pub key: Key,
+    pub at: Instant,
+}
+
+/// Keys one at a time as they are typed, unechoed and with Ctrl+C as a plain key, for as
+/// long as the guard lives. Fails where [`RawMode`] does, e.g. on a pipe.
+pub struct Keystrokes {
+    raw: RawMode,
+}
+
+impl Keystrokes {
+    pub fn enable() -> io::Result<Self> {
+        Ok(Keystrokes { raw: RawMode::enable()? })
+    }
+
+    /// Restores the terminal, e.g. before quitting on Ctrl+C.
+    pub fn disable(self) {
+        drop(self.raw);
+    }
+}
+
+impl Iterator for Keystrokes {
+    type Item = Keystroke;
+
+    /// Waits for the next key; `None` at the end of input or once reading keeps failing.
+    fn next(&mut self) -> Option<Keystroke> {
+        loop {
+            let event = retrying(event::read)?;
+            if let Some(key) = typed_key(&event) {
+                return Some(Keystroke { key, at: Instant::now() });
+            }
+        }
+    }
+}
+
+/// Whether a key went down, is auto-repeating, or came up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAction {
+    Press,
+    Repeat,
+    Release,
+}
+
+/// Private-use code points standing for the arrow keys, which have none of their own.
+pub const LEFT_ARROW: u32 = 0xf702;
+pub const RIGHT_ARROW: u32 = 0xf703;
+
+/// A key going down, repeating or coming up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyReport {
+    /// The key's Unicode code point, e.g. 32 for the spacebar and 13 for Enter, or one of
+    /// the arrow constants.
+    pub code: u32,
+    pub ctrl: bool,
+    pub action: KeyAction,
+}
+
+/// The key report for a key event, or `None` for keys no keying mode uses.
+pub fn key_report(event: &KeyEvent) -> Option<KeyReport> {
+    let code = match event.code {
+        KeyCode::Char(c) => c.to_ascii_lowercase() as u32,
+        KeyCode::Enter => 13,
+        KeyCode::Left => LEFT_ARROW,
+        KeyCode::Right => RIGHT_ARROW,
+        _ => return None,
+    };
+    Some(KeyReport {
+        code,
+        ctrl: event.modifiers.contains(KeyModifiers::CONTROL),
+        action: match event.kind {
+            KeyEventKind::Press => KeyAction::Press,
+            KeyEventKind::Repeat => KeyAction::Repeat,
+            KeyEventKind::Release => KeyAction::Release,
+        },
+    })
+}
+
+/// The next event and when it arrived, waiting at most `timeout`.
+fn next_event(timeout: Duration) -> io::Result<Option<(Event, Instant)>> {
+    if !event::poll(timeout)? {
+        return Ok(None);
+    }
+    Ok(Some((event::read()?, Instant::now())))
+}
+
+/// Presses and releases of every key, as they happen, for as long as the guard lives. The
+/// Windows console reports releases itself; elsewhere it needs a terminal with the kitty
+/// keyboard protocol, such as kitty, WezTerm, foot, Ghostty, Alacritty or iTerm2, as plain
+/// terminals only ever send what a key types.
+pub struct KeyReports {
+    _raw: RawMode,
+    /// Whether the protocol was turned on, and so has to be turned off again.
+    enhanced: bool,
+    /// Keys down, so the Windows console's repeated presses read as repeats.
+    held: Vec<u32>,
+}
+
+impl KeyReports {
+    pub fn enable() -> io::Result<Self> {
+        let raw = RawMode::enable()?;
+        if Os::current() == Os::Windows {
+            return Ok(KeyReports { _raw: raw, enhanced: false, held: Vec::new() });
+        }
+        if !crossterm::terminal::supports_keyboard_enhancement()? {
+            return Err(io::Error::new(io::ErrorKind::Unsupported, "the terminal doesn't report key releases"));
+        }
+        crossterm::execute!(
+            io::stdout(),
+            PushKeyboardEnhancementFlags(
+                KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                    | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+                    | KeyboardEnhancementFlags::REPORT_ALL_KEYS_AS_ESCAPE_CODES
+            )
+        )?;
+        Ok(KeyReports { _raw: raw, enhanced: true, held: Vec::new() })
+    }
+
+    /// The next key report and when it arrived, waiting at most `timeout`.
+    pub fn next(&mut self, timeout: Duration) -> io::Result<Option<(KeyReport, Instant)>> {
+        let deadline = Instant::now() + timeout;
+        while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+            let Some((Event::Key(event), at)) = next_event(left)? else {
+                continue;
+            };
+            let Some(mut report) = key_report(&event) else {
+                continue;
+            };
+            match report.action {
+                KeyAction::Press if self.held.contains(&report.code) => report.action = KeyAction::Repeat,
+                KeyAction::Press => self.held.push(report.code),
+                KeyAction::Release => self.held.retain(|&code| code != report.code),
+                KeyAction::Repeat => {}
+            }
+            return Ok(Some((report, at)));
+        }
+        Ok(None)
+    }
+}
+
+impl Drop for KeyReports {
+    fn drop(&mut self) {
+        if self.enhanced {
+            let _ = crossterm::execute!(io::stdout(), PopKeyboardEnhancementFlags);
+        }
+    }
+}
+
+/// A mouse button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+}
+
+/// A mouse button going down or coming up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseReport {
+    pub button: MouseButton,
+    pub down: bool,
+}
+
+/// The button change in a mouse event. `None` for anything else, including movement, drags
+/// and the scroll wheel.
+pub fn mouse_report(event: &MouseEvent) -> Option<MouseReport> {
+    let (button, down) = match event.kind {
+        MouseEventKind::Down(button) => (button, true),
+        MouseEventKind::Up(button) => (button, false),
+        _ => return None,
+    };
+    let button = match button {
+        event::MouseButton::Left => MouseButton::Left,
+        event::MouseButton::Middle => MouseButton::Middle,
+        event::MouseButton::Right => MouseButton::Right,
+    };
+    Some(MouseReport { button, down })
+}
+
+/// A mouse button, or a key pressed on the keyboard, while mouse buttons are reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseInput {
+    Button(MouseReport),
+    Enter,
+    /// Ctrl+C.
+    Interrupt,
+}
+
+/// Presses and releases of the mouse buttons over the terminal, and Enter and Ctrl+C typed
+/// alongside them, for as long as the guard lives. Almost every terminal emulator reports
+/// the mouse, though tmux and screen pass it on only with their mouse support turned on.
+/// While it lives, clicks no longer select text or open the terminal's menu.
+pub struct MouseReports {
+    _raw: RawMode,
+}
+
+impl MouseReports {
+    pub fn enable() -> io::Result<Self> {
+        let raw = RawMode::enable()?;
+        crossterm::execute!(io::stdout(), EnableMouseCapture)?;
+        Ok(MouseReports { _raw: raw })
+    }
+
+    /// The next button change or key and when it arrived, waiting at most `timeout`.
+    pub fn next(&mut self, timeout: Duration) -> io::Result<Option<(MouseInput, Instant)>> {
+        let deadline = Instant::now() + timeout;
+        while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+            let input = match next_event(left)? {
+                Some((Event::Mouse(event), at)) => mouse_report(&event).map(|report| (MouseInput::Button(report), at)),
+                Some((Event::Key(event), at)) if event.kind == KeyEventKind::Press => match key_report(&event) {
+                    Some(KeyReport { code: 13, .. }) => Some((MouseInput::Enter, at)),
+                    Some(KeyReport { code, ctrl: true, .. }) if code == 'c' as u32 => Some((MouseInput::Interrupt, at)),
+                    _ => None,
+                },
+                _ => None,
+            };
+            if input.is_some() {
+                return Ok(input);
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Drop for MouseReports {
+    fn drop(&mut self) {
+        let _ = crossterm::execute!(io::stdout(), DisableMouseCapture);
+    }
+}
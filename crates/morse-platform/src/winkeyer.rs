@@ -0,0 +1,148 @@
+//! The host protocol of K1EL WinKeyer keyers (WK2 and later), spoken over their USB-serial
+//! port: the keyer sends text at a set speed on its own sidetone and key line, and echoes
+//! back the characters keyed on its paddles.
+
+use std::{io::{self, Read, Write}, time::{Duration, Instant}};
+use serialport::{SerialPort, StopBits};
+
+const BAUD_RATE: u32 = 1200;
+/// How long the keyer gets to answer when the host opens it.
+const OPEN_TIMEOUT: Duration = Duration::from_millis(500);
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+/// Sending that hasn't started by then isn't coming.
+const START_TIMEOUT: Duration = Duration::from_millis(500);
+
+const ADMIN: u8 = 0x00;
+const HOST_OPEN: u8 = 0x02;
+const HOST_CLOSE: u8 = 0x03;
+const SET_SPEED: u8 = 0x02;
+const SET_MODE: u8 = 0x0E;
+/// Mode register bits.
+const PADDLE_ECHO: u8 = 0x40;
+const PADDLE_SWAP: u8 = 0x08;
+const MIN_WPM: u8 = 5;
+const MAX_WPM: u8 = 99;
+/// Characters the keyer sends besides letters and digits; the other bytes are commands.
+const PUNCTUATION: &str = " \"$'()+,-./:;=?@";
+
+/// How the keyer turns paddle presses into elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaddleMode {
+    IambicB,
+    IambicA,
+    Ultimatic,
+    Bug,
+}
+
+impl PaddleMode {
+    fn bits(self) -> u8 {
+        match self {
+            PaddleMode::IambicB => 0x00,
+            PaddleMode::IambicA => 0x10,
+            PaddleMode::Ultimatic => 0x20,
+            PaddleMode::Bug => 0x30,
+        }
+    }
+}
+
+/// A byte the keyer sends back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reply {
+    /// The keyer's state changed; `busy` while it's sending.
+    Status { busy: bool },
+    /// The speed pot moved, to this many WPM above its minimum.
+    SpeedPot(u8),
+    /// A character keyed on the paddles.
+    Echo(char),
+}
+
+pub fn parse_reply(byte: u8) -> Reply {
+    match byte & 0xC0 {
+        0xC0 => Reply::Status { busy: byte & 0x04 != 0 },
+        0x80 => Reply::SpeedPot(byte & 0x3F),
+        _ => Reply::Echo(byte as char),
+    }
+}
+
+/// A WinKeyer opened for the host; it's closed again, going back to standalone keying,
+/// when dropped.
+pub struct WinKeyer {
+    port: Box<dyn SerialPort>,
+    /// Firmware version reported when it was opened.
+    pub version: u8,
+}
+
+impl WinKeyer {
+    pub fn open(path: &str) -> io::Result<Self> {
+        let mut port = serialport::new(path, BAUD_RATE)
+            .stop_bits(StopBits::Two)
+            .timeout(OPEN_TIMEOUT)
+            .open()?;
+        // The keyer's interface is powered from DTR, and RTS must stay low.
+        port.write_data_terminal_ready(true)?;
+        port.write_request_to_send(false)?;
+        port.write_all(&[ADMIN, HOST_OPEN])?;
+        let mut version = [0u8];
+        if port.read_exact(&mut version).is_err() {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "no WinKeyer answered"));
+        }
+        port.set_timeout(POLL_INTERVAL)?;
+        Ok(WinKeyer { port, version: version[0] })
+    }
+
+    pub fn set_speed(&mut self, wpm: f32) -> io::Result<()> {
+        let wpm = (wpm.round() as u8).clamp(MIN_WPM, MAX_WPM);
+        self.port.write_all(&[SET_SPEED, wpm])
+    }
+
+    /// Sets how the paddles key, with the paddle echo that answers are read from.
+    pub fn set_mode(&mut self, mode: PaddleMode, swap: bool) -> io::Result<()> {
+        let swap = if swap { PADDLE_SWAP } else { 0 };
+        self.port.write_all(&[SET_MODE, PADDLE_ECHO | mode.bits() | swap])
+    }
+
+    /// Sends `text` on the keyer and waits until it has been sent, or `limit` has passed.
+    /// Characters the keyer can't send are left out.
+    pub fn send(&mut self, text: &str, limit: Duration) -> io::Result<()> {
+        let text: String = text.to_uppercase().chars()
+            .filter(|c| c.is_ascii_alphanumeric() || PUNCTUATION.contains(*c))
+            .collect();
+        self.port.write_all(text.as_bytes())?;
+        let start = Instant::now();
+        let mut started = false;
+        while start.elapsed() < limit {
+            match self.next(POLL_INTERVAL)? {
+                Some(Reply::Status { busy: true }) => started = true,
+                Some(Reply::Status { busy: false }) if started => break,
+                None if !started && start.elapsed() >= START_TIMEOUT => break,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// The next byte from the keyer, waiting up to `timeout`.
+    pub fn next(&mut self, timeout: Duration) -> io::Result<Option<Reply>> {
+        let deadline = Instant::now() + timeout;
+        let mut byte = [0u8];
+        loop {
+            match self.port.read(&mut byte) {
+                Ok(1) => return Ok(Some(parse_reply(byte[0]))),
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::TimedOut => {}
+                Err(e) => return Err(e),
+            }
+            if Instant::now() >= deadline {
+                return Ok(None);
+            }
+        }
+    }
+}
+
+impl Drop for WinKeyer {
+    fn drop(&mut self) {
+        if let Err(e) = self.port.write_all(&[ADMIN, HOST_CLOSE]) {
+            eprintln!("Error closing the WinKeyer: {}", e);
+        }
+    }
+}
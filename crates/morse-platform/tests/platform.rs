@@ -0,0 +1,178 @@
+use std::{
+    fs,
+    io::{self, BufReader, Read},
+    path::PathBuf,
+    process::Command,
+};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent, MouseEventKind};
+use morse_platform::{notify, paths, serial::SerialKey, shell_command, speech, terminal::{self, Key, KeyAction, KeyReport, MouseButton, MouseReport, RawMode}, winkeyer::{self, Reply}, Os};
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("morse-platform-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn args(command: &Command) -> Vec<String> {
+    command.get_args().map(|a| a.to_string_lossy().into_owned()).collect()
+}
+
+#[test]
+fn data_files_go_to_the_data_dir() {
+    let cwd = scratch_dir("fresh");
+    let data = cwd.join("data");
+    assert_eq!(paths::resolve("morse_stats.toml", &cwd, Some(&data)), data.join("morse_stats.toml"));
+}
+
+#[test]
+fn existing_files_in_the_working_directory_are_kept() {
+    let cwd = scratch_dir("legacy");
+    fs::write(cwd.join("morse_stats.toml"), "").unwrap();
+    let resolved = paths::resolve("morse_stats.toml", &cwd, Some(&cwd.join("data")));
+    assert_eq!(resolved, cwd.join("morse_stats.toml"));
+}
+
+#[test]
+fn working_directory_is_used_without_a_data_dir() {
+    let cwd = scratch_dir("nohome");
+    assert_eq!(paths::resolve("morse_config.toml", &cwd, None), cwd.join("morse_config.toml"));
+}
+
+#[test]
+fn notification_commands() {
+    let linux = notify::notification_command(Os::Linux, "Level up", "Level 3").unwrap();
+    assert_eq!(linux.get_program(), "notify-send");
+    assert!(args(&linux).ends_with(&["Level up".to_string(), "Level 3".to_string()]));
+
+    let mac = notify::notification_command(Os::MacOs, "Say \"hi\"", "it's").unwrap();
+    assert_eq!(mac.get_program(), "osascript");
+    assert_eq!(args(&mac)[1], "display notification \"it's\" with title \"Say \\\"hi\\\"\"");
+
+    let windows = notify::notification_command(Os::Windows, "Level up", "it's done").unwrap();
+    assert_eq!(windows.get_program(), "powershell");
+    assert!(args(&windows)[2].contains("'Level up', 'it''s done'"));
+
+    assert!(notify::notification_command(Os::Other, "a", "b").is_none());
+}
+
+#[test]
+fn speech_commands() {
+    let linux = speech::speech_command(Os::Linux, "K M R").unwrap();
+    assert_eq!(linux.get_program(), "espeak-ng");
+    assert_eq!(args(&linux), ["K M R"]);
+
+    assert_eq!(speech::speech_command(Os::MacOs, "the").unwrap().get_program(), "say");
+
+    let windows = speech::speech_command(Os::Windows, "it's").unwrap();
+    assert_eq!(windows.get_program(), "powershell");
+    assert!(args(&windows)[2].ends_with(".Speak('it''s')"));
+
+    assert!(speech::speech_command(Os::Other, "a").is_none());
+}
+
+#[test]
+fn shell_command_runs_through_the_system_shell() {
+    let output = shell_command("echo hello").output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "hello");
+}
+
+/// Input that fails with each of `errors` in turn before giving `data`.
+struct Flaky {
+    errors: Vec<io::ErrorKind>,
+    data: &'static [u8],
+}
+
+impl Read for Flaky {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if let Some(kind) = self.errors.pop() {
+            return Err(kind.into());
+        }
+        self.data.read(buf)
+    }
+}
+
+#[test]
+fn reads_survive_brief_failures_and_end_with_the_input() {
+    let mut input = BufReader::new(Flaky {
+        errors: vec![io::ErrorKind::Other, io::ErrorKind::Interrupted, io::ErrorKind::Other],
+        data: b"-.-.\n",
+    });
+    assert_eq!(terminal::read_line_from(&mut input).as_deref(), Some("-.-.\n"));
+    assert_eq!(terminal::read_line_from(&mut input), None);
+
+    let mut broken = BufReader::new(Flaky { errors: vec![io::ErrorKind::Other; 3], data: b"-.-.\n" });
+    assert_eq!(terminal::read_line_from(&mut broken), None);
+}
+
+#[test]
+fn raw_mode_needs_a_terminal() {
+    if !std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+        assert!(RawMode::enable().is_err());
+    }
+}
+
+#[test]
+fn key_reports_tell_presses_from_releases() {
+    let report = |code, modifiers, kind| terminal::key_report(&KeyEvent::new_with_kind(code, modifiers, kind));
+    assert_eq!(report(KeyCode::Char(' '), KeyModifiers::NONE, KeyEventKind::Press), Some(KeyReport { code: 32, ctrl: false, action: KeyAction::Press }));
+    assert_eq!(report(KeyCode::Char(' '), KeyModifiers::NONE, KeyEventKind::Repeat).map(|r| r.action), Some(KeyAction::Repeat));
+    assert_eq!(report(KeyCode::Char(' '), KeyModifiers::NONE, KeyEventKind::Release).map(|r| r.action), Some(KeyAction::Release));
+    assert_eq!(report(KeyCode::Char('c'), KeyModifiers::CONTROL, KeyEventKind::Press).map(|r| r.ctrl), Some(true));
+    // Shift doesn't make a different key.
+    assert_eq!(report(KeyCode::Char('Z'), KeyModifiers::SHIFT, KeyEventKind::Press).map(|r| r.code), Some('z' as u32));
+    assert_eq!(report(KeyCode::Left, KeyModifiers::NONE, KeyEventKind::Release), Some(KeyReport { code: terminal::LEFT_ARROW, ctrl: false, action: KeyAction::Release }));
+    assert_eq!(report(KeyCode::Right, KeyModifiers::NONE, KeyEventKind::Press).map(|r| r.code), Some(terminal::RIGHT_ARROW));
+    assert_eq!(report(KeyCode::Enter, KeyModifiers::NONE, KeyEventKind::Press).map(|r| r.code), Some(13));
+    assert_eq!(report(KeyCode::F(1), KeyModifiers::NONE, KeyEventKind::Press), None);
+}
+
+#[test]
+fn typed_keys_leave_out_keys_that_type_nothing() {
+    let key = |code, modifiers| terminal::typed_key(&Event::Key(KeyEvent::new(code, modifiers)));
+    assert_eq!(key(KeyCode::Char('.'), KeyModifiers::NONE), Some(Key::Char('.')));
+    assert_eq!(key(KeyCode::Char('c'), KeyModifiers::CONTROL), Some(Key::Interrupt));
+    assert_eq!(key(KeyCode::Char('d'), KeyModifiers::CONTROL), Some(Key::EndOfInput));
+    assert_eq!(key(KeyCode::Enter, KeyModifiers::NONE), Some(Key::Enter));
+    assert_eq!(key(KeyCode::Backspace, KeyModifiers::NONE), Some(Key::Backspace));
+    assert_eq!(key(KeyCode::Esc, KeyModifiers::NONE), None);
+    assert_eq!(key(KeyCode::Left, KeyModifiers::NONE), None);
+    assert_eq!(key(KeyCode::Char('x'), KeyModifiers::ALT), None);
+    let released = KeyEvent::new_with_kind(KeyCode::Char('.'), KeyModifiers::NONE, KeyEventKind::Release);
+    assert_eq!(terminal::typed_key(&Event::Key(released)), None);
+    assert_eq!(terminal::typed_key(&Event::Paste("-.-.".into())), Some(Key::Paste("-.-.".into())));
+    assert_eq!(terminal::strip_paste_markers("\x1b[200~-.-.\x1b[201~"), ("-.-.".to_string(), true));
+}
+
+#[test]
+fn mouse_reports_tell_buttons_and_releases() {
+    let report = |kind| terminal::mouse_report(&MouseEvent { kind, column: 12, row: 5, modifiers: KeyModifiers::NONE });
+    assert_eq!(report(MouseEventKind::Down(event::MouseButton::Left)), Some(MouseReport { button: MouseButton::Left, down: true }));
+    assert_eq!(report(MouseEventKind::Up(event::MouseButton::Right)), Some(MouseReport { button: MouseButton::Right, down: false }));
+    // Movement, drags and the wheel aren't buttons.
+    assert_eq!(report(MouseEventKind::Moved), None);
+    assert_eq!(report(MouseEventKind::Drag(event::MouseButton::Left)), None);
+    assert_eq!(report(MouseEventKind::ScrollUp), None);
+}
+
+#[test]
+fn terminal_width_comes_from_stty_size() {
+    assert_eq!(terminal::columns_from_size("24 80\n"), Some(80));
+    assert_eq!(terminal::columns_from_size("50 36"), Some(36));
+    assert_eq!(terminal::columns_from_size("0 0"), None);
+    assert_eq!(terminal::columns_from_size(""), None);
+}
+
+#[test]
+fn serial_keys_need_a_port() {
+    let missing = scratch_dir("serial").join("ttyUSB9");
+    assert!(SerialKey::open(missing.to_str().unwrap()).is_err());
+}
+
+#[test]
+fn winkeyer_replies_are_told_apart() {
+    assert_eq!(winkeyer::parse_reply(0xC4), Reply::Status { busy: true });
+    assert_eq!(winkeyer::parse_reply(0xC0), Reply::Status { busy: false });
+    assert_eq!(winkeyer::parse_reply(0x8F), Reply::SpeedPot(15));
+    assert_eq!(winkeyer::parse_reply(b'K'), Reply::Echo('K'));
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "python")]
+//! Python bindings, built with `maturin develop` from this directory.
+
+use std::{collections::HashMap, path::PathBuf};
+use chrono::{DateTime, FixedOffset};
+use pyo3::{exceptions::PyIOError, prelude::*};
+use morse_core::{
+    morse,
+    progression::ProgressionSystem,
+    scheduler::Scheduler,
+};
+use morse_stats::{LearningSession, UserStats};
+
+#[pyfunction]
+fn encode(text: &str) -> String {
+    morse::encode(text)
+}
+
+#[pyfunction]
+fn decode(code: &str) -> String {
+    morse::decode(code)
+}
+
+#[pyfunction]
+fn char_to_morse(c: char) -> Option<&'static str> {
+    morse::char_to_morse(c)
+}
+
+#[pyclass(name = "Scheduler")]
+struct PyScheduler {
+    inner: Scheduler,
+}
+
+#[pymethods]
+impl PyScheduler {
+    /// Builds the character queue for `level`, mixing in `known_chars` like a regular session.
+    #[staticmethod]
+    #[pyo3(signature = (level, known_chars = Vec::new()))]
+    fn for_level(level: u8, known_chars: Vec<char>) -> Self {
+        let progression = ProgressionSystem::with_words(Vec::new());
+        PyScheduler {
+            inner: Scheduler::for_chars(&known_chars, progression.level(level), &mut rand::rng()),
+        }
+    }
+
+    #[staticmethod]
+    fn for_words(words: Vec<String>) -> Self {
+        let words: Vec<String> = words.iter().map(|w| w.to_uppercase()).collect();
+        PyScheduler {
+            inner: Scheduler::for_words(&words, &mut rand::rng()),
+        }
+    }
+
+    fn next_item(&self) -> Option<String> {
+        self.inner.next_item().map(|s| s.to_string())
+    }
+
+    fn record(&mut self, correct: bool) {
+        self.inner.record(correct);
+    }
+
+    fn items(&self) -> Vec<String> {
+        self.inner.iter().cloned().collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+#[pyclass(name = "Session", get_all)]
+#[derive(Clone)]
+struct PySession {
+    timestamp: DateTime<FixedOffset>,
+    duration: u32,
+    chars_practiced: Vec<char>,
+    words_practiced: Vec<String>,
+    accuracy: f32,
+    difficulty: u8,
+}
+
+impl From<&LearningSession> for PySession {
+    fn from(session: &LearningSession) -> Self {
+        PySession {
+            timestamp: session.timestamp,
+            duration: session.duration,
+            chars_practiced: session.chars_practiced.clone(),
+            words_practiced: session.words_practiced.clone(),
+            accuracy: session.accuracy,
+            difficulty: session.difficulty,
+        }
+    }
+}
+
+#[pyclass(name = "Stats", get_all)]
+struct PyStats {
+    sessions_completed: u32,
+    chars_learned: u32,
+    words_learned: u32,
+    accuracy: f32,
+    response_times: HashMap<char, f32>,
+    word_response_times: HashMap<String, f32>,
+    session_history: Vec<PySession>,
+}
+
+#[pymethods]
+impl PyStats {
+    /// Loads a stats file, defaulting to `morse_stats.toml` in the working directory.
+    #[staticmethod]
+    #[pyo3(signature = (path = None))]
+    fn load(path: Option<PathBuf>) -> PyResult<Self> {
+        let path = path.unwrap_or_else(UserStats::stats_path);
+        let stats = UserStats::load_from(&path)
+            .map_err(|e| PyIOError::new_err(e.to_string()))?;
+        
+        Ok(PyStats {
+            sessions_completed: stats.sessions_completed,
+            chars_learned: stats.chars_learned,
+            words_learned: stats.words_learned,
+            accuracy: stats.accuracy,
+            response_times: stats.response_times,
+            word_response_times: stats.word_response_times,
+            session_history: stats.session_history.iter().map(PySession::from).collect(),
+        })
+    }
+}
+
+#[pymodule]
+fn morse_code_learner(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(encode, m)?)?;
+    m.add_function(wrap_pyfunction!(decode, m)?)?;
+    m.add_function(wrap_pyfunction!(char_to_morse, m)?)?;
+    m.add_class::<PyScheduler>()?;
+    m.add_class::<PySession>()?;
+    m.add_class::<PyStats>()?;
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+use morse_stats::DeviceSession;
+
+const RECENT_SESSIONS: usize = 50;
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+pub fn render(sessions: &[DeviceSession]) -> String {
+    let mut devices: BTreeMap<&str, Vec<&DeviceSession>> = BTreeMap::new();
+    for s in sessions {
+        devices.entry(&s.device).or_default().push(s);
+    }
+
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Morse Code Learner</title>\
+         <style>body{font-family:sans-serif;margin:2em}table{border-collapse:collapse;margin-bottom:2em}\
+         td,th{border:1px solid #ccc;padding:4px 10px;text-align:left}</style></head><body>\n\
+         <h1>Morse Code Learner</h1>\n",
+    );
+
+    let minutes = |passive: bool| sessions.iter()
+        .filter(|s| s.session.passive == passive)
+        .map(|s| s.session.duration)
+        .sum::<u32>() / 60;
+    html.push_str(&format!(
+        "<p>{} sessions from {} devices, {} minutes of active practice and {} of passive listening.</p>\n",
+        sessions.len(),
+        devices.len(),
+        minutes(false),
+        minutes(true)
+    ));
+
+    html.push_str("<h2>Devices</h2>\n<table><tr><th>Device</th><th>Sessions</th><th>Average accuracy</th><th>Highest level</th><th>Last session</th></tr>\n");
+    for (device, list) in &devices {
+        let accuracy = list.iter().map(|s| s.session.accuracy).sum::<f32>() / list.len() as f32;
+        let level = list.iter().map(|s| s.session.difficulty).max().unwrap_or(0);
+        let last = list.iter().map(|s| s.session.timestamp).max().map(|t| t.to_rfc3339()).unwrap_or_default();
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{:.1}%</td><td>{}</td><td>{}</td></tr>\n",
+            escape(device),
+            list.len(),
+            accuracy * 100.0,
+            level,
+            escape(&last)
+        ));
+    }
+    html.push_str("</table>\n");
+
+    let mut recent: Vec<&DeviceSession> = sessions.iter().collect();
+    recent.sort_by_key(|s| std::cmp::Reverse(s.session.timestamp));
+    html.push_str("<h2>Recent sessions</h2>\n<table><tr><th>Time</th><th>Device</th><th>Level</th><th>Duration</th><th>Kind</th><th>Accuracy</th></tr>\n");
+    for s in recent.iter().take(RECENT_SESSIONS) {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}m {}s</td><td>{}</td><td>{:.1}%</td></tr>\n",
+            escape(&s.session.timestamp.to_rfc3339()),
+            escape(&s.device),
+            s.session.difficulty,
+            s.session.duration / 60,
+            s.session.duration % 60,
+            if s.session.passive { "passive" } else { "active" },
+            s.session.accuracy * 100.0
+        ));
+    }
+    html.push_str("</table>\n</body></html>\n");
+    html
+}
@@ -0,0 +1,116 @@
+mod dashboard;
+mod http;
+mod store;
+
+use std::{
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+use clap::Parser;
+use morse_stats::{DeviceSession, LearningSession};
+use serde_json::json;
+use http::{Request, Response};
+use store::Store;
+
+/// Companion server collecting practice sessions from several devices.
+#[derive(Debug, Parser)]
+#[command(version)]
+struct Args {
+    /// Address to listen on; use 0.0.0.0:7373 to accept other machines on the network
+    #[arg(long, default_value = "127.0.0.1:7373")]
+    bind: String,
+    /// Where uploaded sessions are stored, defaults to the per-user data directory
+    #[arg(long)]
+    data: Option<PathBuf>,
+    /// Shared secret clients must send; without it anyone who can reach the server may upload
+    #[arg(long)]
+    token: Option<String>,
+}
+
+fn handle(request: &Request, store: &Mutex<Store>, token: Option<&str>) -> Response {
+    if let Some(token) = token {
+        let sent = request.bearer_token().or(request.query.get("token").map(String::as_str));
+        if sent != Some(token) {
+            return Response::error(401, "missing or wrong token");
+        }
+    }
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/") => Response::html(dashboard::render(&store.lock().unwrap().sessions)),
+        ("GET", "/api/sessions") => {
+            let exclude = request.query.get("exclude");
+            let store = store.lock().unwrap();
+            let sessions: Vec<&DeviceSession> = store.sessions.iter()
+                .filter(|s| Some(&s.device) != exclude)
+                .collect();
+            Response::json(&json!(sessions))
+        }
+        ("POST", "/api/sessions") => {
+            let device = match request.query.get("device").filter(|d| !d.trim().is_empty()) {
+                Some(device) => device.trim(),
+                None => return Response::error(400, "missing device name"),
+            };
+            let sessions: Vec<LearningSession> = match serde_json::from_slice(&request.body) {
+                Ok(sessions) => sessions,
+                Err(e) => return Response::error(400, &format!("invalid sessions: {}", e)),
+            };
+            match store.lock().unwrap().add(device, sessions) {
+                Ok(added) => Response::json(&json!({ "added": added })),
+                Err(e) => {
+                    eprintln!("Error saving sessions: {}", e);
+                    Response::error(500, "could not save sessions")
+                }
+            }
+        }
+        _ => Response::error(404, "not found"),
+    }
+}
+
+fn serve(stream: TcpStream, store: &Mutex<Store>, token: Option<&str>) {
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(30)));
+    let response = match Request::read(&stream) {
+        Ok(request) => handle(&request, store, token),
+        Err(e) => Response::error(400, &e.to_string()),
+    };
+    if let Err(e) = response.write(&stream) {
+        eprintln!("Error writing response: {}", e);
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    let path = args.data.unwrap_or_else(|| morse_platform::paths::data_file("morse_sync_sessions.json"));
+    let store = match Store::open(path.clone()) {
+        Ok(store) => Arc::new(Mutex::new(store)),
+        Err(e) => {
+            eprintln!("Error opening {}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+    };
+    let listener = match TcpListener::bind(&args.bind) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Error listening on {}: {}", args.bind, e);
+            std::process::exit(1);
+        }
+    };
+    println!("Serving the dashboard on http://{} with sessions stored in {}", args.bind, path.display());
+    if args.token.is_none() {
+        println!("No --token set: anyone who can reach this address can upload and read sessions.");
+    }
+
+    let token: Arc<Option<String>> = Arc::new(args.token);
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let store = Arc::clone(&store);
+                let token = Arc::clone(&token);
+                thread::spawn(move || serve(stream, &store, token.as_deref()));
+            }
+            Err(e) => eprintln!("Error accepting connection: {}", e),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::{fs, path::PathBuf};
+use morse_stats::DeviceSession;
+
+/// All uploaded sessions, persisted as one JSON file.
+pub struct Store {
+    path: PathBuf,
+    pub sessions: Vec<DeviceSession>,
+}
+
+impl Store {
+    pub fn open(path: PathBuf) -> Result<Self, Box<dyn std::error::Error>> {
+        let sessions = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            Vec::new()
+        };
+        Ok(Store { path, sessions })
+    }
+
+    /// Adds the sessions not uploaded before and returns how many were new. Sessions a device
+    /// pulled from another one are recognized by their timestamp and keep their original device.
+    pub fn add(&mut self, device: &str, sessions: Vec<morse_stats::LearningSession>) -> Result<usize, Box<dyn std::error::Error>> {
+        let mut added = 0;
+        for session in sessions {
+            let known = self.sessions.iter().any(|s| s.session.timestamp == session.timestamp);
+            if !known {
+                self.sessions.push(DeviceSession { device: device.to_string(), session });
+                added += 1;
+            }
+        }
+        if added > 0 {
+            self.save()?;
+        }
+        Ok(added)
+    }
+
+    fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string(&self.sessions)?)?;
+        fs::rename(tmp, &self.path)?;
+        Ok(())
+    }
+}
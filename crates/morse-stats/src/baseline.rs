@@ -0,0 +1,48 @@
+use crate::{LearningSession, UserStats};
+
+/// Sessions the rolling baseline averages over.
+pub const BASELINE_SESSIONS: usize = 10;
+
+/// Your usual accuracy and response time, averaged over recent sessions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Baseline {
+    pub sessions: usize,
+    pub accuracy: f32,
+    /// Mean response time in seconds; `None` when no timed answers were recorded.
+    pub response_time: Option<f32>,
+}
+
+impl LearningSession {
+    /// Mean response time in seconds over the answers whose time says something about
+    /// speed, i.e. not pasted, too fast or hinted.
+    pub fn mean_response_time(&self) -> Option<f32> {
+        let times: Vec<f32> = self.answers.iter()
+            .filter(|a| !a.suspect && !a.hinted)
+            .map(|a| a.response_time)
+            .collect();
+        (!times.is_empty()).then(|| times.iter().sum::<f32>() / times.len() as f32)
+    }
+}
+
+impl UserStats {
+    /// The baseline of up to [`BASELINE_SESSIONS`] sessions practiced here before the
+    /// `before`th one in the history, leaving out imported results and sessions without
+    /// answers. `None` when there are none.
+    pub fn baseline(&self, before: usize) -> Option<Baseline> {
+        let recent: Vec<&LearningSession> = self.session_history[..before.min(self.session_history.len())]
+            .iter()
+            .rev()
+            .filter(|s| s.external.is_none() && !s.answers.is_empty())
+            .take(BASELINE_SESSIONS)
+            .collect();
+        if recent.is_empty() {
+            return None;
+        }
+        let times: Vec<f32> = recent.iter().filter_map(|s| s.mean_response_time()).collect();
+        Some(Baseline {
+            sessions: recent.len(),
+            accuracy: recent.iter().map(|s| s.accuracy).sum::<f32>() / recent.len() as f32,
+            response_time: (!times.is_empty()).then(|| times.iter().sum::<f32>() / times.len() as f32),
+        })
+    }
+}
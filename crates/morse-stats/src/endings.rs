@@ -0,0 +1,85 @@
+use chrono::{DateTime, TimeZone};
+use serde_derive::{Serialize, Deserialize};
+use crate::UserStats;
+
+/// Why a session ended.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EndReason {
+    /// Every item was practiced.
+    Completed,
+    /// The session's time ran out.
+    Timeout,
+    /// Stopped with `q`.
+    Quit,
+    /// Input closed, e.g. with Ctrl+D.
+    Closed,
+}
+
+impl EndReason {
+    /// Stopped by the learner rather than by the session.
+    pub fn stopped_early(self) -> bool {
+        matches!(self, EndReason::Quit | EndReason::Closed)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SessionEnding {
+    pub reason: EndReason,
+    /// Items answered before the end.
+    pub items: usize,
+    /// The item on screen when the session was stopped.
+    pub item: Option<String>,
+    /// How long the session was meant to last.
+    pub planned_secs: u32,
+}
+
+/// How sessions ended, counted by reason and, for sessions stopped early, by which third
+/// of the planned time they were stopped in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EndingPatterns {
+    pub completed: usize,
+    pub timeout: usize,
+    pub quit: usize,
+    pub closed: usize,
+    pub stopped_by_third: [usize; 3],
+    /// Total time into the sessions stopped early.
+    pub stopped_secs: u64,
+}
+
+impl EndingPatterns {
+    pub fn sessions(&self) -> usize {
+        self.completed + self.timeout + self.quit + self.closed
+    }
+
+    pub fn stopped(&self) -> usize {
+        self.quit + self.closed
+    }
+}
+
+impl UserStats {
+    /// How the sessions with a recorded ending ended, all of them or those from `since` on.
+    pub fn ending_patterns<Tz: TimeZone>(&self, since: Option<&DateTime<Tz>>) -> EndingPatterns {
+        let mut patterns = EndingPatterns::default();
+        for session in &self.session_history {
+            let Some(ending) = &session.ending else {
+                continue;
+            };
+            if since.is_some_and(|since| session.timestamp < *since) {
+                continue;
+            }
+            match ending.reason {
+                EndReason::Completed => patterns.completed += 1,
+                EndReason::Timeout => patterns.timeout += 1,
+                EndReason::Quit => patterns.quit += 1,
+                EndReason::Closed => patterns.closed += 1,
+            }
+            if ending.reason.stopped_early() {
+                let through = session.duration as f32 / ending.planned_secs.max(1) as f32;
+                patterns.stopped_by_third[((through * 3.0) as usize).min(2)] += 1;
+                patterns.stopped_secs += session.duration as u64;
+            }
+        }
+        patterns
+    }
+}
@@ -0,0 +1,210 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use chrono::{DateTime, FixedOffset};
+use serde_derive::{Serialize, Deserialize};
+use serde::{Deserialize, Serialize};
+
+mod baseline;
+mod endings;
+mod practice_time;
+mod recovery;
+mod speed_test;
+mod streak;
+mod strength;
+mod sync;
+
+pub use baseline::{Baseline, BASELINE_SESSIONS};
+pub use endings::{EndReason, EndingPatterns, SessionEnding};
+pub use practice_time::PracticeTime;
+pub use recovery::StatsRecovery;
+pub use speed_test::{SpeedTestHistory, SpeedTestResult};
+pub use streak::{practice_day, Streak};
+pub use strength::{Strength, FADING_BELOW};
+pub use sync::DeviceSession;
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct UserStats {
+    pub sessions_completed: u32,
+    pub chars_learned: u32,
+    pub words_learned: u32,
+    pub accuracy: f32,
+    #[serde(serialize_with = "serialize_response_times")]
+    #[serde(deserialize_with = "deserialize_response_times")]
+    pub response_times: HashMap<char, f32>,
+    pub word_response_times: HashMap<String, f32>,
+    pub session_history: Vec<LearningSession>,
+}
+
+fn serialize_response_times<S>(
+    map: &HashMap<char, f32>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let string_map: HashMap<String, f32> = map
+        .iter()
+        .map(|(k, v)| (k.to_string(), *v))
+        .collect();
+    string_map.serialize(serializer)
+}
+
+fn deserialize_response_times<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<char, f32>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let string_map = HashMap::<String, f32>::deserialize(deserializer)?;
+    let char_map = string_map
+        .into_iter()
+        .filter_map(|(k, v)| k.chars().next().map(|c| (c, v)))
+        .collect();
+    Ok(char_map)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LearningSession {
+    pub timestamp: DateTime<FixedOffset>,
+    pub duration: u32,
+    pub chars_practiced: Vec<char>,
+    pub words_practiced: Vec<String>,
+    pub accuracy: f32,
+    pub difficulty: u8,
+    #[serde(default)]
+    pub answers: Vec<AnswerRecord>,
+    /// Missing for sessions recorded before settings were stored.
+    #[serde(default)]
+    pub settings: Option<SessionSettings>,
+    /// Set for results imported from other training programs.
+    #[serde(default)]
+    pub external: Option<ExternalResult>,
+    /// The passages of a head-copy session, which has no per-item answers.
+    #[serde(default)]
+    pub head_copy: Vec<HeadCopyPassage>,
+    /// Listened to without answering, as in audio-only practice.
+    #[serde(default)]
+    pub passive: bool,
+    /// How evenly the answers were keyed, for sessions answered on a real key.
+    #[serde(default)]
+    pub sending: Option<SendingRecord>,
+    /// How and when the session ended; missing for sessions without a queue of items.
+    #[serde(default)]
+    pub ending: Option<SessionEnding>,
+    /// Practiced in a way that doesn't count toward level progression, such as copying,
+    /// echoing or generated material.
+    #[serde(default)]
+    pub outside_progression: bool,
+}
+
+/// Timing of a session's keying; gaps are in the sender's own dots.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct SendingRecord {
+    pub dash_dot_ratio: Option<f32>,
+    pub element_gap: Option<f32>,
+    pub element_gap_spread: Option<f32>,
+    pub letter_gap: Option<f32>,
+    pub letter_gap_spread: Option<f32>,
+}
+
+/// Words or a sentence streamed without a break and copied in one go at the end.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HeadCopyPassage {
+    pub sent: String,
+    pub copied: String,
+    pub words: usize,
+    pub words_copied: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AnswerRecord {
+    pub item: String,
+    pub expected: String,
+    pub answer: String,
+    pub correct: bool,
+    pub response_time: f32,
+    /// Pasted or implausibly fast, so the time says nothing about keying speed.
+    #[serde(default)]
+    pub suspect: bool,
+    /// The code was shown on request before answering.
+    #[serde(default)]
+    pub hinted: bool,
+    /// How closely the keying followed the rhythm of the code played, 0 to 100; set for
+    /// echoed answers with more than one element.
+    #[serde(default)]
+    pub rhythm: Option<f32>,
+    /// Computed difficulty of the item, from its code length, dashes and rarity.
+    #[serde(default)]
+    pub difficulty: Option<f32>,
+    /// Missing for answers recorded before items were tagged.
+    #[serde(default)]
+    pub novelty: Option<Novelty>,
+}
+
+/// Whether an item was new material or going over what was learned before.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Novelty {
+    /// Has a character introduced at the session's level, or was never asked before.
+    New,
+    /// Asked again after a while away from it.
+    RetentionCheck,
+    /// Practiced recently.
+    Review,
+}
+
+/// How the code was played back during a session.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SessionSettings {
+    pub output: String,
+    pub pitch_hz: f32,
+    pub dot_ms: u32,
+    pub dash_ms: u32,
+}
+
+/// A result from another training program such as a contest simulator.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExternalResult {
+    pub source: String,
+    pub score: Option<u32>,
+    pub wpm: Option<f32>,
+}
+
+impl UserStats {
+    pub fn stats_path() -> PathBuf {
+        morse_platform::paths::data_file("morse_stats.toml")
+    }
+
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::load_from(&Self::stats_path())
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        if path.exists() {
+            let data = fs::read_to_string(path)?;
+            Self::from_toml(&data)
+        } else {
+            Ok(UserStats::default())
+        }
+    }
+
+    pub fn from_toml(data: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        toml::from_str(data).map_err(|e| e.into())
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::stats_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if path.exists() {
+            fs::copy(&path, Self::backup_path())?;
+        }
+        let data = toml::to_string(self)?;
+        fs::write(path, data)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use chrono::{DateTime, TimeZone};
+use crate::UserStats;
+
+/// Time spent on Morse, split into graded drills and passive listening.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PracticeTime {
+    /// In sessions with answers to give, here or in other programs.
+    pub active_secs: u64,
+    /// Listening without answering, as in audio-only practice.
+    pub passive_secs: u64,
+}
+
+impl PracticeTime {
+    /// All time spent hearing Morse, active or not.
+    pub fn exposure_secs(&self) -> u64 {
+        self.active_secs + self.passive_secs
+    }
+}
+
+impl UserStats {
+    /// Practice time in all sessions, or in those from `since` on.
+    pub fn practice_time<Tz: TimeZone>(&self, since: Option<&DateTime<Tz>>) -> PracticeTime {
+        let mut time = PracticeTime::default();
+        for session in &self.session_history {
+            if since.is_some_and(|since| session.timestamp < *since) {
+                continue;
+            }
+            match session.passive {
+                true => time.passive_secs += session.duration as u64,
+                false => time.active_secs += session.duration as u64,
+            }
+        }
+        time
+    }
+}
@@ -0,0 +1,112 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use toml::{Table, Value};
+use crate::{LearningSession, UserStats};
+
+const SESSION_HEADER: &str = "[[session_history]]";
+
+/// What happened when a stats file could not be deserialized.
+#[derive(Debug)]
+pub struct StatsRecovery {
+    pub error: String,
+    pub quarantined_to: PathBuf,
+    pub recovered_sessions: usize,
+    pub lost_sessions: usize,
+    pub backup: Option<PathBuf>,
+}
+
+impl UserStats {
+    pub fn backup_path() -> PathBuf {
+        Self::stats_path().with_extension("toml.bak")
+    }
+
+    /// Loads the stats file. If it exists but cannot be parsed, it is moved aside and as
+    /// much as possible is salvaged from it instead of silently starting from scratch.
+    pub fn load_or_recover() -> Result<(Self, Option<StatsRecovery>), Box<dyn std::error::Error>> {
+        let path = Self::stats_path();
+        if !path.exists() {
+            return Ok((UserStats::default(), None));
+        }
+
+        let data = fs::read_to_string(&path)?;
+        let error = match Self::from_toml(&data) {
+            Ok(stats) => return Ok((stats, None)),
+            Err(e) => e.to_string(),
+        };
+
+        let quarantined_to = quarantine(&path)?;
+        let (stats, lost_sessions) = Self::recover_partial(&data);
+        let backup = Some(Self::backup_path()).filter(|p| p.exists());
+
+        let recovery = StatsRecovery {
+            error,
+            quarantined_to,
+            recovered_sessions: stats.session_history.len(),
+            lost_sessions,
+            backup,
+        };
+        Ok((stats, Some(recovery)))
+    }
+
+    /// Salvages whatever fields and sessions still parse, returning the number of
+    /// sessions that had to be dropped.
+    pub fn recover_partial(data: &str) -> (Self, usize) {
+        let mut chunks = data.split(SESSION_HEADER);
+        let head = chunks.next().unwrap_or_default();
+
+        let mut stats = UserStats::default();
+        let head = head.parse::<Table>().unwrap_or_default();
+        if let Some(v) = field::<u32>(&head, "sessions_completed") {
+            stats.sessions_completed = v;
+        }
+        if let Some(v) = field::<u32>(&head, "chars_learned") {
+            stats.chars_learned = v;
+        }
+        if let Some(v) = field::<u32>(&head, "words_learned") {
+            stats.words_learned = v;
+        }
+        if let Some(v) = field::<f32>(&head, "accuracy") {
+            stats.accuracy = v;
+        }
+        if let Some(times) = field::<HashMap<String, f32>>(&head, "response_times") {
+            stats.response_times = times
+                .into_iter()
+                .filter_map(|(k, v)| k.chars().next().map(|c| (c, v)))
+                .collect();
+        }
+        if let Some(times) = field(&head, "word_response_times") {
+            stats.word_response_times = times;
+        }
+
+        let mut lost = 0;
+        for chunk in chunks {
+            // Nested tables such as `[session_history.settings]` and `[[session_history.answers]]`
+            // belong to this chunk's session.
+            let chunk = chunk.replace("[session_history.", "[");
+            match toml::from_str::<LearningSession>(&chunk) {
+                Ok(session) => stats.session_history.push(session),
+                Err(_) => lost += 1,
+            }
+        }
+
+        let total = (stats.session_history.len() + lost) as u32;
+        stats.sessions_completed = stats.sessions_completed.max(total);
+        (stats, lost)
+    }
+}
+
+fn field<T: serde::de::DeserializeOwned>(table: &Table, key: &str) -> Option<T> {
+    table.get(key).cloned().and_then(|v: Value| v.try_into().ok())
+}
+
+fn quarantine(path: &Path) -> std::io::Result<PathBuf> {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".corrupt-{}", stamp));
+    let target = path.with_file_name(name);
+    fs::rename(path, &target)?;
+    Ok(target)
+}
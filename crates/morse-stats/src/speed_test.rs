@@ -0,0 +1,70 @@
+use std::{fs, path::PathBuf};
+use chrono::{DateTime, FixedOffset};
+use serde_derive::{Serialize, Deserialize};
+
+/// The result of one speed test, kept apart from practice sessions.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SpeedTestResult {
+    pub timestamp: DateTime<FixedOffset>,
+    pub wpm: f32,
+    /// How long the text took to send.
+    pub minutes: f32,
+    pub sent: usize,
+    pub errors: usize,
+    /// Longest run of characters copied without an error.
+    pub solid: usize,
+    /// The rules the test was judged by, e.g. `accuracy:90,consecutive:3`.
+    pub rules: String,
+    pub passed: bool,
+}
+
+impl SpeedTestResult {
+    pub fn copied(&self) -> usize {
+        self.sent.saturating_sub(self.errors)
+    }
+
+    /// Errors as a share of the characters sent, from 0 to 1.
+    pub fn error_rate(&self) -> f32 {
+        if self.sent == 0 {
+            return 0.0;
+        }
+        (self.errors as f32 / self.sent as f32).min(1.0)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct SpeedTestHistory {
+    #[serde(default)]
+    pub tests: Vec<SpeedTestResult>,
+}
+
+impl SpeedTestHistory {
+    pub fn path() -> PathBuf {
+        morse_platform::paths::data_file("morse_speed_tests.toml")
+    }
+
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(SpeedTestHistory::default());
+        }
+        let data = fs::read_to_string(&path)?;
+        toml::from_str(&data).map_err(|e| e.into())
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// The fastest speed a test was passed at.
+    pub fn best_pass(&self) -> Option<&SpeedTestResult> {
+        self.tests.iter()
+            .filter(|t| t.passed)
+            .max_by(|a, b| a.wpm.total_cmp(&b.wpm))
+    }
+}
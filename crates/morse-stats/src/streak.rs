@@ -0,0 +1,48 @@
+use std::collections::BTreeSet;
+use chrono::{DateTime, NaiveDate, TimeDelta, TimeZone};
+use crate::UserStats;
+
+/// Days in a row with practice.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Streak {
+    /// Ending today, or yesterday while today's practice is still to come.
+    pub current: u32,
+    pub longest: u32,
+    pub practiced_today: bool,
+}
+
+/// The day `time` counts toward in `tz` when days start at `day_starts_at` o'clock rather
+/// than midnight, so with 4 a.m. a session at 1 a.m. still counts toward the evening before.
+pub fn practice_day<From: TimeZone, Tz: TimeZone>(time: &DateTime<From>, tz: &Tz, day_starts_at: u32) -> NaiveDate {
+    (time.with_timezone(tz).naive_local() - TimeDelta::hours(day_starts_at as i64)).date()
+}
+
+impl UserStats {
+    /// The practice streak as of `now`, with days counted in `tz` from `day_starts_at`
+    /// o'clock. Results imported from other programs don't count.
+    pub fn streak<From: TimeZone, Tz: TimeZone>(&self, now: &DateTime<From>, tz: &Tz, day_starts_at: u32) -> Streak {
+        let days: BTreeSet<NaiveDate> = self.session_history.iter()
+            .filter(|s| s.external.is_none())
+            .map(|s| practice_day(&s.timestamp, tz, day_starts_at))
+            .collect();
+
+        let mut longest = 0;
+        let mut run = 0;
+        let mut previous: Option<NaiveDate> = None;
+        for &day in &days {
+            run = if previous.is_some() && previous == day.pred_opt() { run + 1 } else { 1 };
+            longest = longest.max(run);
+            previous = Some(day);
+        }
+
+        let today = practice_day(now, tz, day_starts_at);
+        let practiced_today = days.contains(&today);
+        let mut day = if practiced_today { Some(today) } else { today.pred_opt() };
+        let mut current = 0;
+        while let Some(d) = day.filter(|d| days.contains(d)) {
+            current += 1;
+            day = d.pred_opt();
+        }
+        Streak { current, longest, practiced_today }
+    }
+}
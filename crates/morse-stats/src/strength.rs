@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+use chrono::{DateTime, FixedOffset, TimeZone};
+use crate::UserStats;
+
+/// Days an unpracticed character's strength takes to halve after it's first recalled.
+pub const INITIAL_HALF_LIFE_DAYS: f32 = 1.0;
+/// Longest half-life, so even well-known characters fade when they're never practiced.
+pub const MAX_HALF_LIFE_DAYS: f32 = 90.0;
+/// Strength below which a character counts as fading and is due for review.
+pub const FADING_BELOW: f32 = 0.5;
+/// How much a recall stretches the half-life when the character had fully faded; recalls
+/// of a still-fresh character stretch it less, so spaced practice pays off most.
+const GROWTH: f32 = 2.0;
+
+/// How well a character is remembered: strength decays exponentially while it goes
+/// unpracticed, and every recall makes the memory last longer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Strength {
+    /// 0 to 1, as of the time the strengths were computed for.
+    pub strength: f32,
+    pub half_life_days: f32,
+    pub last_practiced: DateTime<FixedOffset>,
+}
+
+impl Strength {
+    pub fn is_fading(&self) -> bool {
+        self.strength < FADING_BELOW
+    }
+
+    fn at(&self, time: &DateTime<FixedOffset>) -> f32 {
+        let days = (*time - self.last_practiced).num_seconds().max(0) as f32 / 86_400.0;
+        self.strength * 0.5f32.powf(days / self.half_life_days)
+    }
+
+    /// A correct answer restores full strength and stretches the half-life; a miss, or
+    /// needing the hint, halves the strength and the half-life.
+    fn answer(&mut self, time: DateTime<FixedOffset>, recalled: bool) {
+        let before = self.at(&time);
+        if recalled {
+            self.half_life_days = (self.half_life_days * (1.0 + GROWTH * (1.0 - before))).min(MAX_HALF_LIFE_DAYS);
+            self.strength = 1.0;
+        } else {
+            self.half_life_days = (self.half_life_days / 2.0).max(INITIAL_HALF_LIFE_DAYS);
+            self.strength = before / 2.0;
+        }
+        self.last_practiced = time;
+    }
+}
+
+impl UserStats {
+    /// The strength of every character answered so far, as of `now`.
+    pub fn strengths<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> BTreeMap<char, Strength> {
+        let mut strengths: BTreeMap<char, Strength> = BTreeMap::new();
+        for session in &self.session_history {
+            for answer in &session.answers {
+                let mut chars = answer.item.chars();
+                let (Some(c), None) = (chars.next(), chars.next()) else {
+                    continue;
+                };
+                let recalled = answer.correct && !answer.hinted;
+                strengths.entry(c)
+                    .and_modify(|s| s.answer(session.timestamp, recalled))
+                    .or_insert(Strength {
+                        strength: if recalled { 1.0 } else { FADING_BELOW / 2.0 },
+                        half_life_days: INITIAL_HALF_LIFE_DAYS,
+                        last_practiced: session.timestamp,
+                    });
+            }
+        }
+        let now = now.fixed_offset();
+        for s in strengths.values_mut() {
+            s.strength = s.at(&now);
+        }
+        strengths
+    }
+}
@@ -0,0 +1,32 @@
+use serde_derive::{Serialize, Deserialize};
+use crate::{LearningSession, UserStats};
+
+/// A session as exchanged with the sync server, tagged with the device it was practiced on.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeviceSession {
+    pub device: String,
+    #[serde(flatten)]
+    pub session: LearningSession,
+}
+
+impl UserStats {
+    /// Adds the sessions not already in the history, identified by their timestamp, and
+    /// keeps the history in chronological order. Returns how many were added.
+    pub fn merge_sessions(&mut self, sessions: impl IntoIterator<Item = LearningSession>) -> usize {
+        let mut added = 0;
+        let mut accuracy_sum = self.accuracy * self.sessions_completed as f32;
+        for session in sessions {
+            if !self.session_history.iter().any(|s| s.timestamp == session.timestamp) {
+                accuracy_sum += session.accuracy;
+                self.session_history.push(session);
+                added += 1;
+            }
+        }
+        if added > 0 {
+            self.session_history.sort_by_key(|s| s.timestamp);
+            self.sessions_completed += added as u32;
+            self.accuracy = accuracy_sum / self.sessions_completed as f32;
+        }
+        added
+    }
+}
@@ -0,0 +1,47 @@
+use chrono::DateTime;
+use morse_stats::{EndReason, LearningSession, SendingRecord, SessionEnding, SessionSettings, UserStats};
+
+fn session(accuracy: f32) -> LearningSession {
+    LearningSession {
+        timestamp: DateTime::parse_from_rfc3339("2025-06-14T18:30:00+02:00").unwrap(),
+        duration: 300,
+        chars_practiced: vec!['K', 'M'],
+        words_practiced: Vec::new(),
+        accuracy,
+        difficulty: 1,
+        answers: Vec::new(),
+        settings: Some(SessionSettings { output: "audio".into(), pitch_hz: 600.0, dot_ms: 80, dash_ms: 240 }),
+        external: None,
+        head_copy: Vec::new(),
+        passive: false,
+        sending: Some(SendingRecord {
+            dash_dot_ratio: Some(3.1),
+            element_gap: Some(1.0),
+            element_gap_spread: None,
+            letter_gap: Some(3.2),
+            letter_gap_spread: None,
+        }),
+        ending: Some(SessionEnding { reason: EndReason::Quit, items: 12, item: Some("K".into()), planned_secs: 600 }),
+        outside_progression: false,
+    }
+}
+
+#[test]
+fn recovered_sessions_keep_their_settings_sending_and_ending() {
+    let stats = UserStats {
+        session_history: vec![session(0.9), session(0.8)],
+        ..UserStats::default()
+    };
+    let mut data = toml::to_string(&stats).unwrap();
+    data.push_str("\n[[session_history]]\nduration = \"broken\"\n");
+    assert!(UserStats::from_toml(&data).is_err());
+
+    let (recovered, lost) = UserStats::recover_partial(&data);
+    assert_eq!(lost, 1);
+    assert_eq!(recovered.session_history.len(), 2);
+    for s in &recovered.session_history {
+        assert_eq!(s.settings.as_ref().map(|s| s.dot_ms), Some(80));
+        assert_eq!(s.sending.and_then(|s| s.dash_dot_ratio), Some(3.1));
+        assert_eq!(s.ending.as_ref().map(|e| e.reason), Some(EndReason::Quit));
+    }
+}
@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use morse_cli::{ban::BanList, logbook::{self, Logbook}};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        let records = logbook::parse_adif(text);
+        let mut logbook = Logbook::default();
+        logbook.add_records(&records);
+        let _ = logbook.items(&BanList::default());
+    }
+});
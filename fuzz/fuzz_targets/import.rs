@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use morse_cli::import::{self, ImportSource};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        let _ = import::parse(ImportSource::MorseRunner, text);
+        let _ = import::parse(ImportSource::RufzXp, text);
+    }
+});
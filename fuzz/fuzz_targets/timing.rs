@@ -0,0 +1,21 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use morse_core::timing::{self, TimingDecoder};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(events) = timing::parse_timing_trace(text) else {
+        return;
+    };
+    if let Some(unit_ms) = timing::estimate_unit_ms(&events) {
+        let mut decoder = TimingDecoder::new(unit_ms);
+        for event in &events {
+            decoder.feed(*event);
+        }
+        decoder.finish();
+        let _ = decoder.take_text();
+    }
+});